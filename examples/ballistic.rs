@@ -0,0 +1,88 @@
+//! Fires a projectile without any rendering and checks where it ends up
+//!
+//! Runs a fixed number of physics steps and compares the result to the closed form solution,
+//! exiting with an error if they differ. Run with `cargo run --example ballistic`.
+
+use std::{process::ExitCode, time::Duration};
+
+use bevy::{
+    app::App,
+    asset::{AssetApp, AssetPlugin},
+    gizmos::GizmoPlugin,
+    math::Vec3,
+    render::render_resource::Shader,
+    time::{Fixed, Time, TimeUpdateStrategy},
+    MinimalPlugins,
+};
+
+use physics::{
+    components::{
+        acceleration::Accelerator, inertia::Inertia, velocity::AngularVelocity, velocity::Velocity,
+        SimulationBundle,
+    },
+    interpolation::StepTransforms,
+    resources::{PhysicsSettings, UpAxis},
+    SimulatiorPlugin,
+};
+
+const STEPS: u32 = 256;
+const STEP: Duration = Duration::from_micros(15625); // 1/64 s
+const LAUNCH: Vec3 = Vec3::new(30.0, 30.0, 0.0);
+const TOLERANCE: f32 = 1e-2;
+
+fn main() -> ExitCode {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, AssetPlugin::default()));
+    // The debug arrows still need somewhere to draw to
+    app.init_asset::<Shader>();
+    app.add_plugins((GizmoPlugin, SimulatiorPlugin::default()));
+
+    // One fixed step every update, so the number of steps is exact
+    app.insert_resource(TimeUpdateStrategy::ManualDuration(STEP));
+    app.insert_resource(Time::<Fixed>::from_duration(STEP));
+
+    let projectile = app
+        .world_mut()
+        .spawn(SimulationBundle::new(
+            Velocity(LAUNCH),
+            Accelerator::ZERO,
+            AngularVelocity::ZERO,
+            Inertia::sphere(0.1, 1.0),
+        ))
+        .id();
+
+    while app.world().resource::<Time<Fixed>>().elapsed() < STEP * STEPS {
+        app.update();
+    }
+
+    let world = app.world();
+    let t = world.resource::<Time<Fixed>>().elapsed_seconds();
+    let gravity = world
+        .resource::<PhysicsSettings>()
+        .gravity_for(*world.resource::<UpAxis>())
+        .0;
+
+    // The rendered transform lags behind, the last step is what the physics produced
+    let position = world
+        .get::<StepTransforms>(projectile)
+        .unwrap()
+        .current
+        .translation;
+    let velocity = world.get::<Velocity>(projectile).unwrap().0;
+
+    let expected_position = LAUNCH * t + gravity * t.powi(2) / 2.0;
+    let expected_velocity = LAUNCH + gravity * t;
+
+    println!("after {t} s:");
+    println!("  position {position}, expected {expected_position}");
+    println!("  velocity {velocity}, expected {expected_velocity}");
+
+    if position.abs_diff_eq(expected_position, TOLERANCE)
+        && velocity.abs_diff_eq(expected_velocity, TOLERANCE)
+    {
+        ExitCode::SUCCESS
+    } else {
+        eprintln!("projectile drifted more than {TOLERANCE} from the exact solution");
+        ExitCode::FAILURE
+    }
+}
@@ -0,0 +1,1047 @@
+use std::{f32::consts::TAU, io};
+
+use bevy::{
+    ecs::{
+        entity::Entity,
+        query::{Has, With},
+        system::{Query, Res, ResMut, Resource},
+    },
+    math::Vec3,
+    time::Time,
+    transform::components::Transform,
+    utils::HashMap,
+};
+
+use crate::{
+    components::{
+        inertia::Inertia,
+        spring::Spring,
+        velocity::{AngularVelocity, Velocity},
+        GravityOverride, Orbiting, Simulated,
+    },
+    resources::{CentralBody, PhysicsSettings, SimClock, UpAxis},
+};
+
+/// The state of a single object at the end of a frame
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BodySample {
+    pub position: Vec3,
+    pub velocity: Vec3,
+    pub angular_velocity: Vec3,
+    pub speed: f32,
+}
+
+/// The latest [BodySample] of every simulated object, keyed by its [Entity]
+///
+/// Refreshed every frame by [sample_bodies] so analysis code doesn't need its own queries.
+#[derive(Resource, Debug, Default)]
+pub struct BodySamples(HashMap<Entity, BodySample>);
+
+impl BodySamples {
+    /// Gets the latest sample of an entity, if it is being simulated
+    #[must_use]
+    pub fn get(&self, entity: Entity) -> Option<&BodySample> {
+        self.0.get(&entity)
+    }
+}
+
+/// Aggregate numbers over every simulated object, refreshed every frame by [scene_stats]
+#[derive(Resource, Debug, Default, Clone, Copy, PartialEq)]
+pub struct SceneStats {
+    /// Number of simulated objects
+    pub bodies: usize,
+
+    /// Sum of the translational and rotational kinetic energy of every object
+    pub kinetic_energy: f32,
+
+    /// Sum of the gravitational and [Spring] potential energy of every object
+    ///
+    /// Zero at the origin for gravity, infinitely far away for the [CentralBody] and at the anchor
+    /// for springs.
+    pub potential_energy: f32,
+
+    /// The fastest object and its speed
+    pub fastest: Option<(Entity, f32)>,
+
+    /// The object furthest up along the [UpAxis] and its altitude
+    pub highest: Option<(Entity, f32)>,
+
+    /// Mass weighted average position of every non-static object, see [center_of_mass]
+    pub center_of_mass: Option<Vec3>,
+}
+
+impl SceneStats {
+    /// Kinetic plus potential energy, constant for a perfect simulation without any losses
+    #[must_use]
+    pub fn total_energy(&self) -> f32 {
+        self.kinetic_energy + self.potential_energy
+    }
+}
+
+/// Warns when the [total energy](SceneStats::total_energy) drifts too far, a sign that the
+/// timestep is too long for the simulation to stay stable
+///
+/// The energy is compared to the energy at the start of a window, and at most one warning is
+/// logged per window.
+#[derive(Resource, Debug)]
+pub struct EnergyMonitor {
+    /// Largest allowed drift, as a fraction of the energy at the start of the window
+    pub tolerance: f32,
+
+    /// Length of a window in seconds
+    pub window: f32,
+
+    start: Option<f32>,
+    elapsed: f32,
+    warned: bool,
+}
+
+impl EnergyMonitor {
+    /// Create a new [EnergyMonitor], the first energy it sees starts the first window
+    #[must_use]
+    pub const fn new(tolerance: f32, window: f32) -> Self {
+        Self {
+            tolerance,
+            window,
+            start: None,
+            elapsed: 0.0,
+            warned: false,
+        }
+    }
+
+    /// Feeds the energy `delta` seconds after the last one
+    ///
+    /// Returns the relative drift the first time in a window it is past the tolerance.
+    ///
+    /// ```rust
+    /// # use physics::analysis::EnergyMonitor;
+    /// let mut monitor = EnergyMonitor::new(0.1, 10.0);
+    ///
+    /// assert_eq!(monitor.update(100.0, 0.0), None);
+    /// assert_eq!(monitor.update(105.0, 1.0), None);
+    /// assert_eq!(monitor.update(120.0, 1.0), Some(0.2));
+    ///
+    /// // Only once per window
+    /// assert_eq!(monitor.update(130.0, 1.0), None);
+    /// ```
+    pub fn update(&mut self, energy: f32, delta: f32) -> Option<f32> {
+        self.elapsed += delta;
+
+        let start = match self.start {
+            Some(start) if self.elapsed < self.window => start,
+            _ => {
+                self.start = Some(energy);
+                self.elapsed = 0.0;
+                self.warned = false;
+                return None;
+            }
+        };
+
+        // Nothing to compare a drift to, any change is infinitely large
+        if start == 0.0 {
+            return None;
+        }
+
+        let drift = (energy - start) / start.abs();
+
+        if self.warned || drift.abs() <= self.tolerance {
+            return None;
+        }
+
+        self.warned = true;
+        Some(drift)
+    }
+}
+
+impl Default for EnergyMonitor {
+    fn default() -> Self {
+        Self::new(0.05, 10.0)
+    }
+}
+
+/// Kinetic, potential and total energy of the scene at a point in time
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EnergySample {
+    /// Seconds since the start of the simulation
+    pub time: f32,
+    pub kinetic: f32,
+    pub potential: f32,
+    pub total: f32,
+}
+
+/// Records the energy in the [SceneStats] over time, to plot how an integrator drifts
+///
+/// Only recorded while the resource exists, as the samples are kept until it is removed.
+#[derive(Resource, Debug)]
+pub struct EnergyTrace {
+    /// Records every nth step, 1 records all of them
+    pub every: u32,
+
+    samples: Vec<EnergySample>,
+
+    /// Steps since the last sample
+    steps: u32,
+}
+
+impl EnergyTrace {
+    /// Create a new empty [EnergyTrace] recording every `every` steps
+    #[must_use]
+    pub const fn new(every: u32) -> Self {
+        Self {
+            every,
+            samples: Vec::new(),
+            steps: 0,
+        }
+    }
+
+    /// Counts a step at `time`, sampling the energy if it is one of the recorded ones
+    ///
+    /// The first step is always recorded.
+    pub fn record(&mut self, time: f32, stats: &SceneStats) {
+        if self.steps == 0 {
+            self.samples.push(EnergySample {
+                time,
+                kinetic: stats.kinetic_energy,
+                potential: stats.potential_energy,
+                total: stats.total_energy(),
+            });
+        }
+
+        self.steps = (self.steps + 1) % self.every.max(1);
+    }
+
+    /// Gets every recorded sample, oldest first
+    #[must_use]
+    pub fn samples(&self) -> &[EnergySample] {
+        &self.samples
+    }
+
+    /// Writes every sample as CSV, with a `time,kinetic,potential,total` header
+    pub fn write_csv(&self, mut writer: impl io::Write) -> io::Result<()> {
+        writeln!(writer, "time,kinetic,potential,total")?;
+
+        for sample in &self.samples {
+            writeln!(
+                writer,
+                "{},{},{},{}",
+                sample.time, sample.kinetic, sample.potential, sample.total
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Default for EnergyTrace {
+    fn default() -> Self {
+        Self::new(1)
+    }
+}
+
+/// Shape and size of the orbit of an object around a [CentralBody]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OrbitalElements {
+    /// Half the longest diameter of the orbit, negative for hyperbolic and infinite for parabolic
+    /// trajectories
+    pub semi_major_axis: f32,
+
+    /// 0 for a circle, below 1 for an ellipse, 1 for a parabola and above 1 for a hyperbola
+    pub eccentricity: f32,
+
+    /// Time for a full orbit in seconds, [None] if the object never comes back
+    pub period: Option<f32>,
+
+    /// Furthest distance from the center of the [CentralBody], [None] if the object never comes
+    /// back
+    pub apoapsis: Option<f32>,
+
+    /// Closest distance to the center of the [CentralBody]
+    pub periapsis: f32,
+}
+
+impl OrbitalElements {
+    /// Computes the orbit of an object at `position` moving at `velocity`, both in global
+    /// cordinates
+    ///
+    /// Returns [None] at the center of the [CentralBody] or if it has no gravity.
+    ///
+    /// ```rust
+    /// # use physics::{analysis::OrbitalElements, resources::CentralBody};
+    /// # use bevy::math::Vec3;
+    /// let central = CentralBody { mu: 100.0, position: Vec3::ZERO };
+    ///
+    /// // Fast enough for a circle, sqrt(mu / r)
+    /// let orbit = OrbitalElements::new(&central, Vec3::X * 10.0, Vec3::Z * 10f32.sqrt()).unwrap();
+    ///
+    /// assert!(orbit.eccentricity < 1e-5);
+    /// ```
+    #[must_use]
+    pub fn new(central: &CentralBody, position: Vec3, velocity: Vec3) -> Option<Self> {
+        let mu = central.mu;
+        let offset = position - central.position;
+        let r = offset.length();
+
+        if r == 0.0 || mu <= 0.0 {
+            return None;
+        }
+
+        let energy = velocity.length_squared() / 2.0 - mu / r;
+        let momentum = offset.cross(velocity);
+        let eccentricity = (velocity.cross(momentum) / mu - offset / r).length();
+
+        // Semi-latus rectum, well defined for every kind of trajectory
+        let p = momentum.length_squared() / mu;
+        let bound = eccentricity < 1.0;
+
+        let semi_major_axis = -mu / (2.0 * energy);
+
+        Some(Self {
+            semi_major_axis,
+            eccentricity,
+            period: bound.then(|| TAU * (semi_major_axis.powi(3) / mu).sqrt()),
+            apoapsis: bound.then(|| p / (1.0 - eccentricity)),
+            periapsis: p / (1.0 + eccentricity),
+        })
+    }
+}
+
+/// Speed needed for a circular orbit at distance `r` from the center of a body with gravitational
+/// parameter `mu`
+///
+/// The velocity has to be perpendicular to the offset from the body.
+#[must_use]
+pub fn circular_speed(mu: f32, r: f32) -> f32 {
+    (mu / r).sqrt()
+}
+
+/// Lowest speed that escapes a body with gravitational parameter `mu` from distance `r`, in any
+/// direction that doesn't hit the body
+#[must_use]
+pub fn escape_speed(mu: f32, r: f32) -> f32 {
+    (2.0 * mu / r).sqrt()
+}
+
+/// Logs a warning when the [EnergyMonitor] notices the total energy drifting
+///
+/// Does nothing unless the [SceneStats] resource exists.
+pub fn energy_drift(
+    time: Res<Time>,
+    stats: Option<Res<SceneStats>>,
+    mut monitor: ResMut<EnergyMonitor>,
+) {
+    let Some(stats) = stats else {
+        return;
+    };
+
+    if let Some(drift) = monitor.update(stats.total_energy(), time.delta_seconds()) {
+        log::warn!(
+            "Total energy drifted {:.1}% within {} s, the timestep might be too long",
+            drift * 100.0,
+            monitor.window
+        );
+    }
+}
+
+/// Adds the current [SceneStats] to the [EnergyTrace]
+///
+/// Does nothing unless both resources exist. Only runs on live steps, so pausing or scrubbing
+/// doesn't fill the trace with copies of the same row.
+pub fn trace_energy(
+    clock: Res<SimClock>,
+    stats: Option<Res<SceneStats>>,
+    trace: Option<ResMut<EnergyTrace>>,
+) {
+    let (Some(stats), Some(mut trace)) = (stats, trace) else {
+        return;
+    };
+
+    trace.record(clock.elapsed(), &stats);
+}
+
+/// Replaces the contents of [BodySamples] with the current state of every simulated object
+pub fn sample_bodies(
+    mut samples: ResMut<BodySamples>,
+    query: Query<(Entity, &Transform, &Velocity, &AngularVelocity), With<Simulated>>,
+) {
+    samples.0.clear();
+
+    for (entity, trans, vel, angvel) in query.iter() {
+        samples.0.insert(
+            entity,
+            BodySample {
+                position: trans.translation,
+                velocity: vel.0,
+                angular_velocity: angvel.0,
+                speed: vel.0.length(),
+            },
+        );
+    }
+}
+
+/// Computes the mass weighted average of `(position, mass)` pairs
+///
+/// [None] when the masses don't add up to anything positive, like for no bodies at all. Leave
+/// static objects out, their infinite mass would make the result meaningless.
+///
+/// ```rust
+/// # use physics::analysis::center_of_mass;
+/// # use bevy::math::Vec3;
+/// let com = center_of_mass([(Vec3::ZERO, 3.0), (Vec3::X * 4.0, 1.0)]);
+///
+/// assert_eq!(com, Some(Vec3::X));
+/// assert_eq!(center_of_mass([]), None);
+/// ```
+#[must_use]
+pub fn center_of_mass(bodies: impl IntoIterator<Item = (Vec3, f32)>) -> Option<Vec3> {
+    let (weighted, total) = bodies
+        .into_iter()
+        .fold((Vec3::ZERO, 0.0), |(weighted, total), (pos, mass)| {
+            (weighted + pos * mass, total + mass)
+        });
+
+    (total > 0.0).then(|| weighted / total)
+}
+
+/// Recomputes [SceneStats] from the current state of every simulated object
+#[allow(clippy::type_complexity)]
+pub fn scene_stats(
+    mut stats: ResMut<SceneStats>,
+    settings: Res<PhysicsSettings>,
+    up: Res<UpAxis>,
+    central: Option<Res<CentralBody>>,
+    query: Query<
+        (
+            Entity,
+            &Transform,
+            &Velocity,
+            &AngularVelocity,
+            &Inertia,
+            Option<&GravityOverride>,
+            Option<&Spring>,
+            Has<Orbiting>,
+        ),
+        With<Simulated>,
+    >,
+) {
+    *stats = SceneStats::default();
+    let gravity = settings.gravity_for(*up);
+
+    stats.center_of_mass = center_of_mass(
+        query
+            .iter()
+            .filter(|(.., inertia, _, _, _)| !inertia.is_static())
+            .map(|(_, trans, .., inertia, _, _, _)| (trans.translation, inertia.mass())),
+    );
+
+    for (entity, trans, vel, angvel, inertia, over, spring, orbiting) in query.iter() {
+        let speed = vel.0.length();
+        let altitude = up.height(trans.translation);
+
+        // The tensor is in local space while the angular velocity is in world space
+        let local_angvel = trans.rotation.inverse() * angvel.0;
+
+        stats.bodies += 1;
+        stats.kinetic_energy += inertia.kinetic_energy(vel.0, local_angvel);
+
+        // Same as the forces, orbiting objects only feel the central body
+        if !inertia.is_static() {
+            let mass = inertia.mass();
+            let pos = trans.translation;
+
+            stats.potential_energy += match (orbiting, &central) {
+                (false, _) => -mass * gravity.for_body(over).dot(pos),
+                (true, Some(central)) => {
+                    let r = pos.distance(central.position);
+
+                    if r > 0.0 {
+                        -central.mu * mass / r
+                    } else {
+                        0.0
+                    }
+                }
+                (true, None) => 0.0,
+            };
+
+            if let (Some(spring), false) = (spring, orbiting) {
+                stats.potential_energy += spring.potential_energy(pos);
+            }
+        }
+
+        if !stats.fastest.is_some_and(|(_, fastest)| fastest >= speed) {
+            stats.fastest = Some((entity, speed));
+        }
+
+        if !stats
+            .highest
+            .is_some_and(|(_, highest)| highest >= altitude)
+        {
+            stats.highest = Some((entity, altitude));
+        }
+    }
+}
+
+#[cfg(test)]
+mod samples {
+    use std::time::Duration;
+
+    use bevy::{
+        app::{App, Update},
+        ecs::schedule::IntoSystemConfigs,
+        math::Vec3,
+        time::Time,
+    };
+
+    use super::{sample_bodies, BodySamples};
+    use crate::{
+        components::{
+            acceleration::Accelerator,
+            inertia::Inertia,
+            velocity::{AngularVelocity, Velocity},
+            SimulationBundle,
+        },
+        resources::PhysicsSettings,
+        update_simulated,
+    };
+
+    #[test]
+    fn after_one_frame() {
+        let mut app = App::new();
+        let mut time = Time::<()>::default();
+        time.advance_by(Duration::from_secs(1));
+
+        app.insert_resource(time);
+        app.insert_resource(PhysicsSettings {
+            max_delta: None,
+            ..Default::default()
+        });
+        app.init_resource::<BodySamples>();
+        app.add_systems(Update, (update_simulated, sample_bodies).chain());
+
+        let body = app
+            .world_mut()
+            .spawn(SimulationBundle::new(
+                Velocity(Vec3::X * 2.0),
+                Accelerator::ZERO,
+                AngularVelocity(Vec3::Y),
+                Inertia::cylinder_y(1.0, 1.0, 1.0),
+            ))
+            .id();
+
+        app.update();
+
+        let sample = *app
+            .world()
+            .resource::<BodySamples>()
+            .get(body)
+            .expect("body should have been sampled");
+
+        assert_eq!(sample.position, Vec3::X * 2.0);
+        assert_eq!(sample.velocity, Vec3::X * 2.0);
+        assert_eq!(sample.angular_velocity, Vec3::Y);
+        assert_eq!(sample.speed, 2.0);
+    }
+}
+
+#[cfg(test)]
+mod stats {
+    use std::time::Duration;
+
+    use bevy::{
+        app::{App, Update},
+        ecs::{schedule::IntoSystemConfigs, system::RunSystemOnce, world::World},
+        math::{Mat3, Vec3},
+        time::Time,
+        transform::components::Transform,
+    };
+    use float_cmp::assert_approx_eq;
+
+    use super::{center_of_mass, scene_stats, SceneStats};
+    use crate::{
+        components::{
+            inertia::Inertia,
+            velocity::{AngularVelocity, Velocity},
+            Simulated,
+        },
+        forces::springs,
+        presets::DampedOscillator,
+        resources::{ForceToggles, PhysicsSettings, UpAxis},
+        update_simulated,
+    };
+
+    #[test]
+    fn three_bodies() {
+        let mut world = World::new();
+        world.init_resource::<SceneStats>();
+        world.init_resource::<UpAxis>();
+        world.insert_resource(PhysicsSettings {
+            gravity: Some(Vec3::Y * -10.0),
+            ..Default::default()
+        });
+
+        let mut spawn = |pos: Vec3, vel: Vec3, angvel: Vec3, mass: f32| {
+            world
+                .spawn((
+                    Simulated,
+                    Transform::from_translation(pos),
+                    Velocity(vel),
+                    AngularVelocity(angvel),
+                    Inertia::new(mass, Mat3::IDENTITY * 2.0),
+                ))
+                .id()
+        };
+
+        spawn(Vec3::Y * 5.0, Vec3::X * 2.0, Vec3::ZERO, 1.0);
+        let fast = spawn(Vec3::ZERO, Vec3::Z * -3.0, Vec3::ZERO, 2.0);
+        let high = spawn(Vec3::Y * 10.0, Vec3::ZERO, Vec3::Y, 4.0);
+
+        world.run_system_once(scene_stats);
+
+        let stats = world.resource::<SceneStats>();
+
+        assert_eq!(stats.bodies, 3);
+        // 0.5 * 1 * 2^2 + 0.5 * 2 * 3^2 + 0.5 * 2 * 1^2
+        assert_approx_eq!(f32, stats.kinetic_energy, 2.0 + 9.0 + 1.0);
+        assert_eq!(stats.fastest, Some((fast, 3.0)));
+        assert_eq!(stats.highest, Some((high, 10.0)));
+        // 1 * 10 * 5 + 2 * 10 * 0 + 4 * 10 * 10
+        assert_approx_eq!(f32, stats.potential_energy, 50.0 + 400.0);
+        // (1 * 5 + 2 * 0 + 4 * 10) / 7 up
+        assert_approx_eq!(
+            &[f32],
+            &stats.center_of_mass.unwrap().to_array(),
+            &[0.0, 45.0 / 7.0, 0.0]
+        );
+    }
+
+    #[test]
+    fn z_up() {
+        let mut world = World::new();
+        world.init_resource::<SceneStats>();
+        world.insert_resource(UpAxis::Z);
+        world.init_resource::<PhysicsSettings>();
+
+        let mut spawn = |pos: Vec3| {
+            world
+                .spawn((
+                    Simulated,
+                    Transform::from_translation(pos),
+                    Velocity(Vec3::ZERO),
+                    AngularVelocity(Vec3::ZERO),
+                    Inertia::sphere(1.0, 1.0),
+                ))
+                .id()
+        };
+
+        // Far along y, which is sideways when z points up
+        spawn(Vec3::Y * 20.0);
+        let high = spawn(Vec3::Z * 5.0);
+
+        world.run_system_once(scene_stats);
+
+        assert_eq!(world.resource::<SceneStats>().highest, Some((high, 5.0)));
+    }
+
+    #[test]
+    fn undamped_spring() {
+        let mut app = App::new();
+        let mut time = Time::<()>::default();
+        time.advance_by(Duration::from_millis(1));
+
+        app.insert_resource(time);
+        app.insert_resource(PhysicsSettings {
+            gravity: Some(Vec3::ZERO),
+            ..Default::default()
+        });
+        app.init_resource::<UpAxis>();
+        app.init_resource::<ForceToggles>();
+        app.init_resource::<SceneStats>();
+        app.add_systems(Update, (springs, update_simulated, scene_stats).chain());
+
+        // Stretched by a meter, so 0.5 * 50 * 1^2 stored at the start
+        let oscillator = DampedOscillator {
+            mass: 2.0,
+            stiffness: 50.0,
+            damping: 0.0,
+            initial_displacement: 1.0,
+        };
+        app.world_mut().spawn(oscillator.bundle());
+
+        // A bit more than a period of 2 * pi / 5 s
+        let mut lowest = f32::INFINITY;
+        for _ in 0..1300 {
+            app.update();
+
+            let stats = app.world().resource::<SceneStats>();
+            let total = stats.kinetic_energy + stats.potential_energy;
+            lowest = lowest.min(stats.potential_energy);
+
+            // Moves between the two, only the integrator drifts a little
+            assert!(
+                (total - 25.0).abs() < 0.5,
+                "total energy drifted to {total}"
+            );
+        }
+
+        // Passed through the anchor, where all of it is kinetic
+        assert!(lowest < 0.1, "{lowest}");
+    }
+
+    #[test]
+    fn weighted_centroid() {
+        let com = center_of_mass([
+            (Vec3::new(1.0, 0.0, 0.0), 1.0),
+            (Vec3::new(0.0, 2.0, 0.0), 2.0),
+            (Vec3::new(0.0, 0.0, -3.0), 3.0),
+        ])
+        .unwrap();
+
+        assert_approx_eq!(&[f32], &com.to_array(), &[1.0 / 6.0, 4.0 / 6.0, -9.0 / 6.0]);
+    }
+
+    #[test]
+    fn no_mass() {
+        assert_eq!(center_of_mass([]), None);
+        assert_eq!(center_of_mass([(Vec3::ONE, 0.0)]), None);
+    }
+
+    #[test]
+    fn empty() {
+        let mut world = World::new();
+        world.init_resource::<SceneStats>();
+        world.init_resource::<PhysicsSettings>();
+        world.init_resource::<UpAxis>();
+
+        world.run_system_once(scene_stats);
+
+        assert_eq!(*world.resource::<SceneStats>(), SceneStats::default());
+    }
+}
+
+#[cfg(test)]
+mod orbit {
+    use std::f32::consts::TAU;
+
+    use bevy::math::Vec3;
+    use float_cmp::assert_approx_eq;
+
+    use super::OrbitalElements;
+    use crate::resources::CentralBody;
+
+    const CENTRAL: CentralBody = CentralBody {
+        mu: 100.0,
+        position: Vec3::new(5.0, 0.0, 0.0),
+    };
+
+    #[test]
+    fn circular() {
+        let orbit = OrbitalElements::new(
+            &CENTRAL,
+            CENTRAL.position + Vec3::Z * 10.0,
+            Vec3::Y * 10f32.sqrt(),
+        )
+        .unwrap();
+
+        assert_approx_eq!(f32, orbit.eccentricity, 0.0, epsilon = 1e-5);
+        assert_approx_eq!(f32, orbit.semi_major_axis, 10.0, epsilon = 1e-4);
+        assert_approx_eq!(f32, orbit.periapsis, 10.0, epsilon = 1e-4);
+        assert_approx_eq!(f32, orbit.apoapsis.unwrap(), 10.0, epsilon = 1e-4);
+        assert_approx_eq!(
+            f32,
+            orbit.period.unwrap(),
+            TAU * 10f32.sqrt(),
+            epsilon = 1e-4
+        );
+    }
+
+    #[test]
+    fn elliptical() {
+        // At periapsis of an orbit with e = 0.5, where the speed is sqrt(mu * (1 + e) / r)
+        let orbit = OrbitalElements::new(
+            &CENTRAL,
+            CENTRAL.position + Vec3::X * 10.0,
+            Vec3::Z * 15f32.sqrt(),
+        )
+        .unwrap();
+
+        assert_approx_eq!(f32, orbit.eccentricity, 0.5, epsilon = 1e-5);
+        assert_approx_eq!(f32, orbit.semi_major_axis, 20.0, epsilon = 1e-4);
+        assert_approx_eq!(f32, orbit.periapsis, 10.0, epsilon = 1e-4);
+        assert_approx_eq!(f32, orbit.apoapsis.unwrap(), 30.0, epsilon = 1e-4);
+        assert_approx_eq!(
+            f32,
+            orbit.period.unwrap(),
+            TAU * 80f32.sqrt(),
+            epsilon = 1e-3
+        );
+    }
+
+    #[test]
+    fn escaping() {
+        // Faster than the escape velocity of sqrt(2 * mu / r)
+        let orbit = OrbitalElements::new(
+            &CENTRAL,
+            CENTRAL.position + Vec3::X * 10.0,
+            Vec3::Z * 20f32.sqrt() * 1.5,
+        )
+        .unwrap();
+
+        assert!(orbit.eccentricity > 1.0);
+        assert!(orbit.semi_major_axis < 0.0);
+        assert_eq!(orbit.period, None);
+        assert_eq!(orbit.apoapsis, None);
+    }
+
+    #[test]
+    fn at_center() {
+        assert_eq!(
+            OrbitalElements::new(&CENTRAL, CENTRAL.position, Vec3::X),
+            None
+        );
+    }
+}
+
+#[cfg(test)]
+mod speeds {
+    use bevy::math::Vec3;
+    use float_cmp::assert_approx_eq;
+
+    use super::{circular_speed, escape_speed, OrbitalElements};
+    use crate::resources::CentralBody;
+
+    #[test]
+    fn analytic() {
+        assert_approx_eq!(f32, circular_speed(100.0, 4.0), 5.0);
+        assert_approx_eq!(f32, escape_speed(100.0, 8.0), 5.0);
+
+        // Roughly the earth from its surface, in kilometers
+        let (mu, r) = (398_600.0, 6_371.0);
+        assert_approx_eq!(f32, circular_speed(mu, r), 7.91, epsilon = 1e-2);
+        assert_approx_eq!(f32, escape_speed(mu, r), 11.19, epsilon = 1e-2);
+        assert_approx_eq!(
+            f32,
+            escape_speed(mu, r),
+            circular_speed(mu, r) * 2f32.sqrt()
+        );
+    }
+
+    #[test]
+    fn orbits() {
+        let central = CentralBody {
+            mu: 50.0,
+            position: Vec3::ZERO,
+        };
+        let pos = Vec3::X * 12.0;
+
+        let circle = OrbitalElements::new(&central, pos, Vec3::Z * circular_speed(50.0, 12.0));
+        assert_approx_eq!(f32, circle.unwrap().eccentricity, 0.0, epsilon = 1e-5);
+
+        // Exactly at escape speed the orbit is a parabola
+        let escape = OrbitalElements::new(&central, pos, Vec3::Z * escape_speed(50.0, 12.0));
+        assert_approx_eq!(f32, escape.unwrap().eccentricity, 1.0, epsilon = 1e-5);
+    }
+}
+
+#[cfg(test)]
+mod drift {
+    use super::EnergyMonitor;
+
+    /// Feeds the series one second apart, collecting every warning
+    fn warnings(monitor: &mut EnergyMonitor, series: &[f32]) -> Vec<f32> {
+        series
+            .iter()
+            .filter_map(|energy| monitor.update(*energy, 1.0))
+            .collect()
+    }
+
+    #[test]
+    fn stable() {
+        let mut monitor = EnergyMonitor::new(0.05, 5.0);
+        let series = [100.0, 101.0, 99.0, 102.0, 98.5, 100.0, 103.0, 97.0];
+
+        assert!(warnings(&mut monitor, &series).is_empty());
+    }
+
+    #[test]
+    fn growing() {
+        let mut monitor = EnergyMonitor::new(0.05, 5.0);
+        // Growing 2% a second, like an unstable integrator
+        let series: Vec<f32> = (0..15).map(|i| 100.0 * 1.02f32.powi(i)).collect();
+
+        // Once per window, after it gets past 5% in the fourth second
+        let warned = warnings(&mut monitor, &series);
+        assert_eq!(warned.len(), 3, "{warned:?}");
+        assert!(warned.iter().all(|drift| *drift > 0.05));
+    }
+
+    #[test]
+    fn negative_energy() {
+        // Bound orbits have negative total energy, drifting towards zero is still drift
+        let mut monitor = EnergyMonitor::new(0.05, 5.0);
+        let warned = warnings(&mut monitor, &[-100.0, -98.0, -90.0]);
+
+        assert_eq!(warned.len(), 1);
+        assert!(warned[0] > 0.05);
+    }
+}
+
+#[cfg(test)]
+mod trace {
+    use std::time::Duration;
+
+    use bevy::{
+        app::{App, Update},
+        asset::{AssetApp, AssetPlugin},
+        ecs::schedule::IntoSystemConfigs,
+        gizmos::GizmoPlugin,
+        math::Vec3,
+        render::render_resource::Shader,
+        time::{Time, TimeUpdateStrategy},
+        MinimalPlugins,
+    };
+    use float_cmp::assert_approx_eq;
+
+    use super::{scene_stats, trace_energy, EnergyTrace, SceneStats};
+    use crate::{
+        advance_clock,
+        components::{
+            acceleration::Accelerator,
+            inertia::Inertia,
+            velocity::{AngularVelocity, Velocity},
+            SimulationBundle,
+        },
+        forces::gravity,
+        pause::SimulationPaused,
+        resources::{ForceToggles, PhysicsSettings, SimClock, UpAxis},
+        update_simulated, SimulatiorPlugin,
+    };
+
+    /// Parses every row of the CSV back into numbers, checking the header on the way
+    fn parse(csv: &str) -> Vec<[f32; 4]> {
+        let mut lines = csv.lines();
+        assert_eq!(lines.next(), Some("time,kinetic,potential,total"));
+
+        lines
+            .map(|line| {
+                let columns: Vec<f32> = line.split(',').map(|c| c.parse().unwrap()).collect();
+                columns.try_into().unwrap()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn every_nth() {
+        let mut trace = EnergyTrace::new(3);
+
+        for step in 0..10 {
+            let stats = SceneStats {
+                kinetic_energy: step as f32,
+                potential_energy: -1.0,
+                ..Default::default()
+            };
+            trace.record(step as f32 * 0.5, &stats);
+        }
+
+        let mut csv = Vec::new();
+        trace.write_csv(&mut csv).unwrap();
+
+        assert_eq!(
+            parse(&String::from_utf8(csv).unwrap()),
+            vec![
+                [0.0, 0.0, -1.0, -1.0],
+                [1.5, 3.0, -1.0, 2.0],
+                [3.0, 6.0, -1.0, 5.0],
+                [4.5, 9.0, -1.0, 8.0],
+            ]
+        );
+    }
+
+    #[test]
+    fn falling_body() {
+        let mut app = App::new();
+        let mut time = Time::<()>::default();
+        time.advance_by(Duration::from_millis(10));
+
+        app.insert_resource(time);
+        app.init_resource::<PhysicsSettings>();
+        app.init_resource::<UpAxis>();
+        app.init_resource::<ForceToggles>();
+        app.init_resource::<SceneStats>();
+        app.init_resource::<SimClock>();
+        app.insert_resource(EnergyTrace::new(10));
+        app.add_systems(
+            Update,
+            (
+                gravity,
+                advance_clock,
+                update_simulated,
+                scene_stats,
+                trace_energy,
+            )
+                .chain(),
+        );
+
+        app.world_mut().spawn(SimulationBundle::new(
+            Velocity(Vec3::X),
+            Accelerator::ZERO,
+            AngularVelocity::ZERO,
+            Inertia::sphere(0.1, 2.0),
+        ));
+
+        for _ in 0..100 {
+            app.update();
+        }
+
+        let mut csv = Vec::new();
+        let trace = app.world().resource::<EnergyTrace>();
+        trace.write_csv(&mut csv).unwrap();
+        let rows = parse(&String::from_utf8(csv).unwrap());
+
+        assert_eq!(rows.len(), 10);
+        for [_, kinetic, potential, total] in rows {
+            assert_approx_eq!(f32, kinetic + potential, total, epsilon = 1e-4);
+
+            // Energy is traded between the two, with the total staying at the start
+            assert_approx_eq!(f32, total, 1.0, epsilon = 1e-2);
+        }
+    }
+
+    #[test]
+    fn paused() {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, AssetPlugin::default()));
+        app.init_asset::<Shader>();
+        app.add_plugins((GizmoPlugin, SimulatiorPlugin::default()));
+        app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(
+            20,
+        )));
+        app.insert_resource(PhysicsSettings {
+            timestep: 0.02,
+            ..Default::default()
+        });
+        app.insert_resource(EnergyTrace::new(1));
+
+        app.world_mut().spawn(SimulationBundle::new(
+            Velocity(Vec3::X),
+            Accelerator::ZERO,
+            AngularVelocity::ZERO,
+            Inertia::sphere(0.1, 2.0),
+        ));
+
+        for _ in 0..10 {
+            app.update();
+        }
+
+        let traced = app.world().resource::<EnergyTrace>().samples().len();
+        assert!(traced > 0);
+
+        app.insert_resource(SimulationPaused(true));
+        for _ in 0..10 {
+            app.update();
+        }
+
+        assert_eq!(
+            app.world().resource::<EnergyTrace>().samples().len(),
+            traced
+        );
+    }
+}
@@ -0,0 +1,136 @@
+use bevy::{ecs::entity::Entity, math::Vec3};
+
+/// Bounding sphere of an object, all the broad phase knows about it
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Bounds {
+    pub entity: Entity,
+
+    /// Center in global cordinates
+    pub center: Vec3,
+
+    pub radius: f32,
+}
+
+impl Bounds {
+    /// Checks if the bounding boxes around two spheres overlap
+    #[must_use]
+    pub fn overlaps(&self, other: &Bounds) -> bool {
+        let reach = self.radius + other.radius;
+
+        (self.center - other.center)
+            .abs()
+            .cmple(Vec3::splat(reach))
+            .all()
+    }
+}
+
+/// Finds every pair of objects that might be touching, by sorting and sweeping along the x axis
+///
+/// Only pairs with overlapping bounding boxes are returned, so the narrow phase gets far fewer
+/// pairs than checking everything against everything. Each pair is ordered by the lowest point of
+/// the objects along x.
+#[must_use]
+pub fn candidate_pairs(mut bounds: Vec<Bounds>) -> Vec<(Entity, Entity)> {
+    bounds.sort_by(|a, b| (a.center.x - a.radius).total_cmp(&(b.center.x - b.radius)));
+
+    let mut pairs = Vec::new();
+
+    for (i, a) in bounds.iter().enumerate() {
+        let end = a.center.x + a.radius;
+
+        // Sorted by their start, so nothing past the first one starting after this ends can touch
+        for b in bounds[i + 1..]
+            .iter()
+            .take_while(|b| b.center.x - b.radius <= end)
+        {
+            if a.overlaps(b) {
+                pairs.push((a.entity, b.entity));
+            }
+        }
+    }
+
+    pairs
+}
+
+#[cfg(test)]
+mod sweep {
+    use bevy::{ecs::entity::Entity, math::Vec3, utils::HashSet};
+
+    use super::{candidate_pairs, Bounds};
+
+    /// Small linear congruential generator, to get the same scattered spheres every run
+    struct Lcg(u64);
+
+    impl Lcg {
+        fn next(&mut self) -> f32 {
+            self.0 = self
+                .0
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+
+            (self.0 >> 40) as f32 / (1u64 << 24) as f32
+        }
+    }
+
+    fn touching(a: &Bounds, b: &Bounds) -> bool {
+        a.center.distance(b.center) <= a.radius + b.radius
+    }
+
+    fn unordered((a, b): (Entity, Entity)) -> (Entity, Entity) {
+        (a.min(b), a.max(b))
+    }
+
+    #[test]
+    fn matches_brute_force() {
+        let mut rng = Lcg(7);
+        let bounds: Vec<_> = (0..300)
+            .map(|i| Bounds {
+                entity: Entity::from_raw(i),
+                center: Vec3::new(rng.next(), rng.next(), rng.next()) * 40.0,
+                radius: 0.2 + rng.next() * 2.0,
+            })
+            .collect();
+
+        let mut brute = HashSet::new();
+        for (i, a) in bounds.iter().enumerate() {
+            for b in &bounds[i + 1..] {
+                if touching(a, b) {
+                    brute.insert(unordered((a.entity, b.entity)));
+                }
+            }
+        }
+
+        let candidates = candidate_pairs(bounds.clone());
+        let swept: HashSet<_> = candidates
+            .iter()
+            .copied()
+            .filter(|&(a, b)| touching(&bounds[a.index() as usize], &bounds[b.index() as usize]))
+            .map(unordered)
+            .collect();
+
+        assert!(!brute.is_empty());
+        assert_eq!(swept, brute);
+
+        // Actually pruned something
+        assert!(candidates.len() < bounds.len() * (bounds.len() - 1) / 2 / 10);
+    }
+
+    #[test]
+    fn ordered_along_x() {
+        let (a, b) = (Entity::from_raw(0), Entity::from_raw(1));
+        let bounds = vec![
+            Bounds {
+                entity: a,
+                center: Vec3::X,
+                radius: 1.0,
+            },
+            Bounds {
+                entity: b,
+                center: Vec3::ZERO,
+                radius: 1.0,
+            },
+        ];
+
+        assert_eq!(candidate_pairs(bounds), vec![(b, a)]);
+    }
+}
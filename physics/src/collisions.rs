@@ -0,0 +1,655 @@
+use bevy::{
+    ecs::{
+        component::Component,
+        entity::Entity,
+        event::{Event, EventWriter},
+        query::With,
+        system::{Query, Res, Resource},
+    },
+    math::{Mat3, Vec3},
+    transform::components::Transform,
+};
+
+use crate::{
+    broad_phase::{candidate_pairs, Bounds},
+    components::{
+        inertia::Inertia,
+        joint::cross_matrix,
+        shape::Shape,
+        velocity::{AngularVelocity, Velocity},
+        Simulated,
+    },
+};
+
+/// Sent for every pair of objects that collided this frame
+#[derive(Event, Debug, Clone, Copy, PartialEq)]
+pub struct CollisionEvent {
+    pub a: Entity,
+    pub b: Entity,
+
+    /// Where the objects touch, in global cordinates
+    pub point: Vec3,
+
+    /// Unit vector pointing from `a` towards `b`
+    pub normal: Vec3,
+
+    /// Magnitude of the impulse applied along the normal
+    pub impulse: f32,
+}
+
+/// How much of the speed along the contact normal is kept after a collision
+///
+/// 1 is perfectly elastic and 0 stops the objects from separating at all. Objects without one
+/// are perfectly elastic and the lower value of the two objects is used.
+#[derive(Component, Debug)]
+pub struct Restitution(pub f32);
+
+/// Tolerances that let resting contacts settle instead of jittering
+///
+/// Without them, the tiny overlap gravity pushes a resting object into every step is corrected
+/// and bounced off, so it never comes to rest. Nothing is tolerated when the resource doesn't
+/// exist.
+#[derive(Resource, Debug, Clone, Copy, PartialEq)]
+pub struct CollisionSettings {
+    /// Overlap in meters that is left alone, only the overlap past it is pushed apart
+    pub contact_slop: f32,
+
+    /// Closing speed in m/s under which collisions don't bounce, as if the [Restitution] was 0
+    pub restitution_velocity_threshold: f32,
+}
+
+impl Default for CollisionSettings {
+    fn default() -> Self {
+        Self {
+            contact_slop: 0.01,
+            restitution_velocity_threshold: 0.5,
+        }
+    }
+}
+
+/// Coulomb friction coefficient, limiting the tangential impulse of a collision to this times the
+/// normal impulse
+///
+/// Contacts that need less than that to stop sliding stick, the rest keep sliding with the
+/// limited impulse. Objects without one are frictionless and the lower value of the two objects
+/// is used.
+#[derive(Component, Debug)]
+pub struct Friction(pub f32);
+
+/// Gets the friction impulse on the second object, given how it slides relative to the first
+///
+/// `sliding` is the part of the relative velocity along the contact surface, and `inverse_mass`
+/// how much it changes per unit of impulse against it, with both objects free to turn. The
+/// impulse is the one that stops the sliding completely, clamped to `mu * normal_impulse` so
+/// strong sliding only slows down. The first object gets the opposite impulse.
+///
+/// ```rust
+/// # use physics::collisions::friction_impulse;
+/// # use bevy::math::Vec3;
+/// // Slow enough to stick, the full impulse to stop it
+/// assert_eq!(friction_impulse(Vec3::X, 1.0, 10.0, 0.5), Vec3::NEG_X);
+///
+/// // Too fast, limited by the cone
+/// assert_eq!(friction_impulse(Vec3::X * 20.0, 1.0, 10.0, 0.5), Vec3::NEG_X * 5.0);
+/// ```
+#[must_use]
+pub fn friction_impulse(sliding: Vec3, inverse_mass: f32, normal_impulse: f32, mu: f32) -> Vec3 {
+    if inverse_mass <= 0.0 {
+        return Vec3::ZERO;
+    }
+
+    let sticking = -sliding / inverse_mass;
+
+    sticking.clamp_length_max((mu * normal_impulse).max(0.0))
+}
+
+/// Gets how much the velocity of the point `arm` from the center of mass changes per unit of
+/// impulse applied at it, in global cordinates
+fn mobility(trans: &Transform, inertia: &Inertia, arm: Vec3) -> Mat3 {
+    let cross = cross_matrix(arm);
+
+    Mat3::IDENTITY * inertia.inverse_mass
+        - cross * inertia.world_inverse_tensor(trans.rotation) * cross
+}
+
+/// Solves a perfectly elastic head on collision, returning the velocities after it
+///
+/// Both momentum and kinetic energy are conserved. An infinite mass acts like a wall that keeps
+/// its velocity, while the other object bounces off it.
+///
+/// ```rust
+/// # use physics::collisions::elastic_1d;
+/// // Equal masses swap velocities
+/// assert_eq!(elastic_1d(1.0, 3.0, 1.0, -1.0), (-1.0, 3.0));
+///
+/// // A wall sends it straight back
+/// assert_eq!(elastic_1d(f32::INFINITY, 0.0, 2.0, -5.0), (0.0, 5.0));
+/// ```
+#[must_use]
+pub fn elastic_1d(m1: f32, v1: f32, m2: f32, v2: f32) -> (f32, f32) {
+    match (m1.is_infinite(), m2.is_infinite()) {
+        (true, true) => (v1, v2),
+        (true, false) => (v1, 2.0 * v1 - v2),
+        (false, true) => (2.0 * v2 - v1, v2),
+        (false, false) => {
+            let total = m1 + m2;
+
+            (
+                ((m1 - m2) * v1 + 2.0 * m2 * v2) / total,
+                ((m2 - m1) * v2 + 2.0 * m1 * v1) / total,
+            )
+        }
+    }
+}
+
+/// Pushes apart and bounces every pair of overlapping objects with a [Shape::Sphere]
+///
+/// Objects are moved and bounced in proportion to their inverse mass, so static objects stay put
+/// while dynamic ones are pushed off them. Only the pairs found by the
+/// [broad phase](crate::broad_phase) are checked.
+///
+/// The [CollisionSettings] keep resting contacts from bouncing, if the resource exists. Friction
+/// acts at the contact point, so it also spins the objects up or down.
+#[allow(clippy::type_complexity)]
+pub fn spheres(
+    settings: Option<Res<CollisionSettings>>,
+    mut events: EventWriter<CollisionEvent>,
+    mut query: Query<
+        (
+            Entity,
+            &mut Transform,
+            &mut Velocity,
+            &mut AngularVelocity,
+            &Inertia,
+            &Shape,
+            Option<&Restitution>,
+            Option<&Friction>,
+        ),
+        With<Simulated>,
+    >,
+) {
+    let (slop, threshold) = settings.map_or((0.0, 0.0), |s| {
+        (s.contact_slop, s.restitution_velocity_threshold)
+    });
+
+    let bounds = query
+        .iter()
+        .filter_map(|(entity, trans, _, _, _, shape, _, _)| match *shape {
+            Shape::Sphere { radius } => Some(Bounds {
+                entity,
+                center: trans.translation,
+                radius,
+            }),
+            _ => None,
+        })
+        .collect();
+
+    for (a, b) in candidate_pairs(bounds) {
+        let Ok([a, b]) = query.get_many_mut([a, b]) else {
+            continue;
+        };
+
+        let (a, mut a_trans, mut a_vel, mut a_angvel, a_inertia, a_shape, a_rest, a_friction) = a;
+        let (b, mut b_trans, mut b_vel, mut b_angvel, b_inertia, b_shape, b_rest, b_friction) = b;
+
+        let (Shape::Sphere { radius: a_radius }, Shape::Sphere { radius: b_radius }) =
+            (*a_shape, *b_shape)
+        else {
+            continue;
+        };
+
+        let offset = b_trans.translation - a_trans.translation;
+        let distance = offset.length();
+        let penetration = a_radius + b_radius - distance;
+
+        // Two static objects can't be separated
+        let total_inverse_mass = a_inertia.inverse_mass + b_inertia.inverse_mass;
+
+        if penetration <= 0.0 || total_inverse_mass == 0.0 {
+            continue;
+        }
+
+        // Pick any direction if the centers are on top of each other
+        let normal = offset.try_normalize().unwrap_or(Vec3::Y);
+        let point = a_trans.translation + normal * (a_radius - penetration / 2.0);
+
+        // Move them apart so they only overlap by the slop
+        let correction = normal * (penetration - slop).max(0.0) / total_inverse_mass;
+        a_trans.translation -= correction * a_inertia.inverse_mass;
+        b_trans.translation += correction * b_inertia.inverse_mass;
+
+        // Only bounce if they are moving towards each other
+        let (a_normal, b_normal) = (a_vel.0.dot(normal), b_vel.0.dot(normal));
+        let closing = b_normal - a_normal;
+        let impulse = if closing < 0.0 {
+            let restitution = if -closing < threshold {
+                0.0
+            } else {
+                a_rest.map_or(1.0, |r| r.0).min(b_rest.map_or(1.0, |r| r.0))
+            };
+            let (a_mass, b_mass) = (a_inertia.mass(), b_inertia.mass());
+
+            // Less than elastic keeps less of the speed relative to the common velocity, the one
+            // both would have after a perfectly inelastic collision
+            let (a_elastic, b_elastic) = elastic_1d(a_mass, a_normal, b_mass, b_normal);
+            let common = (a_normal * b_inertia.inverse_mass + b_normal * a_inertia.inverse_mass)
+                / total_inverse_mass;
+
+            let a_after = common + (a_elastic - common) * restitution;
+            let b_after = common + (b_elastic - common) * restitution;
+
+            a_vel.0 += normal * (a_after - a_normal);
+            b_vel.0 += normal * (b_after - b_normal);
+
+            let impulse = -(1.0 + restitution) * closing / total_inverse_mass;
+
+            let mu = a_friction
+                .map_or(0.0, |f| f.0)
+                .min(b_friction.map_or(0.0, |f| f.0));
+
+            // Velocity of the contact point on each side, including the spin
+            let (a_arm, b_arm) = (normal * a_radius, normal * -b_radius);
+            let relative =
+                (b_vel.0 + b_angvel.0.cross(b_arm)) - (a_vel.0 + a_angvel.0.cross(a_arm));
+            let sliding = relative - normal * relative.dot(normal);
+
+            // Solved along the sliding, with the combined mobility of both sides like a joint
+            let along = sliding.normalize_or_zero();
+            let combined =
+                mobility(&a_trans, a_inertia, a_arm) + mobility(&b_trans, b_inertia, b_arm);
+            let friction = friction_impulse(sliding, along.dot(combined * along), impulse, mu);
+
+            a_vel.0 -= friction * a_inertia.inverse_mass;
+            b_vel.0 += friction * b_inertia.inverse_mass;
+            a_angvel.0 -= a_inertia.world_inverse_tensor(a_trans.rotation) * a_arm.cross(friction);
+            b_angvel.0 += b_inertia.world_inverse_tensor(b_trans.rotation) * b_arm.cross(friction);
+
+            impulse
+        } else {
+            0.0
+        };
+
+        events.send(CollisionEvent {
+            a,
+            b,
+            point,
+            normal,
+            impulse,
+        });
+    }
+}
+
+#[cfg(test)]
+mod elastic {
+    use float_cmp::assert_approx_eq;
+
+    use super::elastic_1d;
+
+    #[test]
+    fn conserves() {
+        let cases = [
+            (1.0, 4.0, 1.0, -2.0),
+            (1.0, 3.0, 10.0, 0.0),
+            (50.0, -1.0, 0.5, 6.0),
+            (2.0, 0.0, 7.0, -3.0),
+            (1e3, 2.0, 1e-3, -2.0),
+        ];
+
+        for (m1, v1, m2, v2) in cases {
+            let (u1, u2) = elastic_1d(m1, v1, m2, v2);
+
+            let momentum = m1 * v1 + m2 * v2;
+            let energy = 0.5 * (m1 * v1 * v1 + m2 * v2 * v2);
+
+            assert_approx_eq!(f32, m1 * u1 + m2 * u2, momentum, epsilon = 1e-3);
+            assert_approx_eq!(
+                f32,
+                0.5 * (m1 * u1 * u1 + m2 * u2 * u2),
+                energy,
+                epsilon = 1e-3
+            );
+
+            // The relative velocity flips
+            assert_approx_eq!(f32, u2 - u1, v1 - v2, epsilon = 1e-3);
+        }
+    }
+
+    #[test]
+    fn walls() {
+        assert_eq!(elastic_1d(3.0, -2.0, f32::INFINITY, 1.0), (4.0, 1.0));
+        assert_eq!(
+            elastic_1d(f32::INFINITY, 1.0, f32::INFINITY, -1.0),
+            (1.0, -1.0)
+        );
+    }
+}
+
+#[cfg(test)]
+mod friction {
+    use bevy::{
+        ecs::{entity::Entity, event::Events, system::RunSystemOnce, world::World},
+        math::Vec3,
+        transform::components::Transform,
+    };
+    use float_cmp::assert_approx_eq;
+
+    use super::{friction_impulse, spheres, CollisionEvent, Friction, Restitution};
+    use crate::components::{
+        acceleration::Accelerator,
+        inertia::Inertia,
+        shape::Shape,
+        velocity::{AngularVelocity, Velocity},
+        SimulationBundle,
+    };
+
+    #[test]
+    fn cone() {
+        let sliding = Vec3::new(3.0, 0.0, 4.0);
+
+        // Needs 2.5 to stop, the cone allows 4
+        let stick = friction_impulse(sliding, 2.0, 8.0, 0.5);
+        assert_approx_eq!(&[f32], &stick.to_array(), &[-1.5, 0.0, -2.0]);
+
+        // Same direction, but only allowed 1
+        let slide = friction_impulse(sliding, 2.0, 2.0, 0.5);
+        assert_approx_eq!(&[f32], &slide.to_array(), &[-0.6, 0.0, -0.8]);
+
+        assert_eq!(friction_impulse(sliding, 2.0, 8.0, 0.0), Vec3::ZERO);
+        assert_eq!(friction_impulse(sliding, 0.0, 8.0, 0.5), Vec3::ZERO);
+    }
+
+    /// Drops a ball at 1 m/s onto a static one while sliding at `speed` and spinning at
+    /// `spin`, returning the velocity and angular velocity it leaves with
+    fn glance(speed: f32, spin: Vec3) -> (Vec3, Vec3) {
+        let mut world = World::new();
+        world.init_resource::<Events<CollisionEvent>>();
+
+        let mut spawn = |pos: Vec3, vel: Vec3, angvel: Vec3, inertia: Inertia| -> Entity {
+            let mut body = SimulationBundle::new(
+                Velocity(vel),
+                Accelerator::ZERO,
+                AngularVelocity(angvel),
+                inertia,
+            );
+            body.spatial.transform = Transform::from_translation(pos);
+
+            world
+                .spawn((
+                    body,
+                    Shape::Sphere { radius: 1.0 },
+                    Restitution(0.0),
+                    Friction(0.5),
+                ))
+                .id()
+        };
+
+        spawn(Vec3::ZERO, Vec3::ZERO, Vec3::ZERO, Inertia::static_body());
+        let ball = spawn(
+            Vec3::Y * 1.9,
+            Vec3::new(speed, -1.0, 0.0),
+            spin,
+            Inertia::sphere(1.0, 1.0),
+        );
+
+        world.run_system_once(spheres);
+        (
+            world.get::<Velocity>(ball).unwrap().0,
+            world.get::<AngularVelocity>(ball).unwrap().0,
+        )
+    }
+
+    /// Velocity of the bottom of the ball, where it touches the static one
+    fn contact_velocity((vel, angvel): (Vec3, Vec3)) -> Vec3 {
+        vel + angvel.cross(Vec3::NEG_Y)
+    }
+
+    #[test]
+    fn sticking() {
+        // A unit impulse slows the contact by 1 + 2.5, stopping needs less than the 0.5 allowed
+        let (vel, angvel) = glance(0.3, Vec3::ZERO);
+
+        // Rolls on instead of stopping dead
+        assert_approx_eq!(f32, vel.x, 0.3 * 2.5 / 3.5, epsilon = 1e-6);
+        assert!(angvel.z < 0.0);
+        assert_approx_eq!(
+            &[f32],
+            &contact_velocity((vel, angvel)).to_array(),
+            &[0.0, 0.0, 0.0],
+            epsilon = 1e-6
+        );
+    }
+
+    #[test]
+    fn sliding() {
+        // Stopping needs an impulse of 3 / 3.5, only 0.5 is allowed
+        let (vel, angvel) = glance(3.0, Vec3::ZERO);
+
+        assert_approx_eq!(&[f32], &vel.to_array(), &[2.5, 0.0, 0.0]);
+        assert_approx_eq!(&[f32], &angvel.to_array(), &[0.0, 0.0, -1.25]);
+    }
+
+    #[test]
+    fn spinning() {
+        // Falling straight down, the spin alone slides the bottom along x at 4 m/s
+        let (vel, angvel) = glance(0.0, Vec3::Z * 4.0);
+
+        // Kicked back against the sliding, and the spin slowed by the same impulse
+        assert_approx_eq!(&[f32], &vel.to_array(), &[-0.5, 0.0, 0.0]);
+        assert_approx_eq!(&[f32], &angvel.to_array(), &[0.0, 0.0, 2.75]);
+    }
+}
+
+#[cfg(test)]
+mod resting {
+    use std::time::Duration;
+
+    use bevy::{
+        app::{App, Update},
+        ecs::{entity::Entity, schedule::IntoSystemConfigs},
+        time::Time,
+        transform::components::Transform,
+    };
+    use float_cmp::assert_approx_eq;
+
+    use super::{spheres, CollisionEvent, CollisionSettings, Restitution};
+    use crate::{
+        components::{
+            acceleration::Accelerator,
+            inertia::Inertia,
+            shape::Shape,
+            velocity::{AngularVelocity, Velocity},
+            SimulationBundle,
+        },
+        forces::gravity,
+        resources::{ForceToggles, PhysicsSettings, UpAxis},
+        update_simulated,
+    };
+
+    const GROUND_RADIUS: f32 = 100.0;
+
+    /// Drops a ball from 5 cm above a huge static sphere, returning the app and the ball
+    fn drop(settings: Option<CollisionSettings>) -> (App, Entity) {
+        let mut app = App::new();
+        let mut time = Time::<()>::default();
+        time.advance_by(Duration::from_secs_f32(1.0 / 64.0));
+
+        app.insert_resource(time);
+        app.init_resource::<PhysicsSettings>();
+        app.init_resource::<UpAxis>();
+        app.init_resource::<ForceToggles>();
+        if let Some(settings) = settings {
+            app.insert_resource(settings);
+        }
+        app.add_event::<CollisionEvent>();
+        app.add_systems(Update, (gravity, update_simulated, spheres).chain());
+
+        let mut spawn = |y: f32, radius: f32, inertia: Inertia| {
+            let mut body = SimulationBundle::new(
+                Velocity::ZERO,
+                Accelerator::ZERO,
+                AngularVelocity::ZERO,
+                inertia,
+            );
+            body.spatial.transform = Transform::from_xyz(0.0, y, 0.0);
+
+            app.world_mut()
+                .spawn((body, Shape::Sphere { radius }, Restitution(0.5)))
+                .id()
+        };
+
+        spawn(-GROUND_RADIUS, GROUND_RADIUS, Inertia::static_body());
+        let ball = spawn(1.05, 1.0, Inertia::sphere(1.0, 1.0));
+
+        (app, ball)
+    }
+
+    /// Lowest and highest the ball gets over a second, after settling for five seconds
+    fn resting_range(app: &mut App, ball: Entity) -> (f32, f32) {
+        for _ in 0..5 * 64 {
+            app.update();
+        }
+
+        (0..64)
+            .map(|_| {
+                app.update();
+                app.world().get::<Transform>(ball).unwrap().translation.y
+            })
+            .fold((f32::MAX, f32::MIN), |(lo, hi), y| (lo.min(y), hi.max(y)))
+    }
+
+    #[test]
+    fn comes_to_rest() {
+        let settings = CollisionSettings::default();
+        let (mut app, ball) = drop(Some(settings));
+        let (lo, hi) = resting_range(&mut app, ball);
+
+        // Still, sunk in by no more than the slop and a step of gravity
+        assert_approx_eq!(f32, lo, hi, epsilon = 1e-4);
+        assert!(lo < 1.0 && lo > 1.0 - settings.contact_slop - 0.005, "{lo}");
+
+        let vel = app.world().get::<Velocity>(ball).unwrap().0;
+        assert!(vel.length() < 1e-3, "{vel}");
+    }
+
+    #[test]
+    fn jitters_without() {
+        let (mut app, ball) = drop(None);
+        let (lo, hi) = resting_range(&mut app, ball);
+
+        assert!(hi > lo);
+    }
+}
+
+#[cfg(test)]
+mod spheres {
+    use std::time::Duration;
+
+    use bevy::{
+        app::{App, Update},
+        ecs::{entity::Entity, event::Events, schedule::IntoSystemConfigs, system::RunSystemOnce},
+        math::Vec3,
+        time::Time,
+        transform::components::Transform,
+    };
+    use float_cmp::assert_approx_eq;
+
+    use super::{spheres, CollisionEvent};
+    use crate::{
+        components::{
+            acceleration::Accelerator,
+            inertia::Inertia,
+            shape::Shape,
+            velocity::{AngularVelocity, Velocity},
+            SimulationBundle,
+        },
+        resources::PhysicsSettings,
+        update_simulated,
+    };
+
+    fn app() -> App {
+        let mut app = App::new();
+        let mut time = Time::<()>::default();
+        time.advance_by(Duration::from_millis(100));
+
+        app.insert_resource(time);
+        app.insert_resource(PhysicsSettings {
+            max_delta: None,
+            ..Default::default()
+        });
+        app.add_event::<CollisionEvent>();
+        app.add_systems(Update, (update_simulated, spheres).chain());
+
+        app
+    }
+
+    fn ball(app: &mut App, pos: Vec3, vel: Vec3, inertia: Inertia) -> Entity {
+        let mut body = SimulationBundle::new(
+            Velocity(vel),
+            Accelerator::ZERO,
+            AngularVelocity::ZERO,
+            inertia,
+        );
+        body.spatial.transform = Transform::from_translation(pos);
+
+        app.world_mut()
+            .spawn((body, Shape::Sphere { radius: 1.0 }))
+            .id()
+    }
+
+    #[test]
+    fn bounces_off_static() {
+        let mut app = app();
+        let wall = ball(&mut app, Vec3::ZERO, Vec3::ZERO, Inertia::static_body());
+        let moving = ball(
+            &mut app,
+            Vec3::NEG_X * 3.0,
+            Vec3::X,
+            Inertia::sphere(1.0, 1.0),
+        );
+
+        for _ in 0..40 {
+            app.update();
+        }
+
+        let world = app.world();
+        assert_eq!(
+            world.get::<Transform>(wall).unwrap().translation,
+            Vec3::ZERO
+        );
+        assert_eq!(world.get::<Velocity>(wall).unwrap().0, Vec3::ZERO);
+
+        // Bounced straight back with the same speed
+        let vel = world.get::<Velocity>(moving).unwrap().0;
+        assert_approx_eq!(&[f32], &vel.to_array(), &[-1.0, 0.0, 0.0]);
+        assert!(world.get::<Transform>(moving).unwrap().translation.x < -2.0);
+    }
+
+    #[test]
+    fn equal_masses_swap() {
+        let mut app = app();
+        let a = ball(
+            &mut app,
+            Vec3::NEG_X * 0.95,
+            Vec3::X,
+            Inertia::sphere(1.0, 1.0),
+        );
+        let b = ball(
+            &mut app,
+            Vec3::X * 0.95,
+            Vec3::ZERO,
+            Inertia::sphere(1.0, 1.0),
+        );
+
+        app.world_mut().run_system_once(spheres);
+
+        let world = app.world();
+        assert_approx_eq!(f32, world.get::<Velocity>(a).unwrap().0.x, 0.0);
+        assert_approx_eq!(f32, world.get::<Velocity>(b).unwrap().0.x, 1.0);
+
+        let events = world.resource::<Events<CollisionEvent>>();
+        let event = events.iter_current_update_events().next().unwrap();
+        assert_eq!((event.a, event.b), (a, b));
+        assert_eq!(event.normal, Vec3::X);
+        assert_approx_eq!(f32, event.impulse, 1.0);
+    }
+}
@@ -13,6 +13,62 @@ impl Accelerator {
     pub const ZERO: Self = Self(Vec3::ZERO);
 
     /// [Accelerator] that mimics gravity (-9.82 m/s^2 in y velocity)
+    ///
+    /// Gravity is applied to every object by the
+    /// [PhysicsSettings](crate::resources::PhysicsSettings) now, so adding this on top pulls the
+    /// object down twice.
+    #[deprecated(
+        note = "gravity is applied from the PhysicsSettings, adding this on top doubles it, use \
+                Accelerator::ZERO instead"
+    )]
     pub const GRAVITY: Self = Self(Vec3::new(0.0, -9.82, 0.0));
 }
 
+/// Upper limit on the magnitude of the net linear acceleration of an object
+///
+/// A safety valve for misconfigured scenes, where a large force on a small mass would otherwise
+/// throw the object far away in a single step.
+#[derive(Component, Debug)]
+pub struct MaxAcceleration(pub f32);
+
+impl From<Vec3> for Accelerator {
+    fn from(value: Vec3) -> Self {
+        Self(value)
+    }
+}
+
+impl From<[f32; 3]> for Accelerator {
+    fn from(value: [f32; 3]) -> Self {
+        Self(value.into())
+    }
+}
+
+impl From<(f32, f32, f32)> for Accelerator {
+    fn from(value: (f32, f32, f32)) -> Self {
+        Self(value.into())
+    }
+}
+
+impl From<Accelerator> for Vec3 {
+    fn from(value: Accelerator) -> Self {
+        value.0
+    }
+}
+
+#[cfg(test)]
+mod conversions {
+    use bevy::math::Vec3;
+
+    use super::Accelerator;
+
+    #[test]
+    fn round_trip() {
+        let a = Vec3::new(0.5, -9.82, 0.0);
+
+        assert_eq!(Vec3::from(Accelerator::from(a)), a);
+        assert_eq!(Vec3::from(Accelerator::from([0.5, -9.82, 0.0])), a);
+
+        let into: Vec3 = Accelerator::from((0.5, -9.82, 0.0)).into();
+        assert_eq!(into, a);
+    }
+}
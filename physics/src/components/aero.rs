@@ -0,0 +1,205 @@
+use bevy::{
+    ecs::component::Component,
+    math::{Quat, Vec3},
+};
+
+use super::force::{Force, Moment};
+
+/// Density of air at sea level in kg/m^3
+pub const AIR_DENSITY: f32 = 1.225;
+
+/// A surface that produces lift and drag when moving through air, like a fin or a wing
+///
+/// The forces are applied at [AeroSurface::center], so a surface placed behind the center of mass
+/// will turn the object into the airflow.
+#[derive(Component, Debug)]
+pub struct AeroSurface {
+    /// Where the forces are applied, relative to the center of mass in local cordinates
+    pub center: Vec3,
+
+    /// Reference area of the surface
+    pub area: f32,
+
+    /// Lift coefficient
+    pub cl: f32,
+
+    /// Drag coefficient
+    pub cd: f32,
+}
+
+impl AeroSurface {
+    /// Computes the [Moment] created by moving through the air
+    ///
+    /// `airflow` is the velocity of the object relative to the air. The lift opposes the part of
+    /// the airflow that crosses the objects forward (x) axis and the drag opposes all of it.
+    #[must_use]
+    pub fn get_moment(&self, rotation: Quat, airflow: Vec3, density: f32) -> Moment {
+        let forward = rotation * Vec3::X;
+        let cross_flow = airflow - airflow.project_onto_normalized(forward);
+
+        // Dynamic pressure times area, with one factor of speed left in the vectors below
+        let q = 0.5 * density * self.area * airflow.length();
+
+        let lift = cross_flow * -q * self.cl;
+        let drag = airflow * -q * self.cd;
+
+        Moment::new(rotation * self.center, lift + drag)
+    }
+}
+
+/// Angle in radians between the forward (x) axis of an object and the direction it is moving
+///
+/// Always positive, no matter which side the flow comes from. Zero if the object isn't moving.
+///
+/// ```rust
+/// # use physics::components::aero::angle_of_attack;
+/// # use bevy::math::{Quat, Vec3};
+/// # use std::f32::consts::FRAC_PI_2;
+/// let aoa = angle_of_attack(Quat::IDENTITY, Vec3::Y);
+///
+/// assert!((aoa - FRAC_PI_2).abs() < 1e-6);
+/// ```
+#[must_use]
+pub fn angle_of_attack(orientation: Quat, velocity: Vec3) -> f32 {
+    if velocity == Vec3::ZERO {
+        return 0.0;
+    }
+
+    (orientation * Vec3::X).angle_between(velocity)
+}
+
+/// Makes a spinning object curve sideways when moving through the air, like a ball with backspin
+#[derive(Component, Debug)]
+pub struct MagnusCoefficient(pub f32);
+
+impl MagnusCoefficient {
+    /// Computes the Magnus [Force] for an object spinning at `spin` radians per second
+    ///
+    /// `airflow` is the velocity of the object relative to the air. The force is perpendicular to
+    /// both and zero if either is.
+    #[must_use]
+    pub fn get_force(&self, spin: Vec3, airflow: Vec3) -> Force {
+        Force(spin.cross(airflow) * self.0)
+    }
+}
+
+#[cfg(test)]
+mod restoring {
+    use bevy::math::{Quat, Vec3};
+
+    use super::{AeroSurface, AIR_DENSITY};
+
+    fn rear_fin() -> AeroSurface {
+        AeroSurface {
+            center: Vec3::NEG_X,
+            area: 0.1,
+            cl: 1.0,
+            cd: 0.1,
+        }
+    }
+
+    #[test]
+    fn pitched_up() {
+        let rot = Quat::from_rotation_z(0.05);
+        let torque = rear_fin()
+            .get_moment(rot, Vec3::X * 10.0, AIR_DENSITY)
+            .get_torque();
+
+        assert!(
+            torque.0.z < 0.0,
+            "fin should pitch the nose down: {torque:?}"
+        );
+    }
+
+    #[test]
+    fn pitched_down() {
+        let rot = Quat::from_rotation_z(-0.05);
+        let torque = rear_fin()
+            .get_moment(rot, Vec3::X * 10.0, AIR_DENSITY)
+            .get_torque();
+
+        assert!(torque.0.z > 0.0, "fin should pitch the nose up: {torque:?}");
+    }
+
+    #[test]
+    fn aligned() {
+        let torque = rear_fin()
+            .get_moment(Quat::IDENTITY, Vec3::X * 10.0, AIR_DENSITY)
+            .get_torque();
+
+        assert_eq!(torque.0, Vec3::ZERO);
+    }
+
+    #[test]
+    fn still_air() {
+        let moment = rear_fin().get_moment(Quat::from_rotation_z(0.05), Vec3::ZERO, AIR_DENSITY);
+
+        assert_eq!(moment.get_force().0, Vec3::ZERO);
+        assert_eq!(moment.get_torque().0, Vec3::ZERO);
+    }
+}
+
+#[cfg(test)]
+mod magnus {
+    use bevy::math::Vec3;
+    use float_cmp::assert_approx_eq;
+
+    use super::MagnusCoefficient;
+
+    #[test]
+    fn backspin_lifts() {
+        // Spinning around +z while moving along +x means the top moves backwards
+        let force = MagnusCoefficient(0.5).get_force(Vec3::Z * 4.0, Vec3::X * 10.0);
+
+        assert_approx_eq!(&[f32], &force.0.to_array(), &[0.0, 20.0, 0.0]);
+    }
+
+    #[test]
+    fn topspin_dips() {
+        let force = MagnusCoefficient(0.5).get_force(Vec3::NEG_Z * 4.0, Vec3::X * 10.0);
+
+        assert_approx_eq!(&[f32], &force.0.to_array(), &[0.0, -20.0, 0.0]);
+    }
+
+    #[test]
+    fn no_spin_or_motion() {
+        let magnus = MagnusCoefficient(0.5);
+
+        assert_eq!(magnus.get_force(Vec3::ZERO, Vec3::X).0, Vec3::ZERO);
+        assert_eq!(magnus.get_force(Vec3::Z, Vec3::ZERO).0, Vec3::ZERO);
+    }
+}
+
+#[cfg(test)]
+mod attack {
+    use bevy::math::{Quat, Vec3};
+    use float_cmp::assert_approx_eq;
+
+    use super::angle_of_attack;
+
+    #[test]
+    fn pitched_up() {
+        let pitch = 10f32.to_radians();
+        let aoa = angle_of_attack(Quat::from_rotation_z(pitch), Vec3::X * 50.0);
+
+        assert_approx_eq!(f32, aoa, pitch, epsilon = 1e-6);
+    }
+
+    #[test]
+    fn aligned() {
+        let rot = Quat::from_rotation_y(1.0);
+
+        assert_approx_eq!(
+            f32,
+            angle_of_attack(rot, rot * Vec3::X * 3.0),
+            0.0,
+            epsilon = 1e-3
+        );
+        assert_eq!(angle_of_attack(Quat::IDENTITY, Vec3::X), 0.0);
+    }
+
+    #[test]
+    fn standing_still() {
+        assert_eq!(angle_of_attack(Quat::from_rotation_z(1.0), Vec3::ZERO), 0.0);
+    }
+}
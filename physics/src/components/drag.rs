@@ -0,0 +1,183 @@
+use bevy::{
+    ecs::component::Component,
+    math::{Quat, Vec3},
+};
+
+use super::{force::Force, shape::Shape};
+
+/// Drag that grows with the square of the speed through the air
+#[derive(Component, Debug)]
+pub struct QuadraticDrag {
+    /// Drag coefficient
+    pub cd: f32,
+
+    /// Reference area
+    ///
+    /// When [None], the area of the objects [Shape] facing the airflow is used instead.
+    pub area: Option<f32>,
+}
+
+/// Drag coefficient that depends on the speed through the air, used by [QuadraticDrag] instead of
+/// its own coefficient
+///
+/// Holds `(speed, cd)` samples sorted by speed, linearly interpolated between and clamped to the
+/// first and last one outside of them. Speeds are in m/s, divide a table by Mach by the speed of
+/// sound first.
+#[derive(Component, Debug, Clone, PartialEq)]
+pub struct DragTable(Vec<(f32, f32)>);
+
+impl DragTable {
+    /// Create a new [DragTable], sorting the samples by speed
+    #[must_use]
+    pub fn new(mut samples: Vec<(f32, f32)>) -> Self {
+        samples.sort_by(|a, b| a.0.total_cmp(&b.0));
+        Self(samples)
+    }
+
+    /// Gets the samples, sorted by speed
+    #[must_use]
+    pub fn samples(&self) -> &[(f32, f32)] {
+        &self.0
+    }
+
+    /// Gets the drag coefficient at `speed`, [None] for an empty table
+    #[must_use]
+    pub fn cd(&self, speed: f32) -> Option<f32> {
+        let (first, last) = (self.0.first()?, self.0.last()?);
+
+        if speed <= first.0 {
+            return Some(first.1);
+        }
+        if speed >= last.0 {
+            return Some(last.1);
+        }
+
+        // The first sample faster than the speed, never the first one after the checks above
+        let i = self.0.partition_point(|(s, _)| *s <= speed);
+        let ((s0, cd0), (s1, cd1)) = (self.0[i - 1], self.0[i]);
+
+        Some(cd0 + (cd1 - cd0) * (speed - s0) / (s1 - s0))
+    }
+}
+
+impl QuadraticDrag {
+    /// Create a new [QuadraticDrag] that gets its area from the objects [Shape]
+    #[must_use]
+    pub const fn from_shape(cd: f32) -> Self {
+        Self { cd, area: None }
+    }
+
+    /// Gets this drag with the coefficient from `table` at `speed`, if there is a table
+    #[must_use]
+    pub fn with_table(&self, table: Option<&DragTable>, speed: f32) -> Self {
+        Self {
+            cd: table.and_then(|t| t.cd(speed)).unwrap_or(self.cd),
+            area: self.area,
+        }
+    }
+
+    /// Gets the reference area when moving in a certain direction
+    ///
+    /// Returns zero if there is neither a set area nor a shape to get it from.
+    #[must_use]
+    pub fn area(&self, rotation: Quat, airflow: Vec3, shape: Option<&Shape>) -> f32 {
+        match (self.area, shape) {
+            (Some(area), _) => area,
+            (None, Some(shape)) => shape.frontal_area(rotation.inverse() * airflow),
+            (None, None) => 0.0,
+        }
+    }
+
+    /// Computes the drag [Force] when moving through air
+    ///
+    /// `airflow` is the velocity of the object relative to the air.
+    #[must_use]
+    pub fn get_force(
+        &self,
+        rotation: Quat,
+        airflow: Vec3,
+        density: f32,
+        shape: Option<&Shape>,
+    ) -> Force {
+        let area = self.area(rotation, airflow, shape);
+
+        Force(airflow * airflow.length() * -0.5 * density * self.cd * area)
+    }
+}
+
+#[cfg(test)]
+mod table {
+    use float_cmp::assert_approx_eq;
+
+    use super::DragTable;
+
+    fn table() -> DragTable {
+        // Out of order on purpose, roughly a projectile around the speed of sound
+        DragTable::new(vec![(400.0, 0.3), (100.0, 0.2), (340.0, 0.45)])
+    }
+
+    #[test]
+    fn interpolates() {
+        let table = table();
+
+        assert_approx_eq!(f32, table.cd(100.0).unwrap(), 0.2);
+        assert_approx_eq!(f32, table.cd(220.0).unwrap(), 0.325);
+        assert_approx_eq!(f32, table.cd(340.0).unwrap(), 0.45);
+        assert_approx_eq!(f32, table.cd(370.0).unwrap(), 0.375);
+    }
+
+    #[test]
+    fn clamps() {
+        let table = table();
+
+        assert_eq!(table.cd(0.0), Some(0.2));
+        assert_eq!(table.cd(2000.0), Some(0.3));
+        assert_eq!(DragTable::new(vec![(10.0, 0.7)]).cd(50.0), Some(0.7));
+        assert_eq!(DragTable::new(Vec::new()).cd(50.0), None);
+    }
+}
+
+#[cfg(test)]
+mod quadratic {
+    use std::f32::consts::PI;
+
+    use bevy::math::{Quat, Vec3};
+    use float_cmp::assert_approx_eq;
+
+    use super::QuadraticDrag;
+    use crate::components::shape::{Axis, Shape};
+
+    #[test]
+    fn area_from_shape() {
+        let cyl = Shape::Cylinder {
+            axis: Axis::X,
+            height: 2.0,
+            radius: 0.5,
+        };
+        let drag = QuadraticDrag::from_shape(1.0);
+
+        assert_approx_eq!(
+            f32,
+            drag.area(Quat::IDENTITY, Vec3::X, Some(&cyl)),
+            PI * 0.25
+        );
+
+        // Yawed so the side of the cylinder faces the airflow
+        let yawed = Quat::from_rotation_y(PI / 2.0);
+        assert_approx_eq!(f32, drag.area(yawed, Vec3::X, Some(&cyl)), 2.0);
+
+        assert_eq!(drag.area(Quat::IDENTITY, Vec3::X, None), 0.0);
+    }
+
+    #[test]
+    fn opposes_motion() {
+        let drag = QuadraticDrag {
+            cd: 0.5,
+            area: Some(2.0),
+        };
+
+        let force = drag.get_force(Quat::IDENTITY, Vec3::X * 2.0, 1.0, None);
+
+        assert_approx_eq!(&[f32], &force.0.to_array(), &[-2.0, 0.0, 0.0]);
+    }
+}
@@ -1,4 +1,15 @@
-use bevy::math::Vec3;
+use std::{iter::Sum, ops::Neg};
+
+use bevy::{
+    ecs::component::Component,
+    math::{Quat, Vec3},
+};
+
+/// Sine of the largest angle between an offset and a force still treated as parallel
+///
+/// Rounding in the projection leaves a tiny torque behind for parallel vectors, this snaps it to
+/// zero.
+const PARALLEL_EPSILON: f32 = 1e-5;
 
 /// Represents a force that is not applied at the center of mass
 #[derive(Debug, PartialEq)]
@@ -11,13 +22,80 @@ pub struct Moment {
 }
 
 /// Represents a force applied at the center of mass
+///
+/// In newtons (kg m/s^2), in global cordinates.
 #[derive(Debug, PartialEq)]
 pub struct Force(pub Vec3);
 
 /// Represents a torque being applied on a object
+///
+/// In newton meters (kg m^2/s^2) around each axis, in global cordinates.
 #[derive(Debug, PartialEq)]
 pub struct Torque(pub Vec3);
 
+/// Collects every force and torque applied to an object during a single step
+///
+/// Force systems add to this and the integrator drains it, so anything added will only affect
+/// the step it was added in.
+#[derive(Component, Debug, Default)]
+pub struct ForceAccumulator {
+    /// Sum of all forces applied at the center of mass
+    pub force: Vec3,
+
+    /// Sum of all torques
+    pub torque: Vec3,
+}
+
+/// Copy of the [ForceAccumulator] from the last step, taken right before it was drained
+///
+/// Only kept up to date on objects that have one, add it to read the net force and torque without
+/// summing the forces again.
+#[derive(Component, Debug, Default, Clone, Copy, PartialEq)]
+pub struct LastStepForces {
+    /// Net force at the center of mass
+    pub force: Vec3,
+
+    /// Net torque
+    pub torque: Vec3,
+}
+
+/// Forces applied at fixed points of an object, every step
+///
+/// Offsets and forces are in global cordinates, with the offset relative to the center of mass.
+#[derive(Component, Debug, Default)]
+pub struct MomentList(pub Vec<Moment>);
+
+/// An instant change in momentum, applied at an offset from the center of mass
+///
+/// In newton seconds, in global cordinates. Split the same way as a [Moment], so only the part
+/// across the offset turns the object.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Impulse {
+    /// Offset of the hit from the center of mass
+    pub offset: Vec3,
+
+    /// The impulse being applied
+    pub impulse: Vec3,
+}
+
+/// Impulses waiting to be applied to an object, all of them are applied in order next step
+///
+/// Unlike forces, an impulse is independent of the length of the step, which suits hits and
+/// collisions.
+#[derive(Component, Debug, Default)]
+pub struct ImpulseQueue(pub Vec<Impulse>);
+
+/// A constant torque around a global axis, like from a motor or a reaction wheel
+///
+/// Applied every step, a zero axis applies nothing.
+#[derive(Component, Debug, Clone, Copy, PartialEq)]
+pub struct MotorTorque {
+    pub axis: Vec3,
+
+    /// In newton meters
+    pub magnitude: f32,
+}
+
 impl Moment {
     /// [Moment] with no force in any direction
     pub const ZERO: Self = Self::new(Vec3::ZERO, Vec3::ZERO);
@@ -88,12 +166,164 @@ impl Moment {
                 let force = self.force.project_onto_normalized(offset);
                 let torq = self.offset.cross(self.force - force);
 
+                let limit = PARALLEL_EPSILON * self.offset.length() * self.force.length();
+                if torq.length() <= limit {
+                    return (Torque(Vec3::ZERO), Force(force));
+                }
+
                 (Torque(torq), Force(force))
             }
         }
     }
 }
 
+impl MomentList {
+    /// Gets the sum of the torque and force of every [Moment]
+    ///
+    /// ```rust
+    /// # use bevy::math::Vec3;
+    /// # use physics::components::force::{MomentList, Moment};
+    /// let list = MomentList(vec![Moment::new(Vec3::X, Vec3::Y), Moment::from_force(Vec3::Y)]);
+    ///
+    /// let (t, f) = list.get_parts();
+    ///
+    /// assert_eq!(t.0, Vec3::Z);
+    /// assert_eq!(f.0, Vec3::Y);
+    /// ```
+    #[must_use]
+    pub fn get_parts(&self) -> (Torque, Force) {
+        (
+            self.0.iter().map(Moment::get_torque).sum(),
+            self.0.iter().map(Moment::get_force).sum(),
+        )
+    }
+}
+
+impl Impulse {
+    /// Create a new [Impulse] from an offset and an impulse
+    #[inline]
+    #[must_use]
+    pub const fn new(offset: Vec3, impulse: Vec3) -> Self {
+        Self { offset, impulse }
+    }
+
+    /// Gets the angular and linear impulse as a tuple
+    ///
+    /// ```rust
+    /// # use bevy::math::Vec3;
+    /// # use physics::components::force::Impulse;
+    /// let (angular, linear) = Impulse::new(Vec3::X, Vec3::new(1.0, 1.0, 0.0)).get_parts();
+    ///
+    /// assert_eq!(angular, Vec3::Z);
+    /// assert_eq!(linear, Vec3::X);
+    /// ```
+    #[must_use]
+    pub fn get_parts(&self) -> (Vec3, Vec3) {
+        let (torque, force) = Moment::new(self.offset, self.impulse).get_parts();
+
+        (torque.0, force.0)
+    }
+}
+
+impl MotorTorque {
+    /// Gets the [Torque] applied each step
+    #[must_use]
+    pub fn get_torque(&self) -> Torque {
+        Torque(self.axis.normalize_or_zero() * self.magnitude)
+    }
+}
+
+impl Force {
+    /// Shortens the force to at most `max` newtons, keeping its direction
+    ///
+    /// ```rust
+    /// # use bevy::math::Vec3;
+    /// # use physics::components::force::Force;
+    /// assert_eq!(Force(Vec3::X * 10.0).clamp_length(4.0), Force(Vec3::X * 4.0));
+    /// assert_eq!(Force(Vec3::Y).clamp_length(4.0), Force(Vec3::Y));
+    /// ```
+    #[must_use]
+    pub fn clamp_length(self, max: f32) -> Self {
+        Self(self.0.clamp_length_max(max.max(0.0)))
+    }
+}
+
+impl Torque {
+    /// Shortens the torque to at most `max` newton meters, keeping its axis
+    #[must_use]
+    pub fn clamp_length(self, max: f32) -> Self {
+        Self(self.0.clamp_length_max(max.max(0.0)))
+    }
+}
+
+impl ImpulseQueue {
+    /// Queues an impulse to be applied next step
+    #[inline]
+    pub fn push(&mut self, impulse: Impulse) {
+        self.0.push(impulse);
+    }
+}
+
+impl ForceAccumulator {
+    /// Adds a force applied at the center of mass
+    #[inline]
+    pub fn add_force(&mut self, force: Force) {
+        self.force += force.0;
+    }
+
+    /// Adds a torque
+    #[inline]
+    pub fn add_torque(&mut self, torque: Torque) {
+        self.torque += torque.0;
+    }
+
+    /// Adds a force applied at the center of mass, shortened to at most `max` newtons first
+    ///
+    /// Only the added force is limited, so a runaway controller can't push harder than `max`
+    /// while other forces on the object are left alone.
+    #[inline]
+    pub fn add_force_clamped(&mut self, force: Force, max: f32) {
+        self.add_force(force.clamp_length(max));
+    }
+
+    /// Adds a force given in the local cordinates of an object with this orientation
+    #[inline]
+    pub fn add_local_force(&mut self, local: Vec3, orientation: Quat) {
+        self.add_force(Force(orientation * local));
+    }
+
+    /// Adds a torque given in the local cordinates of an object with this orientation
+    #[inline]
+    pub fn add_local_torque(&mut self, local: Vec3, orientation: Quat) {
+        self.add_torque(Torque(orientation * local));
+    }
+
+    /// Splits a [Moment] into its torque and force and adds both
+    ///
+    /// ```rust
+    /// # use bevy::math::Vec3;
+    /// # use physics::components::force::{ForceAccumulator, Moment};
+    /// let mut acc = ForceAccumulator::default();
+    /// acc.add_moment(&Moment::new(Vec3::X, Vec3::new(1.0, 1.0, 0.0)));
+    ///
+    /// assert_eq!(acc.force, Vec3::X);
+    /// assert_eq!(acc.torque, Vec3::Z);
+    /// ```
+    pub fn add_moment(&mut self, moment: &Moment) {
+        let (torque, force) = moment.get_parts();
+        self.add_torque(torque);
+        self.add_force(force);
+    }
+
+    /// Returns the accumulated torque and force, leaving the accumulator empty
+    #[must_use]
+    pub fn take(&mut self) -> (Torque, Force) {
+        let parts = (Torque(self.torque), Force(self.force));
+        *self = Self::default();
+        parts
+    }
+}
+
 impl From<Moment> for Force {
     fn from(value: Moment) -> Self {
         value.get_force()
@@ -106,6 +336,34 @@ impl From<Moment> for Torque {
     }
 }
 
+impl Neg for Force {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self(-self.0)
+    }
+}
+
+impl Neg for Torque {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self(-self.0)
+    }
+}
+
+impl Sum for Force {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        Self(iter.map(|f| f.0).sum())
+    }
+}
+
+impl Sum for Torque {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        Self(iter.map(|t| t.0).sum())
+    }
+}
+
 #[cfg(test)]
 mod parts {
     use super::Moment;
@@ -139,4 +397,137 @@ mod parts {
         assert_eq!(Moment::new(Vec3::X, Vec3::Y).get_torque().0, Vec3::Z);
         assert_eq!(Moment::new(Vec3::X, Vec3::Z).get_torque().0, Vec3::NEG_Y);
     }
+
+    #[test]
+    fn nearly_parallel() {
+        let offset = Vec3::new(0.3, -1.7, 2.9);
+
+        for force in [
+            offset * 0.7,
+            offset * -13.0,
+            offset * 3.1 + Vec3::new(1e-7, 0.0, -1e-7),
+        ] {
+            let (t, f) = Moment::new(offset, force).get_parts();
+
+            assert_eq!(t.0, Vec3::ZERO, "torque left for {force}");
+            assert!(f.0.distance(force) < 1e-5, "{f:?} isn't {force}");
+        }
+
+        // Barely off parallel still turns
+        let t = Moment::new(Vec3::X, Vec3::new(1.0, 1e-3, 0.0)).get_torque();
+        assert!(t.0.z > 0.0);
+    }
+}
+
+#[cfg(test)]
+mod ops {
+    use bevy::math::Vec3;
+
+    use super::{Force, Moment, Torque};
+
+    #[test]
+    fn negate() {
+        assert_eq!(
+            -Force(Vec3::new(1.0, -2.0, 3.0)),
+            Force(Vec3::new(-1.0, 2.0, -3.0))
+        );
+        assert_eq!(-Torque(Vec3::Z), Torque(Vec3::NEG_Z));
+        assert_eq!(-Force(Vec3::ZERO), Force(Vec3::ZERO));
+    }
+
+    #[test]
+    fn sum() {
+        let forces = [Force(Vec3::X), Force(Vec3::Y * 2.0), Force(Vec3::NEG_X)];
+        assert_eq!(forces.into_iter().sum::<Force>(), Force(Vec3::Y * 2.0));
+
+        let moments = [
+            Moment::new(Vec3::X, Vec3::Y),
+            Moment::new(Vec3::Y, Vec3::Z),
+            Moment::new(Vec3::X, Vec3::Z),
+        ];
+        let torque: Torque = moments.into_iter().map(Torque::from).sum();
+        assert_eq!(torque, Torque(Vec3::new(1.0, -1.0, 1.0)));
+
+        assert_eq!([].into_iter().sum::<Force>(), Force(Vec3::ZERO));
+    }
+
+    #[test]
+    fn equal_and_opposite() {
+        let pull = Force(Vec3::new(0.5, 3.0, -1.0));
+
+        assert_eq!(
+            [-Force(pull.0), pull].into_iter().sum::<Force>(),
+            Force(Vec3::ZERO)
+        );
+    }
+}
+
+#[cfg(test)]
+mod clamping {
+    use bevy::math::Vec3;
+    use float_cmp::assert_approx_eq;
+
+    use super::{Force, ForceAccumulator, Torque};
+
+    #[test]
+    fn over_limit() {
+        let force = Force(Vec3::new(30.0, -40.0, 0.0)).clamp_length(5.0);
+        assert_approx_eq!(&[f32], &force.0.to_array(), &[3.0, -4.0, 0.0]);
+        assert_approx_eq!(f32, force.0.length(), 5.0);
+
+        let torque = Torque(Vec3::new(0.0, 2.0, 2.0)).clamp_length(1.0);
+        assert_approx_eq!(f32, torque.0.length(), 1.0);
+        assert_approx_eq!(f32, torque.0.y, torque.0.z);
+    }
+
+    #[test]
+    fn under_limit() {
+        assert_eq!(
+            Force(Vec3::new(1.0, 2.0, 2.0)).clamp_length(3.0),
+            Force(Vec3::new(1.0, 2.0, 2.0))
+        );
+        assert_eq!(Torque(Vec3::Z).clamp_length(0.0), Torque(Vec3::ZERO));
+    }
+
+    #[test]
+    fn accumulated() {
+        let mut acc = ForceAccumulator::default();
+        acc.add_force(Force(Vec3::Y * 100.0));
+        acc.add_force_clamped(Force(Vec3::X * 1000.0), 10.0);
+
+        // Only the clamped force is limited
+        assert_approx_eq!(&[f32], &acc.force.to_array(), &[10.0, 100.0, 0.0]);
+    }
+}
+
+#[cfg(test)]
+mod local {
+    use std::f32::consts::FRAC_PI_2;
+
+    use bevy::math::{Quat, Vec3};
+    use float_cmp::assert_approx_eq;
+
+    use super::ForceAccumulator;
+
+    #[test]
+    fn yawed() {
+        let yaw = Quat::from_rotation_y(FRAC_PI_2);
+        let mut acc = ForceAccumulator::default();
+
+        acc.add_local_force(Vec3::Z * 2.0, yaw);
+        acc.add_local_torque(Vec3::X, yaw);
+
+        assert_approx_eq!(&[f32], &acc.force.to_array(), &[2.0, 0.0, 0.0]);
+        assert_approx_eq!(&[f32], &acc.torque.to_array(), &[0.0, 0.0, -1.0]);
+    }
+
+    #[test]
+    fn unrotated() {
+        let mut acc = ForceAccumulator::default();
+
+        acc.add_local_force(Vec3::new(1.0, 2.0, 3.0), Quat::IDENTITY);
+
+        assert_eq!(acc.force, Vec3::new(1.0, 2.0, 3.0));
+        assert_eq!(acc.torque, Vec3::ZERO);
+    }
 }
@@ -1,18 +1,79 @@
 use bevy::{
     ecs::component::Component,
-    math::{Mat3, Vec3},
+    math::{Mat3, Quat, Vec3},
 };
 
-use crate::components::force::Torque;
+use crate::components::force::{Force, Torque};
 
 /// An objects mass and inertia tesnsor.
 ///
 /// Used when calculating forces and moments being applied to get a correct rotational and
 /// translational acceleration
 #[derive(Component, Debug)]
-pub struct Inertia(pub Mat3);
+pub struct Inertia {
+    /// One over the total mass of the object
+    ///
+    /// Zero means infinite mass, making the object static. See [Inertia::static_body].
+    pub inverse_mass: f32,
+
+    /// Inertia tensor around the center of mass
+    pub tensor: Mat3,
+}
 
 impl Inertia {
+    /// Create a new [Inertia] from a mass and an inertia tensor
+    #[inline]
+    #[must_use]
+    pub fn new(mass: f32, tensor: Mat3) -> Self {
+        Self {
+            inverse_mass: mass.recip(),
+            tensor,
+        }
+    }
+
+    /// Create a static object with infinite mass
+    ///
+    /// Static objects are never moved by the integrator, but other objects still collide with them.
+    #[inline]
+    #[must_use]
+    pub const fn static_body() -> Self {
+        Self {
+            inverse_mass: 0.0,
+            tensor: Mat3::ZERO,
+        }
+    }
+
+    /// Checks if the object has infinite mass
+    #[inline]
+    #[must_use]
+    pub fn is_static(&self) -> bool {
+        self.inverse_mass == 0.0
+    }
+
+    /// Total mass of the object, infinite for static objects
+    #[inline]
+    #[must_use]
+    pub fn mass(&self) -> f32 {
+        self.inverse_mass.recip()
+    }
+
+    /// Create a new [Inertia] from a full inertia tensor, including products of inertia
+    ///
+    /// # Panics
+    /// This function will panic if the tensor isn't symmetric, with some tolerance for rounding
+    /// errors relative to its largest element
+    #[must_use]
+    pub fn from_tensor(mass: f32, tensor: Mat3) -> Self {
+        let largest = tensor.abs().to_cols_array().into_iter().fold(1.0, f32::max);
+
+        assert!(
+            tensor.abs_diff_eq(tensor.transpose(), largest * 1e-5),
+            "Inertia tensor is not symmetric: {tensor}"
+        );
+
+        Self::new(mass, tensor)
+    }
+
     /// Returns a cylinder with the height going in the x direction
     pub fn cylinder_x(height: f32, radius: f32, mass: f32) -> Self {
         let h2 = height.powi(2);
@@ -22,11 +83,14 @@ impl Inertia {
         let side = m * h2 / 12.0 + m * r2 / 4.0;
         let front = m * r2 / 2.0;
 
-        Self(Mat3::from_cols_array_2d(&[
-            [front, 0.0, 0.0],
-            [0.0, side, 0.0],
-            [0.0, 0.0, side],
-        ]))
+        Self {
+            inverse_mass: mass.recip(),
+            tensor: Mat3::from_cols_array_2d(&[
+                [front, 0.0, 0.0],
+                [0.0, side, 0.0],
+                [0.0, 0.0, side],
+            ]),
+        }
     }
 
     /// Returns a cylinder with the height going in the y direction
@@ -38,11 +102,14 @@ impl Inertia {
         let side = m * h2 / 12.0 + m * r2 / 4.0;
         let front = m * r2 / 2.0;
 
-        Self(Mat3::from_cols_array_2d(&[
-            [side, 0.0, 0.0],
-            [0.0, front, 0.0],
-            [0.0, 0.0, side],
-        ]))
+        Self {
+            inverse_mass: mass.recip(),
+            tensor: Mat3::from_cols_array_2d(&[
+                [side, 0.0, 0.0],
+                [0.0, front, 0.0],
+                [0.0, 0.0, side],
+            ]),
+        }
     }
 
     /// Returns a cylinder with the height going in the z direction
@@ -54,16 +121,188 @@ impl Inertia {
         let side = m * h2 / 12.0 + m * r2 / 4.0;
         let front = m * r2 / 2.0;
 
-        Self(Mat3::from_cols_array_2d(&[
-            [side, 0.0, 0.0],
-            [0.0, side, 0.0],
-            [0.0, 0.0, front],
-        ]))
+        Self {
+            inverse_mass: mass.recip(),
+            tensor: Mat3::from_cols_array_2d(&[
+                [side, 0.0, 0.0],
+                [0.0, side, 0.0],
+                [0.0, 0.0, front],
+            ]),
+        }
+    }
+
+    /// Returns a hollow cylinder, like a tube or ring, with the height going in the y direction
+    ///
+    /// # Panics
+    /// This function will panic if `inner_radius` isn't between zero and `outer_radius`
+    pub fn tube_y(height: f32, outer_radius: f32, inner_radius: f32, mass: f32) -> Self {
+        assert!(
+            (0.0..outer_radius).contains(&inner_radius),
+            "Inner radius {inner_radius} has to be between 0 and the outer radius {outer_radius}"
+        );
+
+        let h2 = height.powi(2);
+        let r2 = outer_radius.powi(2) + inner_radius.powi(2);
+        let m = mass;
+
+        let side = m * (3.0 * r2 + h2) / 12.0;
+        let front = m * r2 / 2.0;
+
+        Self {
+            inverse_mass: mass.recip(),
+            tensor: Mat3::from_cols_array_2d(&[
+                [side, 0.0, 0.0],
+                [0.0, front, 0.0],
+                [0.0, 0.0, side],
+            ]),
+        }
+    }
+
+    /// Returns a solid sphere
+    pub fn sphere(radius: f32, mass: f32) -> Self {
+        let i = 0.4 * mass * radius.powi(2);
+
+        Self::new(mass, Mat3::from_diagonal(Vec3::splat(i)))
+    }
+
+    /// Returns a solid box with the sides `x`, `y` and `z` long along each local axis
+    pub fn cuboid(x: f32, y: f32, z: f32, mass: f32) -> Self {
+        let (x2, y2, z2) = (x.powi(2), y.powi(2), z.powi(2));
+
+        Self::new(
+            mass,
+            Mat3::from_diagonal(Vec3::new(y2 + z2, x2 + z2, x2 + y2) * mass / 12.0),
+        )
+    }
+
+    /// Estimates the inertia of anything from its mass and the half extents of its bounding box
+    ///
+    /// Treats the object as a solid [Inertia::cuboid] filling the box, which is close enough for
+    /// imported meshes without a better shape.
+    pub fn from_mass_and_bounds(mass: f32, half_extents: Vec3) -> Self {
+        let size = half_extents * 2.0;
+
+        Self::cuboid(size.x, size.y, size.z, mass)
+    }
+
+    /// Returns an object with the given radius of gyration around each local axis
+    ///
+    /// The radius of gyration is the distance from the axis where all the mass could be put
+    /// without changing the moment of inertia, so each moment is `mass * k^2`.
+    pub fn from_gyration(mass: f32, kx: f32, ky: f32, kz: f32) -> Self {
+        let k = Vec3::new(kx, ky, kz);
+
+        Self::new(mass, Mat3::from_diagonal(k * k * mass))
     }
 
     /// Computes the resulting angular acceleration when applying a certain torque
+    ///
+    /// Static objects never accelerate.
     pub fn get_angular_acceleration(&self, torque: Torque) -> Vec3 {
-        self.0.inverse().mul_vec3(torque.0)
+        if self.is_static() {
+            return Vec3::ZERO;
+        }
+
+        self.tensor.inverse().mul_vec3(torque.0)
+    }
+
+    /// Gets the inverse of the tensor turned into global cordinates by `rotation`
+    ///
+    /// Zero for static objects, which never start turning.
+    #[must_use]
+    pub fn world_inverse_tensor(&self, rotation: Quat) -> Mat3 {
+        if self.is_static() {
+            return Mat3::ZERO;
+        }
+
+        let rot = Mat3::from_quat(rotation);
+        rot * self.tensor.inverse() * rot.transpose()
+    }
+
+    /// Computes the resulting translational acceleration when applying a certain force
+    pub fn get_acceleration(&self, force: Force) -> Vec3 {
+        force.0 * self.inverse_mass
+    }
+
+    /// Computes the total kinetic energy, both translational and rotational
+    ///
+    /// `angular_velocity` has to be in the same frame as the tensor.
+    #[must_use]
+    pub fn kinetic_energy(&self, velocity: Vec3, angular_velocity: Vec3) -> f32 {
+        // Static objects don't move, avoid multiplying infinity by zero
+        if self.is_static() {
+            return 0.0;
+        }
+
+        let linear = self.mass() * velocity.length_squared();
+        let angular = angular_velocity.dot(self.tensor * angular_velocity);
+
+        0.5 * (linear + angular)
+    }
+}
+
+#[cfg(test)]
+mod tensor {
+    use bevy::math::{Mat3, Vec3};
+    use float_cmp::assert_approx_eq;
+
+    use super::Inertia;
+    use crate::components::force::Torque;
+
+    #[test]
+    fn products_of_inertia() {
+        let inertia = Inertia::from_tensor(
+            1.0,
+            Mat3::from_cols_array_2d(&[[2.0, -1.0, 0.0], [-1.0, 2.0, 0.0], [0.0, 0.0, 1.0]]),
+        );
+
+        // The upper block inverts to 1/3 * [[2, 1], [1, 2]], so a torque around x also
+        // accelerates around y
+        let angacc = inertia.get_angular_acceleration(Torque(Vec3::X));
+        assert_approx_eq!(&[f32], &angacc.to_array(), &[2.0 / 3.0, 1.0 / 3.0, 0.0]);
+
+        let angacc = inertia.get_angular_acceleration(Torque(Vec3::Z * 2.0));
+        assert_approx_eq!(&[f32], &angacc.to_array(), &[0.0, 0.0, 2.0]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn asymmetric() {
+        let _ = Inertia::from_tensor(
+            1.0,
+            Mat3::from_cols_array_2d(&[[2.0, -1.0, 0.0], [1.0, 2.0, 0.0], [0.0, 0.0, 1.0]]),
+        );
+    }
+}
+
+#[cfg(test)]
+mod static_body {
+    use bevy::math::Vec3;
+
+    use super::Inertia;
+    use crate::components::force::{Force, Torque};
+
+    #[test]
+    fn never_accelerates() {
+        let wall = Inertia::static_body();
+
+        assert!(wall.is_static());
+        assert_eq!(wall.mass(), f32::INFINITY);
+        assert_eq!(wall.get_acceleration(Force(Vec3::X * 1e6)), Vec3::ZERO);
+        assert_eq!(
+            wall.get_angular_acceleration(Torque(Vec3::Y * 1e6)),
+            Vec3::ZERO
+        );
+        assert_eq!(wall.kinetic_energy(Vec3::X, Vec3::Y), 0.0);
+    }
+
+    #[test]
+    fn inverse_mass() {
+        let body = Inertia::cylinder_y(1.0, 1.0, 4.0);
+
+        assert!(!body.is_static());
+        assert_eq!(body.inverse_mass, 0.25);
+        assert_eq!(body.mass(), 4.0);
     }
 }
 
@@ -78,7 +317,7 @@ mod constructors {
         #[test]
         fn thin() {
             assert_eq!(
-                Inertia::cylinder_x(4.0, 0.5, 20.0).0,
+                Inertia::cylinder_x(4.0, 0.5, 20.0).tensor,
                 Mat3::from_cols_array_2d(&[
                     [5.0 / 2.0, 0.0, 0.0],
                     [0.0, 335.0 / 12.0, 0.0],
@@ -87,7 +326,7 @@ mod constructors {
             );
 
             assert_eq!(
-                Inertia::cylinder_y(4.0, 0.5, 20.0).0,
+                Inertia::cylinder_y(4.0, 0.5, 20.0).tensor,
                 Mat3::from_cols_array_2d(&[
                     [335.0 / 12.0, 0.0, 0.0],
                     [0.0, 5.0 / 2.0, 0.0],
@@ -96,7 +335,7 @@ mod constructors {
             );
 
             assert_eq!(
-                Inertia::cylinder_z(4.0, 0.5, 20.0).0,
+                Inertia::cylinder_z(4.0, 0.5, 20.0).tensor,
                 Mat3::from_cols_array_2d(&[
                     [335.0 / 12.0, 0.0, 0.0],
                     [0.0, 335.0 / 12.0, 0.0],
@@ -115,7 +354,7 @@ mod constructors {
         fn x_cylinder() {
             let cyl = Inertia::cylinder_x(1.0, 1.0, 1.0);
             assert_eq!(
-                cyl.0,
+                cyl.tensor,
                 Mat3::from_cols_array_2d(&[
                     [1.0 / 2.0, 0.0, 0.0],
                     [0.0, 1.0 / 3.0, 0.0],
@@ -128,7 +367,7 @@ mod constructors {
         fn y_cylinder() {
             let cyl = Inertia::cylinder_y(1.0, 1.0, 1.0);
             assert_eq!(
-                cyl.0,
+                cyl.tensor,
                 Mat3::from_cols_array_2d(&[
                     [1.0 / 3.0, 0.0, 0.0],
                     [0.0, 1.0 / 2.0, 0.0],
@@ -141,7 +380,7 @@ mod constructors {
         fn z_cylinder() {
             let cyl = Inertia::cylinder_z(1.0, 1.0, 1.0);
             assert_eq!(
-                cyl.0,
+                cyl.tensor,
                 Mat3::from_cols_array_2d(&[
                     [1.0 / 3.0, 0.0, 0.0],
                     [0.0, 1.0 / 3.0, 0.0],
@@ -151,3 +390,112 @@ mod constructors {
         }
     }
 }
+
+#[cfg(test)]
+mod gyration {
+    use bevy::math::{Mat3, Vec3};
+    use float_cmp::assert_approx_eq;
+
+    use super::Inertia;
+
+    #[test]
+    fn matches_cylinder() {
+        let (height, radius, mass) = (4.0f32, 0.5f32, 20.0);
+
+        // Radii of gyration of a solid cylinder around its axis and across it
+        let axial = radius / 2f32.sqrt();
+        let across = (height.powi(2) / 12.0 + radius.powi(2) / 4.0).sqrt();
+
+        let gyration = Inertia::from_gyration(mass, axial, across, across);
+        let cylinder = Inertia::cylinder_x(height, radius, mass);
+
+        assert_eq!(gyration.mass(), mass);
+        assert_approx_eq!(
+            &[f32],
+            &gyration.tensor.to_cols_array(),
+            &cylinder.tensor.to_cols_array(),
+            epsilon = 1e-4
+        );
+    }
+
+    #[test]
+    fn diagonal() {
+        let inertia = Inertia::from_gyration(2.0, 1.0, 2.0, 3.0);
+
+        assert_eq!(
+            inertia.tensor,
+            Mat3::from_diagonal(Vec3::new(2.0, 8.0, 18.0))
+        );
+    }
+}
+
+#[cfg(test)]
+mod cuboid {
+    use bevy::math::{Mat3, Vec3};
+    use float_cmp::assert_approx_eq;
+
+    use super::Inertia;
+
+    #[test]
+    fn solid_box() {
+        // m/12 * (b^2 + c^2) around each axis, from the other two sides
+        let inertia = Inertia::cuboid(1.0, 2.0, 3.0, 12.0);
+
+        assert_approx_eq!(
+            &[f32],
+            &inertia.tensor.to_cols_array(),
+            &Mat3::from_diagonal(Vec3::new(13.0, 10.0, 5.0)).to_cols_array()
+        );
+        assert_eq!(inertia.mass(), 12.0);
+    }
+
+    #[test]
+    fn from_bounds() {
+        let bounds = Inertia::from_mass_and_bounds(5.0, Vec3::new(0.5, 1.5, 0.25));
+        let cuboid = Inertia::cuboid(1.0, 3.0, 0.5, 5.0);
+
+        assert_eq!(bounds.tensor, cuboid.tensor);
+        assert_eq!(bounds.inverse_mass, cuboid.inverse_mass);
+    }
+}
+
+#[cfg(test)]
+mod tube {
+    use bevy::math::Mat3;
+    use float_cmp::assert_approx_eq;
+
+    use super::Inertia;
+
+    #[test]
+    fn hollow() {
+        // m/2 * (r1^2 + r2^2) around the axis, m/12 * (3 * (r1^2 + r2^2) + h^2) across it
+        let tube = Inertia::tube_y(2.0, 1.0, 0.5, 4.0);
+
+        assert_approx_eq!(
+            &[f32],
+            &tube.tensor.to_cols_array(),
+            &Mat3::from_cols_array_2d(&[
+                [31.0 / 12.0, 0.0, 0.0],
+                [0.0, 2.5, 0.0],
+                [0.0, 0.0, 31.0 / 12.0],
+            ])
+            .to_cols_array()
+        );
+        assert_eq!(tube.inverse_mass, 0.25);
+    }
+
+    #[test]
+    fn solid() {
+        assert_approx_eq!(
+            &[f32],
+            &Inertia::tube_y(4.0, 0.5, 0.0, 20.0).tensor.to_cols_array(),
+            &Inertia::cylinder_y(4.0, 0.5, 20.0).tensor.to_cols_array()
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn inner_too_large() {
+        let _ = Inertia::tube_y(1.0, 0.5, 0.5, 1.0);
+    }
+}
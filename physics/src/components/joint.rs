@@ -0,0 +1,30 @@
+use bevy::{
+    ecs::{component::Component, entity::Entity},
+    math::{Mat3, Vec3},
+};
+
+/// Pins a point on this object to a point on `other`, leaving both free to rotate around it
+///
+/// Both anchors are offsets from the center of mass, in the local cordinates of their own object.
+/// Enforced after every integration step by [ball_joints](crate::constraints::ball_joints).
+#[derive(Component, Debug, Clone, Copy, PartialEq)]
+pub struct BallJoint {
+    /// The object on the other side of the joint
+    pub other: Entity,
+
+    /// Where the joint is on this object
+    pub anchor_a: Vec3,
+
+    /// Where the joint is on `other`
+    pub anchor_b: Vec3,
+}
+
+/// Gets the matrix taking a vector `v` to `r × v`
+#[must_use]
+pub(crate) fn cross_matrix(r: Vec3) -> Mat3 {
+    Mat3::from_cols(
+        Vec3::new(0.0, r.z, -r.y),
+        Vec3::new(-r.z, 0.0, r.x),
+        Vec3::new(r.y, -r.x, 0.0),
+    )
+}
@@ -1,15 +1,31 @@
+use bevy::color::Color;
 use bevy::ecs::{bundle::Bundle, component::Component};
+use bevy::math::{BVec3, Vec3};
 use bevy::prelude::SpatialBundle;
 
+use crate::components::force::ForceAccumulator;
 use crate::components::inertia::Inertia;
 use crate::components::velocity::{AngularVelocity, Velocity};
 use crate::components::acceleration::Accelerator;
 
 pub mod acceleration;
+pub mod aero;
+pub mod drag;
 pub mod force;
 pub mod inertia;
+pub mod joint;
+pub mod shape;
+pub mod spring;
 pub mod velocity;
 
+/// Everything an object needs to be simulated
+///
+/// Every non-static object is pulled by the gravity of the
+/// [PhysicsSettings](crate::resources::PhysicsSettings), 9.82 m/s^2 down along the
+/// [UpAxis](crate::resources::UpAxis) unless set otherwise. This used to be opt in through
+/// [SimulationBundle::new_with_gravity], so objects made with [SimulationBundle::new] now fall
+/// too. Set the gravity of the settings to `Some(Vec3::ZERO)` for scenes that should float, or
+/// give single objects a zero [GravityOverride].
 #[derive(Bundle)]
 pub struct SimulationBundle {
     pub spatial: SpatialBundle,
@@ -18,6 +34,7 @@ pub struct SimulationBundle {
     pub angvel: AngularVelocity,
     pub inertia: Inertia,
     pub acc: Accelerator,
+    pub forces: ForceAccumulator,
 }
 
 impl SimulationBundle {
@@ -29,12 +46,24 @@ impl SimulationBundle {
             angvel,
             inertia,
             acc,
+            forces: ForceAccumulator::default(),
         }
     }
+
+    /// Same as [SimulationBundle::new] without angular velocity or extra acceleration
+    ///
+    /// Gravity used to be opt in through this constructor. Every object falls by the gravity of
+    /// the [PhysicsSettings](crate::resources::PhysicsSettings) now, so call
+    /// [SimulationBundle::new] with [Accelerator::ZERO] instead. Don't pass the deprecated
+    /// `Accelerator::GRAVITY` there, that pulls the object down twice.
+    #[deprecated(
+        note = "gravity now pulls on every object from the PhysicsSettings, use new and set the \
+                gravity there to Some(Vec3::ZERO) for objects that shouldn't fall"
+    )]
     pub fn new_with_gravity(vel: Velocity, inertia: Inertia) -> Self {
         Self::new(
             vel,
-            Accelerator::GRAVITY,
+            Accelerator::ZERO,
             AngularVelocity::ZERO,
             inertia,
         )
@@ -46,3 +75,50 @@ impl SimulationBundle {
 /// Remove to easily stop something from being simulated.
 #[derive(Component, Debug)]
 pub struct Simulated;
+
+/// Marker for objects in orbit around the [CentralBody](crate::resources::CentralBody)
+///
+/// Only the gravity of the central body acts on these, every other force is skipped.
+#[derive(Component, Debug)]
+pub struct Orbiting;
+
+/// Color of an object, used for its material and the arrows drawn on it
+///
+/// Objects without one keep the colors of their scene and the defaults in
+/// [ArrowSettings](crate::vector_arrows::ArrowSettings).
+#[derive(Component, Debug, Clone, Copy, PartialEq)]
+pub struct BodyColor(pub Color);
+
+/// Keeps an object in the plane through the origin with this normal
+///
+/// After every step the position and velocity lose their parts along the normal, and the
+/// angular velocity keeps only its part around the normal, as anything else would turn the object
+/// out of the plane.
+#[derive(Component, Debug, Clone, Copy, PartialEq)]
+pub struct PlanarConstraint {
+    pub normal: Vec3,
+}
+
+/// Locks movement along and rotation around some of the global axes
+///
+/// The locked parts of the velocity, angular velocity and accumulated forces are dropped right
+/// before every step, and whatever else moved the object along the locked axes is undone after
+/// the collisions, so the object can only move and turn along the free axes.
+#[derive(Component, Debug, Clone, Copy, PartialEq)]
+pub struct AxisLock {
+    pub lock_translation: BVec3,
+    pub lock_rotation: BVec3,
+}
+
+/// Gravity for this object alone, replacing the global [Gravity](crate::resources::Gravity)
+///
+/// For objects on a tilted surface or in a rotating reference frame.
+#[derive(Component, Debug, Clone, Copy, PartialEq)]
+pub struct GravityOverride(pub Vec3);
+
+/// Tags an object as a member of a group, to push every member at once with
+/// [apply_to_group](crate::forces::apply_to_group)
+///
+/// The number means nothing else, pick any for each group.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct BodyGroup(pub u32);
@@ -0,0 +1,161 @@
+use std::f32::consts::PI;
+
+use bevy::{ecs::component::Component, math::Vec3};
+use serde::{Deserialize, Serialize};
+
+use super::inertia::Inertia;
+
+/// One of the local cordinate axes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Axis {
+    X,
+    Y,
+    Z,
+}
+
+/// The geometric shape an objects [Inertia] is generated from
+///
+/// Kept next to the [Inertia] so other systems, like drag and collisions, can use the dimensions
+/// of the object.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum Shape {
+    /// Solid cylinder with the height going along `axis`
+    Cylinder {
+        axis: Axis,
+        height: f32,
+        radius: f32,
+    },
+
+    /// Solid sphere
+    Sphere { radius: f32 },
+}
+
+impl Axis {
+    /// Gets the unit vector pointing along this axis
+    #[must_use]
+    pub const fn to_vec3(self) -> Vec3 {
+        match self {
+            Axis::X => Vec3::X,
+            Axis::Y => Vec3::Y,
+            Axis::Z => Vec3::Z,
+        }
+    }
+}
+
+impl Shape {
+    /// Computes the [Inertia] of this shape with a certain mass
+    #[must_use]
+    pub fn inertia(&self, mass: f32) -> Inertia {
+        match *self {
+            Shape::Cylinder {
+                axis,
+                height,
+                radius,
+            } => match axis {
+                Axis::X => Inertia::cylinder_x(height, radius, mass),
+                Axis::Y => Inertia::cylinder_y(height, radius, mass),
+                Axis::Z => Inertia::cylinder_z(height, radius, mass),
+            },
+            Shape::Sphere { radius } => Inertia::sphere(radius, mass),
+        }
+    }
+
+    /// Computes the area of the shape projected onto a plane perpendicular to `direction`
+    ///
+    /// `direction` is in local cordinates. If it is zero, the area is zero.
+    ///
+    /// ```rust
+    /// # use physics::components::shape::{Axis, Shape};
+    /// # use bevy::math::Vec3;
+    /// let rod = Shape::Cylinder { axis: Axis::X, height: 2.0, radius: 0.5 };
+    ///
+    /// assert_eq!(rod.frontal_area(Vec3::Y), 2.0);
+    /// ```
+    #[must_use]
+    pub fn frontal_area(&self, direction: Vec3) -> f32 {
+        let Some(direction) = direction.try_normalize() else {
+            return 0.0;
+        };
+
+        match *self {
+            Shape::Cylinder {
+                axis,
+                height,
+                radius,
+            } => {
+                // The end caps are seen with the cosine of the angle from the axis and the side
+                // with the sine of it
+                let cos = direction.dot(axis.to_vec3()).abs();
+                let sin = (1.0 - cos.powi(2)).max(0.0).sqrt();
+
+                PI * radius.powi(2) * cos + 2.0 * radius * height * sin
+            }
+            Shape::Sphere { radius } => PI * radius.powi(2),
+        }
+    }
+}
+
+#[cfg(test)]
+mod frontal_area {
+    use std::f32::consts::PI;
+
+    use bevy::math::Vec3;
+    use float_cmp::assert_approx_eq;
+
+    use super::{Axis, Shape};
+
+    #[test]
+    fn cylinder() {
+        for (axis, along) in [(Axis::X, Vec3::X), (Axis::Y, Vec3::Y), (Axis::Z, Vec3::Z)] {
+            let cyl = Shape::Cylinder {
+                axis,
+                height: 4.0,
+                radius: 0.5,
+            };
+
+            assert_approx_eq!(f32, cyl.frontal_area(along), PI * 0.25);
+            assert_approx_eq!(f32, cyl.frontal_area(-along), PI * 0.25);
+        }
+
+        let cyl = Shape::Cylinder {
+            axis: Axis::Y,
+            height: 4.0,
+            radius: 0.5,
+        };
+
+        assert_approx_eq!(f32, cyl.frontal_area(Vec3::X), 4.0);
+        assert_eq!(cyl.frontal_area(Vec3::ZERO), 0.0);
+    }
+
+    #[test]
+    fn sphere() {
+        let ball = Shape::Sphere { radius: 2.0 };
+
+        for dir in [Vec3::X, Vec3::NEG_Y, Vec3::new(1.0, 2.0, 3.0)] {
+            assert_approx_eq!(f32, ball.frontal_area(dir), PI * 4.0);
+        }
+        assert_eq!(ball.frontal_area(Vec3::ZERO), 0.0);
+    }
+
+    #[test]
+    fn inertia() {
+        let cyl = Shape::Cylinder {
+            axis: Axis::Z,
+            height: 4.0,
+            radius: 0.5,
+        };
+
+        assert_eq!(
+            cyl.inertia(20.0).tensor,
+            super::Inertia::cylinder_z(4.0, 0.5, 20.0).tensor
+        );
+
+        // 2/5 * m * r^2 around every axis
+        let ball = Shape::Sphere { radius: 0.5 }.inertia(10.0);
+        assert_approx_eq!(
+            &[f32],
+            &ball.tensor.to_cols_array(),
+            &[1.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0]
+        );
+    }
+}
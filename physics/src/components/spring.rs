@@ -0,0 +1,55 @@
+use bevy::{ecs::component::Component, math::Vec3};
+
+use super::force::Force;
+
+/// Zero length spring and damper tying an object to a fixed point
+///
+/// The force is linear in both the offset and the velocity, so a lone object behaves exactly like
+/// the textbook mass-spring-damper along every axis.
+#[derive(Component, Debug, Clone, Copy, PartialEq)]
+pub struct Spring {
+    /// Point the object is pulled towards, in global cordinates
+    pub anchor: Vec3,
+
+    /// Spring constant in newtons per meter
+    pub stiffness: f32,
+
+    /// Damping coefficient in newton seconds per meter
+    pub damping: f32,
+}
+
+impl Spring {
+    /// Computes the [Force] on an object at `position` moving with `velocity`
+    #[must_use]
+    pub fn get_force(&self, position: Vec3, velocity: Vec3) -> Force {
+        Force((position - self.anchor) * -self.stiffness - velocity * self.damping)
+    }
+
+    /// Computes the energy stored in the spring with the object at `position`, in joules
+    #[must_use]
+    pub fn potential_energy(&self, position: Vec3) -> f32 {
+        0.5 * self.stiffness * position.distance_squared(self.anchor)
+    }
+}
+
+#[cfg(test)]
+mod hooke {
+    use bevy::math::Vec3;
+    use float_cmp::assert_approx_eq;
+
+    use super::Spring;
+
+    #[test]
+    fn pulls_back() {
+        let spring = Spring {
+            anchor: Vec3::Y,
+            stiffness: 4.0,
+            damping: 0.5,
+        };
+
+        assert_eq!(spring.get_force(Vec3::Y, Vec3::ZERO).0, Vec3::ZERO);
+
+        let force = spring.get_force(Vec3::new(2.0, 1.0, 0.0), Vec3::Z * 2.0).0;
+        assert_approx_eq!(&[f32], &force.to_array(), &[-8.0, 0.0, -1.0]);
+    }
+}
@@ -1,6 +1,7 @@
 use bevy::{ecs::component::Component, math::{Quat, Vec3}};
 
 use super::acceleration::Accelerator;
+use crate::resources::UpAxis;
 
 
 /// Stores the current translational Velocity
@@ -15,6 +16,27 @@ pub struct Velocity(pub Vec3);
 #[derive(Component, Debug)]
 pub struct AngularVelocity(pub Vec3);
 
+/// Marks objects that should always point in the direction they are moving
+///
+/// The rotation is replaced by [Velocity::to_direction] after every step, unless the object is
+/// standing still.
+#[derive(Component, Debug)]
+pub struct AlignToVelocity;
+
+/// Rotates a child entity, like a mesh, to face the direction its parent is moving
+///
+/// The parent keeps its own rotation, only the child turns. Every frame the child gets whatever
+/// local rotation makes its global rotation [Velocity::to_direction] of the parent, unless the
+/// parent is standing still.
+#[derive(Component, Debug)]
+pub struct OrientWithParentVelocity;
+
+/// Limits the magnitude of an objects [AngularVelocity]
+///
+/// The angular velocity is clamped after every step, keeping its axis.
+#[derive(Component, Debug)]
+pub struct MaxAngularSpeed(pub f32);
+
 
 impl Velocity {
     /// [Velocity] of zero in every direction
@@ -58,11 +80,110 @@ impl Velocity {
     pub fn to_direction(&self) -> Quat {
         Quat::from_euler(bevy::math::EulerRot::YXZ, self.yaw(), 0.0, self.pitch())
     }
+
+    /// Same as [Velocity::to_direction], with `up` as the up axis of the world
+    ///
+    /// The top of the object faces `up` when flying level.
+    pub fn to_direction_in(&self, up: UpAxis) -> Quat {
+        let rotation = up.to_y_up();
+
+        rotation.inverse() * Velocity(rotation * self.0).to_direction() * rotation
+    }
+
+    /// Computes the angle from the horizontal plane to the velocity, with `up` as the up axis
+    pub fn pitch_in(&self, up: UpAxis) -> f32 {
+        Velocity(up.to_y_up() * self.0).pitch()
+    }
+
+    /// Computes the horizontal angle from the x axis to the velocity, counterclockwise when
+    /// looking down along `up`
+    pub fn yaw_in(&self, up: UpAxis) -> f32 {
+        Velocity(up.to_y_up() * self.0).yaw()
+    }
+
+    /// Bounces off a surface with the unit `normal`
+    ///
+    /// The part along the normal is reversed and scaled by `restitution`, the part along the
+    /// surface is kept as is.
+    ///
+    /// ```rust
+    /// # use physics::components::velocity::Velocity;
+    /// # use bevy::math::Vec3;
+    /// let mut v = Velocity(Vec3::new(2.0, -4.0, 0.0));
+    /// v.reflect(Vec3::Y, 0.5);
+    ///
+    /// assert_eq!(v.0, Vec3::new(2.0, 2.0, 0.0));
+    /// ```
+    pub fn reflect(&mut self, normal: Vec3, restitution: f32) {
+        let along = self.0.dot(normal) * normal;
+        self.0 -= along * (1.0 + restitution);
+    }
+
+    /// Gets the signed speed along `dir`, or zero when `dir` is zero
+    ///
+    /// The direction doesn't need to be normalized.
+    ///
+    /// ```rust
+    /// # use physics::components::velocity::Velocity;
+    /// # use bevy::math::Vec3;
+    /// let v = Velocity(Vec3::new(3.0, -4.0, 0.0));
+    ///
+    /// assert_eq!(v.component_along(Vec3::Y * 2.0), -4.0);
+    /// assert_eq!(v.component_along(Vec3::ZERO), 0.0);
+    /// ```
+    #[must_use]
+    pub fn component_along(&self, dir: Vec3) -> f32 {
+        self.0.dot(dir.normalize_or_zero())
+    }
+
+    /// Gets the part of the velocity along `dir`, or zero when `dir` is zero
+    ///
+    /// What is left after subtracting it is the part perpendicular to `dir`, so splitting a
+    /// velocity into radial and tangential parts is one call.
+    #[must_use]
+    pub fn project_onto(&self, dir: Vec3) -> Vec3 {
+        let dir = dir.normalize_or_zero();
+        self.0.dot(dir) * dir
+    }
 }
 
 impl AngularVelocity {
     /// [AngularVelocity] of zero in every direction
     pub const ZERO: Self = Self(Vec3::ZERO);
+
+    /// Create an [AngularVelocity] spinning around `axis` at `rate` radians per second
+    ///
+    /// The axis doesn't need to be normalized, but an axis of zero results in no spin.
+    ///
+    /// ```rust
+    /// # use physics::components::velocity::AngularVelocity;
+    /// # use bevy::math::Vec3;
+    /// let spin = AngularVelocity::from_axis_rate(Vec3::Y * 3.0, 2.0);
+    ///
+    /// assert_eq!(spin.0, Vec3::Y * 2.0);
+    /// ```
+    #[must_use]
+    pub fn from_axis_rate(axis: Vec3, rate: f32) -> Self {
+        Self(axis.normalize_or_zero() * rate)
+    }
+
+    /// Gets the unit axis the object spins around, or [None] when it isn't spinning
+    ///
+    /// ```rust
+    /// # use physics::components::velocity::AngularVelocity;
+    /// # use bevy::math::Vec3;
+    /// assert_eq!(AngularVelocity(Vec3::Z * -3.0).axis(), Some(Vec3::NEG_Z));
+    /// assert_eq!(AngularVelocity::ZERO.axis(), None);
+    /// ```
+    #[must_use]
+    pub fn axis(&self) -> Option<Vec3> {
+        self.0.try_normalize()
+    }
+
+    /// Limits the rotation speed to `max` radians per second without changing the axis
+    pub fn clamp_speed(&mut self, max: f32) {
+        self.0 = self.0.clamp_length_max(max);
+    }
 }
 
 impl Velocity {
@@ -80,6 +201,84 @@ impl Velocity {
     }
 }
 
+impl From<Vec3> for Velocity {
+    fn from(value: Vec3) -> Self {
+        Self(value)
+    }
+}
+
+impl From<[f32; 3]> for Velocity {
+    fn from(value: [f32; 3]) -> Self {
+        Self(value.into())
+    }
+}
+
+impl From<(f32, f32, f32)> for Velocity {
+    fn from(value: (f32, f32, f32)) -> Self {
+        Self(value.into())
+    }
+}
+
+impl From<Velocity> for Vec3 {
+    fn from(value: Velocity) -> Self {
+        value.0
+    }
+}
+
+impl From<Vec3> for AngularVelocity {
+    fn from(value: Vec3) -> Self {
+        Self(value)
+    }
+}
+
+impl From<[f32; 3]> for AngularVelocity {
+    fn from(value: [f32; 3]) -> Self {
+        Self(value.into())
+    }
+}
+
+impl From<(f32, f32, f32)> for AngularVelocity {
+    fn from(value: (f32, f32, f32)) -> Self {
+        Self(value.into())
+    }
+}
+
+impl From<AngularVelocity> for Vec3 {
+    fn from(value: AngularVelocity) -> Self {
+        value.0
+    }
+}
+
+#[cfg(test)]
+mod angular_velocity {
+    use bevy::math::Vec3;
+    use float_cmp::assert_approx_eq;
+
+    use super::AngularVelocity;
+
+    #[test]
+    fn from_axis_rate() {
+        let spin = AngularVelocity::from_axis_rate(Vec3::new(3.0, 0.0, 4.0), 10.0);
+
+        assert_approx_eq!(&[f32], &spin.0.to_array(), &[6.0, 0.0, 8.0]);
+        assert_eq!(
+            AngularVelocity::from_axis_rate(Vec3::ZERO, 10.0).0,
+            Vec3::ZERO
+        );
+    }
+
+    #[test]
+    fn clamp_speed() {
+        let mut spin = AngularVelocity(Vec3::new(0.0, 30.0, 40.0));
+        spin.clamp_speed(5.0);
+        assert_approx_eq!(&[f32], &spin.0.to_array(), &[0.0, 3.0, 4.0]);
+
+        let mut slow = AngularVelocity(Vec3::X);
+        slow.clamp_speed(5.0);
+        assert_eq!(slow.0, Vec3::X);
+    }
+}
+
 #[cfg(test)]
 mod linear_velocity {
     use std::f32::consts::PI;
@@ -139,3 +338,141 @@ mod linear_velocity {
         assert_approx_eq!(f32, nz.yaw(), PI / 2.0);
     }
 }
+
+#[cfg(test)]
+mod up_axis {
+    use std::f32::consts::FRAC_PI_4;
+
+    use bevy::math::Vec3;
+    use float_cmp::assert_approx_eq;
+
+    use super::Velocity;
+    use crate::resources::UpAxis;
+
+    #[test]
+    fn z_up() {
+        let vel = Velocity(Vec3::new(1.0, 1.0, 2f32.sqrt()));
+
+        assert_approx_eq!(f32, vel.pitch_in(UpAxis::Z), FRAC_PI_4);
+        assert_approx_eq!(f32, vel.yaw_in(UpAxis::Z), FRAC_PI_4);
+
+        // Pointing along the velocity, with the top leaning towards z
+        let dir = vel.to_direction_in(UpAxis::Z);
+        assert_approx_eq!(
+            &[f32],
+            &(dir * Vec3::X).to_array(),
+            &vel.0.normalize().to_array(),
+            epsilon = 1e-6
+        );
+        assert!((dir * Vec3::Z).z > 0.0);
+    }
+
+    #[test]
+    fn matches_y_up() {
+        let vel = Velocity(Vec3::new(3.0, -1.0, 2.0));
+
+        assert_eq!(vel.pitch_in(UpAxis::Y), vel.pitch());
+        assert_eq!(vel.yaw_in(UpAxis::Y), vel.yaw());
+        assert_eq!(vel.to_direction_in(UpAxis::Y), vel.to_direction());
+    }
+}
+
+#[cfg(test)]
+mod reflection {
+    use bevy::math::Vec3;
+    use float_cmp::assert_approx_eq;
+
+    use super::Velocity;
+
+    #[test]
+    fn head_on() {
+        let mut vel = Velocity(Vec3::X * -10.0);
+        vel.reflect(Vec3::X, 0.8);
+
+        assert_approx_eq!(&[f32], &vel.0.to_array(), &[8.0, 0.0, 0.0]);
+    }
+
+    #[test]
+    fn glancing() {
+        let normal = Vec3::new(0.0, 1.0, 1.0).normalize();
+        let mut vel = Velocity(Vec3::new(5.0, -1.0, 0.0));
+        vel.reflect(normal, 1.0);
+
+        // The part along the surface is untouched, the part into it flips
+        assert_approx_eq!(f32, vel.0.x, 5.0);
+        assert_approx_eq!(f32, vel.0.dot(normal), 1.0 / 2f32.sqrt());
+        assert_approx_eq!(f32, vel.0.length(), 26f32.sqrt(), epsilon = 1e-5);
+    }
+
+    #[test]
+    fn inelastic() {
+        let mut vel = Velocity(Vec3::new(3.0, -2.0, 1.0));
+        vel.reflect(Vec3::Y, 0.0);
+
+        assert_eq!(vel.0, Vec3::new(3.0, 0.0, 1.0));
+    }
+}
+
+#[cfg(test)]
+mod projection {
+    use bevy::math::Vec3;
+    use float_cmp::assert_approx_eq;
+
+    use super::Velocity;
+
+    #[test]
+    fn onto_axis() {
+        let vel = Velocity(Vec3::new(2.0, -3.0, 5.0));
+
+        assert_eq!(vel.component_along(Vec3::NEG_Z), -5.0);
+        assert_eq!(vel.project_onto(Vec3::X * 4.0), Vec3::X * 2.0);
+    }
+
+    #[test]
+    fn onto_direction() {
+        let dir = Vec3::new(1.0, 1.0, 0.0);
+        let vel = Velocity(Vec3::new(3.0, 1.0, 7.0));
+
+        assert_approx_eq!(f32, vel.component_along(dir), 4.0 / 2f32.sqrt());
+        assert_approx_eq!(&[f32], &vel.project_onto(dir).to_array(), &[2.0, 2.0, 0.0]);
+
+        // What is left is perpendicular
+        let rest = vel.0 - vel.project_onto(dir);
+        assert_approx_eq!(f32, rest.dot(dir), 0.0, epsilon = 1e-5);
+    }
+
+    #[test]
+    fn zero_direction() {
+        let vel = Velocity(Vec3::ONE);
+
+        assert_eq!(vel.component_along(Vec3::ZERO), 0.0);
+        assert_eq!(vel.project_onto(Vec3::ZERO), Vec3::ZERO);
+    }
+}
+
+#[cfg(test)]
+mod conversions {
+    use bevy::math::Vec3;
+
+    use super::{AngularVelocity, Velocity};
+
+    #[test]
+    fn velocity() {
+        let v = Vec3::new(1.0, -2.0, 3.5);
+
+        assert_eq!(Vec3::from(Velocity::from(v)), v);
+        assert_eq!(Vec3::from(Velocity::from([1.0, -2.0, 3.5])), v);
+        assert_eq!(Vec3::from(Velocity::from((1.0, -2.0, 3.5))), v);
+    }
+
+    #[test]
+    fn angular_velocity() {
+        let w = Vec3::new(0.0, 4.0, -0.25);
+
+        assert_eq!(Vec3::from(AngularVelocity::from(w)), w);
+        assert_eq!(Vec3::from(AngularVelocity::from([0.0, 4.0, -0.25])), w);
+
+        let into: Vec3 = AngularVelocity::from((0.0, 4.0, -0.25)).into();
+        assert_eq!(into, w);
+    }
+}
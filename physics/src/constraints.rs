@@ -0,0 +1,799 @@
+use bevy::{
+    ecs::{
+        entity::Entity,
+        query::{With, Without},
+        system::{Query, Res},
+    },
+    hierarchy::Parent,
+    math::{Mat3, Quat, Vec3},
+    transform::components::Transform,
+};
+
+use crate::{
+    components::{
+        force::ForceAccumulator,
+        inertia::Inertia,
+        joint::{cross_matrix, BallJoint},
+        velocity::{
+            AlignToVelocity, AngularVelocity, MaxAngularSpeed, OrientWithParentVelocity, Velocity,
+        },
+        AxisLock, PlanarConstraint, Simulated,
+    },
+    interpolation::StepTransforms,
+    resources::UpAxis,
+};
+
+/// Clamps the [AngularVelocity] of every object with a [MaxAngularSpeed]
+pub fn max_angular_speed(
+    mut query: Query<(&mut AngularVelocity, &MaxAngularSpeed), With<Simulated>>,
+) {
+    for (mut angvel, max) in query.iter_mut() {
+        angvel.clamp_speed(max.0);
+    }
+}
+
+/// Rotates every object with [AlignToVelocity] to face the direction it is moving, with the top
+/// facing the [UpAxis]
+#[allow(clippy::type_complexity)]
+pub fn align_to_velocity(
+    up: Option<Res<UpAxis>>,
+    mut query: Query<(&mut Transform, &Velocity), (With<AlignToVelocity>, With<Simulated>)>,
+) {
+    let up = up.map_or(UpAxis::Y, |up| *up);
+
+    for (mut trans, vel) in query.iter_mut() {
+        // Keep the last orientation instead of snapping to the default one
+        if vel.0 == Vec3::ZERO {
+            continue;
+        }
+
+        trans.rotation = vel.to_direction_in(up);
+    }
+}
+
+/// Turns every child with [OrientWithParentVelocity] to face the direction its parent is moving,
+/// with the top facing the [UpAxis]
+#[allow(clippy::type_complexity)]
+pub fn orient_with_parent_velocity(
+    up: Option<Res<UpAxis>>,
+    parents: Query<(&Transform, &Velocity), Without<OrientWithParentVelocity>>,
+    mut children: Query<(&Parent, &mut Transform), With<OrientWithParentVelocity>>,
+) {
+    let up = up.map_or(UpAxis::Y, |up| *up);
+
+    for (parent, mut trans) in children.iter_mut() {
+        let Ok((parent_trans, vel)) = parents.get(parent.get()) else {
+            continue;
+        };
+
+        // Keep the last orientation instead of snapping to the default one
+        if vel.0 == Vec3::ZERO {
+            continue;
+        }
+
+        trans.rotation = parent_trans.rotation.inverse() * vel.to_direction_in(up);
+    }
+}
+
+/// Moves every object with a [PlanarConstraint] back into its plane
+pub fn planar(
+    mut query: Query<
+        (
+            &mut Transform,
+            &mut Velocity,
+            &mut AngularVelocity,
+            &PlanarConstraint,
+        ),
+        With<Simulated>,
+    >,
+) {
+    for (mut trans, mut vel, mut angvel, plane) in query.iter_mut() {
+        let normal = plane.normal.normalize_or_zero();
+
+        let (pos_out, vel_out) = (trans.translation.dot(normal), vel.0.dot(normal));
+
+        trans.translation -= pos_out * normal;
+        vel.0 -= vel_out * normal;
+        angvel.0 = angvel.0.dot(normal) * normal;
+    }
+}
+
+/// Drops the locked parts of the motion and forces of every object with an [AxisLock]
+///
+/// Runs right before integrating, so the step can't move the object along a locked axis.
+pub fn axis_lock(
+    mut query: Query<
+        (
+            &mut Velocity,
+            &mut AngularVelocity,
+            &mut ForceAccumulator,
+            &AxisLock,
+        ),
+        With<Simulated>,
+    >,
+) {
+    for (mut vel, mut angvel, mut forces, lock) in query.iter_mut() {
+        let (free_trans, free_rot) = (!lock.lock_translation, !lock.lock_rotation);
+
+        vel.0 = Vec3::select(free_trans, vel.0, Vec3::ZERO);
+        forces.force = Vec3::select(free_trans, forces.force, Vec3::ZERO);
+
+        angvel.0 = Vec3::select(free_rot, angvel.0, Vec3::ZERO);
+        forces.torque = Vec3::select(free_rot, forces.torque, Vec3::ZERO);
+    }
+}
+
+/// Undoes the locked parts of the step for every object with an [AxisLock]
+///
+/// Runs after the collisions. Constant accelerations, joints and collisions move objects without
+/// going through the forces [axis_lock] drops, so the position along the locked axes is put back
+/// where the step started and the turn around them is taken out, along with any velocity added.
+#[allow(clippy::type_complexity)]
+pub fn pin_locked_axes(
+    mut query: Query<
+        (
+            &mut Transform,
+            &mut Velocity,
+            &mut AngularVelocity,
+            &AxisLock,
+            &StepTransforms,
+        ),
+        With<Simulated>,
+    >,
+) {
+    for (mut trans, mut vel, mut angvel, lock, steps) in query.iter_mut() {
+        let (free_trans, free_rot) = (!lock.lock_translation, !lock.lock_rotation);
+        let start = steps.current;
+
+        trans.translation = Vec3::select(free_trans, trans.translation, start.translation);
+        vel.0 = Vec3::select(free_trans, vel.0, Vec3::ZERO);
+
+        if lock.lock_rotation.any() {
+            let turn = (trans.rotation * start.rotation.inverse()).to_scaled_axis();
+            let free_turn = Quat::from_scaled_axis(Vec3::select(free_rot, turn, Vec3::ZERO));
+
+            trans.rotation = (free_turn * start.rotation).normalize();
+            angvel.0 = Vec3::select(free_rot, angvel.0, Vec3::ZERO);
+        }
+    }
+}
+
+/// Times the anchors of a [BallJoint] are pulled together every step
+const JOINT_ITERATIONS: usize = 4;
+
+/// One side of a [BallJoint], as seen by the solver
+struct JointSide {
+    inverse_mass: f32,
+
+    /// Inverse of the inertia tensor in global cordinates
+    inverse_tensor: Mat3,
+
+    /// Offset from the center of mass to the anchor, in global cordinates
+    arm: Vec3,
+}
+
+impl JointSide {
+    fn new(trans: &Transform, inertia: &Inertia, anchor: Vec3) -> Self {
+        Self {
+            inverse_mass: inertia.inverse_mass,
+            inverse_tensor: inertia.world_inverse_tensor(trans.rotation),
+            arm: trans.rotation * anchor,
+        }
+    }
+
+    /// Gets how much the velocity of the anchor changes per unit of impulse applied at it
+    fn mobility(&self) -> Mat3 {
+        let cross = cross_matrix(self.arm);
+
+        Mat3::IDENTITY * self.inverse_mass - cross * self.inverse_tensor * cross
+    }
+
+    /// Gets the change in velocity and angular velocity when `impulse` is applied at the anchor
+    fn response(&self, impulse: Vec3) -> (Vec3, Vec3) {
+        (
+            impulse * self.inverse_mass,
+            self.inverse_tensor * self.arm.cross(impulse),
+        )
+    }
+}
+
+/// Keeps the anchors of every [BallJoint] together
+///
+/// Runs after integrating. First both objects are moved and turned so the anchors meet, then an
+/// impulse at the anchors cancels their relative velocity. Both are split by the inverse mass and
+/// inertia of each side, like a collision, so a static object holds the other one in place.
+#[allow(clippy::type_complexity)]
+pub fn ball_joints(
+    joints: Query<(Entity, &BallJoint)>,
+    mut bodies: Query<
+        (
+            &mut Transform,
+            &mut Velocity,
+            &mut AngularVelocity,
+            &Inertia,
+        ),
+        With<Simulated>,
+    >,
+) {
+    for (entity, joint) in joints.iter() {
+        let Ok([a, b]) = bodies.get_many_mut([entity, joint.other]) else {
+            continue;
+        };
+
+        let (mut a_trans, mut a_vel, mut a_angvel, a_inertia) = a;
+        let (mut b_trans, mut b_vel, mut b_angvel, b_inertia) = b;
+
+        // Pull the anchors together, turning by the angle an impulse would have turned them.
+        // Turning moves the arms, so repeat a few times to get close when turning far.
+        for _ in 0..JOINT_ITERATIONS {
+            let side_a = JointSide::new(&a_trans, a_inertia, joint.anchor_a);
+            let side_b = JointSide::new(&b_trans, b_inertia, joint.anchor_b);
+
+            // Two static objects can't be pulled together
+            let Some(inverse) = invert(side_a.mobility() + side_b.mobility()) else {
+                break;
+            };
+
+            let gap = (b_trans.translation + side_b.arm) - (a_trans.translation + side_a.arm);
+            let shift = inverse * gap;
+
+            let (a_move, a_turn) = side_a.response(shift);
+            let (b_move, b_turn) = side_b.response(-shift);
+
+            a_trans.translation += a_move;
+            a_trans.rotation = (Quat::from_scaled_axis(a_turn) * a_trans.rotation).normalize();
+            b_trans.translation += b_move;
+            b_trans.rotation = (Quat::from_scaled_axis(b_turn) * b_trans.rotation).normalize();
+        }
+
+        // Then stop them from drifting apart again, with the arms after the correction
+        let side_a = JointSide::new(&a_trans, a_inertia, joint.anchor_a);
+        let side_b = JointSide::new(&b_trans, b_inertia, joint.anchor_b);
+
+        let Some(inverse) = invert(side_a.mobility() + side_b.mobility()) else {
+            continue;
+        };
+
+        let relative =
+            (b_vel.0 + b_angvel.0.cross(side_b.arm)) - (a_vel.0 + a_angvel.0.cross(side_a.arm));
+        let impulse = inverse * relative;
+
+        let (a_dv, a_dw) = side_a.response(impulse);
+        let (b_dv, b_dw) = side_b.response(-impulse);
+
+        a_vel.0 += a_dv;
+        a_angvel.0 += a_dw;
+        b_vel.0 += b_dv;
+        b_angvel.0 += b_dw;
+    }
+}
+
+/// Inverts a matrix, [None] if it can't be
+fn invert(matrix: Mat3) -> Option<Mat3> {
+    let det = matrix.determinant();
+
+    det.is_normal().then(|| matrix.inverse())
+}
+
+#[cfg(test)]
+mod angular {
+    use bevy::{ecs::system::RunSystemOnce, ecs::world::World, math::Vec3};
+    use float_cmp::assert_approx_eq;
+
+    use super::max_angular_speed;
+    use crate::components::{
+        velocity::{AngularVelocity, MaxAngularSpeed},
+        Simulated,
+    };
+
+    #[test]
+    fn saturates() {
+        let mut world = World::new();
+        let body = world
+            .spawn((
+                Simulated,
+                AngularVelocity(Vec3::Z * 100.0),
+                MaxAngularSpeed(2.0),
+            ))
+            .id();
+
+        world.run_system_once(max_angular_speed);
+
+        let spin = world.get::<AngularVelocity>(body).unwrap();
+        assert_approx_eq!(&[f32], &spin.0.to_array(), &[0.0, 0.0, 2.0]);
+    }
+}
+
+#[cfg(test)]
+mod align {
+    use std::f32::consts::PI;
+
+    use bevy::{
+        ecs::{system::RunSystemOnce, world::World},
+        math::{Quat, Vec3},
+        transform::components::Transform,
+    };
+    use float_cmp::assert_approx_eq;
+
+    use super::align_to_velocity;
+    use crate::components::{
+        velocity::{AlignToVelocity, Velocity},
+        Simulated,
+    };
+
+    fn aligned(vel: Vec3, start: Quat) -> Quat {
+        let mut world = World::new();
+        let body = world
+            .spawn((
+                Simulated,
+                AlignToVelocity,
+                Velocity(vel),
+                Transform::from_rotation(start),
+            ))
+            .id();
+
+        world.run_system_once(align_to_velocity);
+
+        world.get::<Transform>(body).unwrap().rotation
+    }
+
+    #[test]
+    fn along_x() {
+        let rot = aligned(Vec3::X * 3.0, Quat::from_rotation_z(1.0));
+
+        assert_approx_eq!(&[f32], &rot.to_array(), &Quat::IDENTITY.to_array());
+    }
+
+    #[test]
+    fn along_z() {
+        let rot = aligned(Vec3::Z * 3.0, Quat::IDENTITY);
+
+        assert_approx_eq!(
+            &[f32],
+            &rot.to_array(),
+            &Quat::from_rotation_y(-PI / 2.0).to_array()
+        );
+    }
+
+    #[test]
+    fn standing_still() {
+        let start = Quat::from_rotation_z(1.0);
+
+        assert_eq!(aligned(Vec3::ZERO, start), start);
+    }
+}
+
+#[cfg(test)]
+mod plane {
+    use std::time::Duration;
+
+    use bevy::{
+        app::{App, Update},
+        ecs::schedule::IntoSystemConfigs,
+        math::Vec3,
+        time::Time,
+        transform::components::Transform,
+    };
+
+    use super::planar;
+    use crate::{
+        components::{
+            acceleration::Accelerator,
+            inertia::Inertia,
+            velocity::{AngularVelocity, Velocity},
+            PlanarConstraint, SimulationBundle,
+        },
+        resources::PhysicsSettings,
+        update_simulated,
+    };
+
+    #[test]
+    fn stays_in_xy() {
+        let mut app = App::new();
+        let mut time = Time::<()>::default();
+        time.advance_by(Duration::from_millis(10));
+
+        app.insert_resource(time);
+        app.init_resource::<PhysicsSettings>();
+        app.add_systems(Update, (update_simulated, planar).chain());
+
+        let body = app
+            .world_mut()
+            .spawn((
+                SimulationBundle::new(
+                    Velocity(Vec3::new(3.0, 2.0, 1e-3)),
+                    Accelerator(Vec3::new(0.0, -9.82, 1e-4)),
+                    AngularVelocity(Vec3::new(0.1, 0.0, 1.0)),
+                    Inertia::sphere(0.1, 1.0),
+                ),
+                PlanarConstraint { normal: Vec3::Z },
+            ))
+            .id();
+
+        for _ in 0..1000 {
+            app.update();
+
+            let world = app.world();
+            assert_eq!(world.get::<Transform>(body).unwrap().translation.z, 0.0);
+            assert_eq!(world.get::<Velocity>(body).unwrap().0.z, 0.0);
+        }
+
+        let world = app.world();
+        let pos = world.get::<Transform>(body).unwrap().translation;
+        let angvel = world.get::<AngularVelocity>(body).unwrap().0;
+
+        // Still moving within the plane
+        assert!(pos.x > 29.0, "{pos}");
+        assert_eq!(angvel, Vec3::Z);
+    }
+}
+
+#[cfg(test)]
+mod locks {
+    use std::time::Duration;
+
+    use bevy::{
+        app::{App, Update},
+        ecs::schedule::IntoSystemConfigs,
+        math::{BVec3, Vec3},
+        time::Time,
+        transform::components::Transform,
+    };
+    use float_cmp::assert_approx_eq;
+
+    use super::axis_lock;
+    use crate::{
+        components::{
+            acceleration::Accelerator,
+            inertia::Inertia,
+            velocity::{AngularVelocity, Velocity},
+            AxisLock, SimulationBundle,
+        },
+        forces::gravity,
+        resources::{ForceToggles, PhysicsSettings, UpAxis},
+        update_simulated,
+    };
+
+    #[test]
+    fn only_vertical() {
+        let mut app = App::new();
+        let mut time = Time::<()>::default();
+        time.advance_by(Duration::from_millis(10));
+
+        app.insert_resource(time);
+        app.init_resource::<ForceToggles>();
+        // Gravity pulling sideways too, which the lock has to hold back
+        app.insert_resource(PhysicsSettings {
+            gravity: Some(Vec3::new(3.0, -9.82, -1.0)),
+            ..Default::default()
+        });
+        app.init_resource::<UpAxis>();
+        app.add_systems(Update, (gravity, axis_lock, update_simulated).chain());
+
+        let body = app
+            .world_mut()
+            .spawn((
+                SimulationBundle::new(
+                    Velocity(Vec3::new(2.0, 5.0, 1.0)),
+                    Accelerator::ZERO,
+                    AngularVelocity(Vec3::new(1.0, 2.0, 3.0)),
+                    Inertia::sphere(0.5, 1.0),
+                ),
+                AxisLock {
+                    lock_translation: BVec3::new(true, false, true),
+                    lock_rotation: BVec3::new(true, false, true),
+                },
+            ))
+            .id();
+
+        for _ in 0..100 {
+            app.update();
+
+            let pos = app.world().get::<Transform>(body).unwrap().translation;
+            assert_eq!((pos.x, pos.z), (0.0, 0.0));
+        }
+
+        let world = app.world();
+        let trans = world.get::<Transform>(body).unwrap();
+        let angvel = world.get::<AngularVelocity>(body).unwrap().0;
+
+        // Thrown up at 5 m/s for a second
+        assert_approx_eq!(f32, trans.translation.y, 5.0 - 9.82 / 2.0, epsilon = 1e-3);
+
+        // Only spinning around y
+        assert_eq!(angvel, Vec3::Y * 2.0);
+        assert_approx_eq!(f32, (trans.rotation * Vec3::Y).y, 1.0, epsilon = 1e-5);
+    }
+}
+
+#[cfg(test)]
+mod pinned {
+    use std::time::Duration;
+
+    use bevy::{
+        app::App,
+        asset::{AssetApp, AssetPlugin},
+        gizmos::GizmoPlugin,
+        math::{BVec3, Quat, Vec3},
+        render::render_resource::Shader,
+        time::TimeUpdateStrategy,
+        transform::components::Transform,
+        MinimalPlugins,
+    };
+
+    use crate::{
+        components::{
+            acceleration::Accelerator,
+            inertia::Inertia,
+            joint::BallJoint,
+            shape::Shape,
+            velocity::{AngularVelocity, Velocity},
+            AxisLock, SimulationBundle,
+        },
+        resources::PhysicsSettings,
+        SimulatiorPlugin,
+    };
+
+    fn body(pos: Vec3, vel: Vec3, acc: Vec3) -> (SimulationBundle, Shape) {
+        let mut body = SimulationBundle::new(
+            Velocity(vel),
+            Accelerator(acc),
+            AngularVelocity::ZERO,
+            Inertia::sphere(0.5, 1.0),
+        );
+        body.spatial.transform = Transform::from_translation(pos);
+
+        (body, Shape::Sphere { radius: 0.5 })
+    }
+
+    #[test]
+    fn held_against_everything() {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, AssetPlugin::default()));
+        app.init_asset::<Shader>();
+        app.add_plugins((GizmoPlugin, SimulatiorPlugin::default()));
+        app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(
+            20,
+        )));
+        app.insert_resource(PhysicsSettings {
+            gravity: Some(Vec3::ZERO),
+            timestep: 0.02,
+            ..Default::default()
+        });
+
+        // Pushed sideways by its own acceleration, hit by a ball and pulled on by a joint
+        let joined = app
+            .world_mut()
+            .spawn(body(Vec3::new(0.0, 1.5, 0.0), Vec3::Z * 3.0, Vec3::ZERO))
+            .id();
+        let locked = app
+            .world_mut()
+            .spawn((
+                body(Vec3::ZERO, Vec3::ZERO, Vec3::new(2.0, 0.0, -1.0)),
+                AxisLock {
+                    lock_translation: BVec3::new(true, false, true),
+                    lock_rotation: BVec3::TRUE,
+                },
+                BallJoint {
+                    other: joined,
+                    anchor_a: Vec3::Y * 0.75,
+                    anchor_b: Vec3::NEG_Y * 0.75,
+                },
+            ))
+            .id();
+        app.world_mut()
+            .spawn(body(Vec3::new(-2.0, 0.2, 0.3), Vec3::X * 4.0, Vec3::ZERO));
+
+        for _ in 0..50 {
+            app.update();
+
+            let trans = app.world().get::<Transform>(locked).unwrap();
+            assert_eq!((trans.translation.x, trans.translation.z), (0.0, 0.0));
+            assert_eq!(trans.rotation, Quat::IDENTITY);
+        }
+    }
+}
+
+#[cfg(test)]
+mod ball_joint {
+    use std::time::Duration;
+
+    use bevy::{
+        app::{App, Update},
+        ecs::{entity::Entity, schedule::IntoSystemConfigs},
+        math::Vec3,
+        time::Time,
+        transform::components::Transform,
+    };
+
+    use super::ball_joints;
+    use crate::{
+        components::{
+            acceleration::Accelerator,
+            inertia::Inertia,
+            joint::BallJoint,
+            velocity::{AngularVelocity, Velocity},
+            SimulationBundle,
+        },
+        resources::PhysicsSettings,
+        update_simulated,
+    };
+
+    fn app() -> App {
+        let mut app = App::new();
+        let mut time = Time::<()>::default();
+        time.advance_by(Duration::from_millis(10));
+
+        app.insert_resource(time);
+        app.init_resource::<PhysicsSettings>();
+        app.add_systems(Update, (update_simulated, ball_joints).chain());
+
+        app
+    }
+
+    fn body(app: &mut App, pos: Vec3, vel: Vec3, angvel: Vec3, acc: Vec3) -> Entity {
+        let mut body = SimulationBundle::new(
+            Velocity(vel),
+            Accelerator(acc),
+            AngularVelocity(angvel),
+            Inertia::cuboid(2.0, 1.0, 1.0, 1.0),
+        );
+        body.spatial.transform = Transform::from_translation(pos);
+
+        app.world_mut().spawn(body).id()
+    }
+
+    /// Gets where the anchor of each side of the joint on `a` is, in global cordinates
+    fn anchors(app: &App, a: Entity) -> (Vec3, Vec3) {
+        let world = app.world();
+        let joint = world.get::<BallJoint>(a).unwrap();
+        let a_trans = world.get::<Transform>(a).unwrap();
+        let b_trans = world.get::<Transform>(joint.other).unwrap();
+
+        (
+            a_trans.transform_point(joint.anchor_a),
+            b_trans.transform_point(joint.anchor_b),
+        )
+    }
+
+    #[test]
+    fn stays_together() {
+        let mut app = app();
+        let a = body(&mut app, Vec3::ZERO, Vec3::Y, Vec3::Z * 2.0, Vec3::ZERO);
+        let b = body(
+            &mut app,
+            Vec3::X * 2.0,
+            Vec3::ZERO,
+            Vec3::X,
+            Vec3::NEG_Y * 9.82,
+        );
+
+        app.world_mut().entity_mut(a).insert(BallJoint {
+            other: b,
+            anchor_a: Vec3::X,
+            anchor_b: Vec3::NEG_X,
+        });
+
+        for _ in 0..100 {
+            app.update();
+
+            let (anchor_a, anchor_b) = anchors(&app, a);
+            assert!(
+                anchor_a.distance(anchor_b) < 1e-3,
+                "anchors {anchor_a} and {anchor_b} came apart"
+            );
+        }
+
+        // Still moving, the joint doesn't just stop everything
+        let world = app.world();
+        assert!(world.get::<Transform>(a).unwrap().translation.length() > 0.1);
+    }
+
+    #[test]
+    fn hangs_from_static() {
+        let mut app = app();
+        let ceiling = app
+            .world_mut()
+            .spawn(SimulationBundle::new(
+                Velocity::ZERO,
+                Accelerator::ZERO,
+                AngularVelocity::ZERO,
+                Inertia::static_body(),
+            ))
+            .id();
+        let bob = body(
+            &mut app,
+            Vec3::new(1.0, -1.0, 0.0),
+            Vec3::ZERO,
+            Vec3::ZERO,
+            Vec3::NEG_Y * 9.82,
+        );
+
+        app.world_mut().entity_mut(bob).insert(BallJoint {
+            other: ceiling,
+            anchor_a: Vec3::new(-1.0, 1.0, 0.0),
+            anchor_b: Vec3::ZERO,
+        });
+
+        for _ in 0..100 {
+            app.update();
+
+            let (anchor_a, anchor_b) = anchors(&app, bob);
+            assert!(anchor_a.distance(anchor_b) < 1e-3);
+            assert_eq!(anchor_b, Vec3::ZERO);
+        }
+    }
+}
+
+#[cfg(test)]
+mod child_orientation {
+    use bevy::{
+        ecs::{system::RunSystemOnce, world::World},
+        hierarchy::BuildWorldChildren,
+        math::{Quat, Vec3},
+        transform::components::Transform,
+    };
+    use float_cmp::assert_approx_eq;
+
+    use super::orient_with_parent_velocity;
+    use crate::components::velocity::{OrientWithParentVelocity, Velocity};
+
+    /// Gets the rotation of the child, composed with the parent, and the local one
+    fn oriented(parent_rot: Quat, vel: Vec3, start: Quat) -> (Quat, Quat) {
+        let mut world = World::new();
+        let mut child = None;
+
+        world
+            .spawn((Transform::from_rotation(parent_rot), Velocity(vel)))
+            .with_children(|parent| {
+                child = Some(
+                    parent
+                        .spawn((
+                            Transform::from_rotation(start).with_translation(Vec3::Y),
+                            OrientWithParentVelocity,
+                        ))
+                        .id(),
+                );
+            });
+
+        world.run_system_once(orient_with_parent_velocity);
+
+        let local = world.get::<Transform>(child.unwrap()).unwrap().rotation;
+        (parent_rot * local, local)
+    }
+
+    #[test]
+    fn composed() {
+        let parent_rot = Quat::from_rotation_z(0.7) * Quat::from_rotation_x(-1.2);
+        let vel = Vec3::new(1.0, 2.0, -3.0);
+
+        let (global, _) = oriented(parent_rot, vel, Quat::IDENTITY);
+
+        assert_approx_eq!(
+            &[f32],
+            &global.to_array(),
+            &Velocity(vel).to_direction().to_array(),
+            epsilon = 1e-5
+        );
+    }
+
+    #[test]
+    fn unrotated_parent() {
+        let (global, local) = oriented(Quat::IDENTITY, Vec3::Z, Quat::IDENTITY);
+
+        assert_eq!(global, local);
+        assert_approx_eq!(
+            &[f32],
+            &local.to_array(),
+            &Velocity(Vec3::Z).to_direction().to_array()
+        );
+    }
+
+    #[test]
+    fn standing_still() {
+        let start = Quat::from_rotation_y(0.3);
+        let (_, local) = oriented(Quat::from_rotation_z(1.0), Vec3::ZERO, start);
+
+        assert_eq!(local, start);
+    }
+}
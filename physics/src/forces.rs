@@ -0,0 +1,812 @@
+use bevy::{
+    ecs::{
+        query::{QueryFilter, With, Without},
+        system::{Query, Res},
+    },
+    math::Vec3,
+    transform::components::Transform,
+};
+
+use crate::components::{
+    aero::{AeroSurface, MagnusCoefficient},
+    drag::{DragTable, QuadraticDrag},
+    force::{Force, ForceAccumulator, MomentList, MotorTorque},
+    inertia::Inertia,
+    shape::Shape,
+    spring::Spring,
+    velocity::{AngularVelocity, Velocity},
+    BodyGroup, GravityOverride, Orbiting, Simulated,
+};
+use crate::resources::{Atmosphere, CentralBody, ForceToggles, PhysicsSettings, UpAxis, Wind};
+use crate::sleep::Sleeping;
+
+/// Applies the [Gravity](crate::resources::Gravity) of the [PhysicsSettings] to every non-static
+/// object, or its [GravityOverride] if it has one
+///
+/// [Sleeping] objects are skipped, the steady pull would otherwise wake them up again right away.
+#[allow(clippy::type_complexity)]
+pub fn gravity(
+    toggles: Res<ForceToggles>,
+    settings: Res<PhysicsSettings>,
+    up: Res<UpAxis>,
+    mut query: Query<
+        (&Inertia, Option<&GravityOverride>, &mut ForceAccumulator),
+        (With<Simulated>, Without<Orbiting>, Without<Sleeping>),
+    >,
+) {
+    if !toggles.gravity {
+        return;
+    }
+
+    let gravity = settings.gravity_for(*up);
+
+    for (inertia, over, mut forces) in query.iter_mut() {
+        // Infinite mass times gravity would turn into NaN when accelerating
+        if inertia.is_static() {
+            continue;
+        }
+
+        forces.add_force(Force(gravity.for_body(over) * inertia.mass()));
+    }
+}
+
+/// Pulls every [Orbiting] object towards the [CentralBody]
+///
+/// Does nothing unless the [CentralBody] resource exists. [Sleeping] objects are skipped like in
+/// [gravity].
+#[allow(clippy::type_complexity)]
+pub fn central_gravity(
+    toggles: Res<ForceToggles>,
+    central: Option<Res<CentralBody>>,
+    mut query: Query<
+        (&Transform, &Inertia, &mut ForceAccumulator),
+        (With<Simulated>, With<Orbiting>, Without<Sleeping>),
+    >,
+) {
+    if !toggles.central_gravity {
+        return;
+    }
+
+    let Some(central) = central else {
+        return;
+    };
+
+    for (trans, inertia, mut forces) in query.iter_mut() {
+        if inertia.is_static() {
+            continue;
+        }
+
+        forces.add_force(Force(
+            central.acceleration(trans.translation) * inertia.mass(),
+        ));
+    }
+}
+
+/// Applies lift and drag for every [AeroSurface] based on the airflow relative to the object, in
+/// air as dense as the [Atmosphere] at its height along the [UpAxis]
+#[allow(clippy::type_complexity)]
+pub fn aerodynamics(
+    toggles: Res<ForceToggles>,
+    wind: Res<Wind>,
+    atmosphere: Res<Atmosphere>,
+    up: Res<UpAxis>,
+    mut query: Query<
+        (&Transform, &Velocity, &AeroSurface, &mut ForceAccumulator),
+        (With<Simulated>, Without<Orbiting>),
+    >,
+) {
+    if !toggles.aerodynamics {
+        return;
+    }
+
+    for (trans, vel, surface, mut forces) in query.iter_mut() {
+        let airflow = vel.0 - wind.0;
+        let density = atmosphere.density(up.height(trans.translation));
+
+        forces.add_moment(&surface.get_moment(trans.rotation, airflow, density));
+    }
+}
+
+/// Applies [QuadraticDrag] based on the airflow relative to the object, in air as dense as the
+/// [Atmosphere] at its height along the [UpAxis]
+///
+/// The coefficient comes from the [DragTable] of the object at its airspeed, if it has one.
+#[allow(clippy::type_complexity)]
+pub fn drag(
+    toggles: Res<ForceToggles>,
+    wind: Res<Wind>,
+    atmosphere: Res<Atmosphere>,
+    up: Res<UpAxis>,
+    mut query: Query<
+        (
+            &Transform,
+            &Velocity,
+            &QuadraticDrag,
+            Option<&DragTable>,
+            Option<&Shape>,
+            &mut ForceAccumulator,
+        ),
+        (With<Simulated>, Without<Orbiting>),
+    >,
+) {
+    if !toggles.drag {
+        return;
+    }
+
+    for (trans, vel, drag, table, shape, mut forces) in query.iter_mut() {
+        let airflow = vel.0 - wind.0;
+
+        let density = atmosphere.density(up.height(trans.translation));
+        let drag = drag.with_table(table, airflow.length());
+
+        forces.add_force(drag.get_force(trans.rotation, airflow, density, shape));
+    }
+}
+
+/// Applies the Magnus effect for every spinning object with a [MagnusCoefficient]
+#[allow(clippy::type_complexity)]
+pub fn magnus(
+    toggles: Res<ForceToggles>,
+    wind: Res<Wind>,
+    mut query: Query<
+        (
+            &Velocity,
+            &AngularVelocity,
+            &MagnusCoefficient,
+            &mut ForceAccumulator,
+        ),
+        (With<Simulated>, Without<Orbiting>),
+    >,
+) {
+    if !toggles.magnus {
+        return;
+    }
+
+    for (vel, angvel, magnus, mut forces) in query.iter_mut() {
+        let airflow = vel.0 - wind.0;
+
+        forces.add_force(magnus.get_force(angvel.0, airflow));
+    }
+}
+
+/// Applies every [Moment](crate::components::force::Moment) in a [MomentList]
+#[allow(clippy::type_complexity)]
+pub fn moments(
+    toggles: Res<ForceToggles>,
+    mut query: Query<(&MomentList, &mut ForceAccumulator), (With<Simulated>, Without<Orbiting>)>,
+) {
+    if !toggles.moments {
+        return;
+    }
+
+    for (moments, mut forces) in query.iter_mut() {
+        let (torque, force) = moments.get_parts();
+
+        forces.add_torque(torque);
+        forces.add_force(force);
+    }
+}
+
+/// Applies the force of every [Spring]
+#[allow(clippy::type_complexity)]
+pub fn springs(
+    toggles: Res<ForceToggles>,
+    mut query: Query<
+        (&Transform, &Velocity, &Spring, &mut ForceAccumulator),
+        (With<Simulated>, Without<Orbiting>),
+    >,
+) {
+    if !toggles.springs {
+        return;
+    }
+
+    for (trans, vel, spring, mut forces) in query.iter_mut() {
+        forces.add_force(spring.get_force(trans.translation, vel.0));
+    }
+}
+
+/// Applies the torque of every [MotorTorque]
+#[allow(clippy::type_complexity)]
+pub fn motors(
+    toggles: Res<ForceToggles>,
+    mut query: Query<(&MotorTorque, &mut ForceAccumulator), (With<Simulated>, Without<Orbiting>)>,
+) {
+    if !toggles.motors {
+        return;
+    }
+
+    for (motor, mut forces) in query.iter_mut() {
+        forces.add_torque(motor.get_torque());
+    }
+}
+
+/// Adds `force` at the center of mass of every object in `group`
+///
+/// For pushing a whole cluster at once, like a gust of wind or a tractor beam. The force only
+/// lasts for the next step, call it from a system in
+/// [PhysicsSet::Forces](crate::PhysicsSet::Forces) to keep pushing.
+pub fn apply_to_group<F: QueryFilter>(
+    bodies: &mut Query<(&BodyGroup, &mut ForceAccumulator), F>,
+    group: u32,
+    force: Vec3,
+) {
+    for (member, mut forces) in bodies.iter_mut() {
+        if member.0 == group {
+            forces.add_force(Force(force));
+        }
+    }
+}
+
+#[cfg(test)]
+mod orbit {
+    use std::time::Duration;
+
+    use bevy::{
+        app::{App, Update},
+        ecs::schedule::IntoSystemConfigs,
+        math::Vec3,
+        time::Time,
+        transform::components::Transform,
+    };
+
+    use super::{central_gravity, gravity, moments, motors, springs};
+    use crate::{
+        components::{
+            acceleration::Accelerator,
+            force::{ForceAccumulator, Moment, MomentList, MotorTorque},
+            inertia::Inertia,
+            spring::Spring,
+            velocity::{AngularVelocity, Velocity},
+            Orbiting, SimulationBundle,
+        },
+        resources::{CentralBody, ForceToggles, PhysicsSettings, UpAxis},
+        update_simulated,
+    };
+
+    #[test]
+    fn circular() {
+        let mut app = App::new();
+        let mut time = Time::<()>::default();
+        time.advance_by(Duration::from_millis(1));
+
+        let (mu, radius) = (100.0, 10.0);
+
+        app.insert_resource(time);
+        app.init_resource::<PhysicsSettings>();
+        app.init_resource::<UpAxis>();
+        app.init_resource::<ForceToggles>();
+        app.insert_resource(CentralBody {
+            mu,
+            position: Vec3::ZERO,
+        });
+        app.add_systems(
+            Update,
+            ((gravity, central_gravity), update_simulated).chain(),
+        );
+
+        // Speed for a circular orbit is sqrt(mu / r)
+        let mut body = SimulationBundle::new(
+            Velocity(Vec3::Z * (mu / radius).sqrt()),
+            Accelerator::ZERO,
+            AngularVelocity::ZERO,
+            Inertia::sphere(1.0, 1.0),
+        );
+        body.spatial.transform = Transform::from_translation(Vec3::X * radius);
+        let body = app.world_mut().spawn((body, Orbiting)).id();
+
+        // A bit more than one orbit
+        for _ in 0..25_000 {
+            app.update();
+
+            let r = app
+                .world()
+                .get::<Transform>(body)
+                .unwrap()
+                .translation
+                .length();
+            assert!((r - radius).abs() < radius * 0.01, "radius drifted to {r}");
+        }
+    }
+
+    #[test]
+    fn only_central_gravity() {
+        let mut app = App::new();
+        app.init_resource::<ForceToggles>();
+        app.add_systems(Update, (moments, springs, motors));
+
+        let mut body = SimulationBundle::new(
+            Velocity(Vec3::Z),
+            Accelerator::ZERO,
+            AngularVelocity::ZERO,
+            Inertia::sphere(1.0, 1.0),
+        );
+        body.spatial.transform = Transform::from_translation(Vec3::X * 10.0);
+
+        let body = app
+            .world_mut()
+            .spawn((
+                body,
+                Orbiting,
+                MomentList(vec![Moment::new(Vec3::X, Vec3::Y)]),
+                Spring {
+                    anchor: Vec3::ZERO,
+                    stiffness: 5.0,
+                    damping: 1.0,
+                },
+                MotorTorque {
+                    axis: Vec3::Y,
+                    magnitude: 2.0,
+                },
+            ))
+            .id();
+
+        app.update();
+
+        let forces = app.world().get::<ForceAccumulator>(body).unwrap();
+        assert_eq!((forces.force, forces.torque), (Vec3::ZERO, Vec3::ZERO));
+    }
+}
+
+#[cfg(test)]
+mod override_gravity {
+    use std::time::Duration;
+
+    use bevy::{
+        app::{App, Update},
+        ecs::schedule::IntoSystemConfigs,
+        math::Vec3,
+        time::Time,
+    };
+    use float_cmp::assert_approx_eq;
+
+    use super::gravity;
+    use crate::{
+        components::{
+            acceleration::Accelerator,
+            inertia::Inertia,
+            velocity::{AngularVelocity, Velocity},
+            GravityOverride, SimulationBundle,
+        },
+        resources::{ForceToggles, PhysicsSettings, UpAxis},
+        update_simulated,
+    };
+
+    #[test]
+    fn sideways() {
+        let mut app = App::new();
+        let mut time = Time::<()>::default();
+        time.advance_by(Duration::from_secs(1));
+
+        app.insert_resource(time);
+        app.insert_resource(PhysicsSettings {
+            gravity: Some(Vec3::Y * -10.0),
+            max_delta: None,
+            ..Default::default()
+        });
+        app.init_resource::<UpAxis>();
+        app.init_resource::<ForceToggles>();
+        app.add_systems(Update, (gravity, update_simulated).chain());
+
+        let resting = || {
+            SimulationBundle::new(
+                Velocity::ZERO,
+                Accelerator::ZERO,
+                AngularVelocity::ZERO,
+                Inertia::sphere(1.0, 2.0),
+            )
+        };
+
+        let world = app.world_mut();
+        let body = world.spawn(resting()).id();
+        let tilted = world
+            .spawn((resting(), GravityOverride(Vec3::X * 5.0)))
+            .id();
+
+        app.update();
+
+        let world = app.world();
+        let vel = world.get::<Velocity>(body).unwrap().0;
+        let tilted_vel = world.get::<Velocity>(tilted).unwrap().0;
+
+        assert_approx_eq!(&[f32], &vel.to_array(), &[0.0, -10.0, 0.0]);
+        assert_approx_eq!(&[f32], &tilted_vel.to_array(), &[5.0, 0.0, 0.0]);
+    }
+}
+
+#[cfg(test)]
+mod moment_list {
+    use std::time::Duration;
+
+    use bevy::{
+        app::{App, Update},
+        ecs::schedule::IntoSystemConfigs,
+        math::{Mat3, Vec3},
+        time::Time,
+    };
+    use float_cmp::assert_approx_eq;
+
+    use super::moments;
+    use crate::{
+        components::{
+            acceleration::Accelerator,
+            force::{Moment, MomentList},
+            inertia::Inertia,
+            velocity::{AngularVelocity, Velocity},
+            SimulationBundle,
+        },
+        resources::{ForceToggles, PhysicsSettings},
+        update_simulated,
+    };
+
+    #[test]
+    fn two_offset_forces() {
+        let mut app = App::new();
+        let mut time = Time::<()>::default();
+        time.advance_by(Duration::from_secs(1));
+
+        app.insert_resource(time);
+        app.insert_resource(PhysicsSettings {
+            max_delta: None,
+            ..Default::default()
+        });
+        app.init_resource::<ForceToggles>();
+        app.add_systems(Update, (moments, update_simulated).chain());
+
+        let body = app
+            .world_mut()
+            .spawn((
+                SimulationBundle::new(
+                    Velocity::ZERO,
+                    Accelerator::ZERO,
+                    AngularVelocity::ZERO,
+                    Inertia::new(2.0, Mat3::from_diagonal(Vec3::new(1.0, 2.0, 4.0))),
+                ),
+                MomentList(vec![
+                    // Pushes along x while turning around z
+                    Moment::new(Vec3::X, Vec3::new(1.0, 1.0, 0.0)),
+                    // Straight through the center of mass, only pushes
+                    Moment::new(Vec3::Y * 2.0, Vec3::Y * 3.0),
+                ]),
+            ))
+            .id();
+
+        app.update();
+
+        // A net force of (1, 3, 0) on 2 kg and a torque of (0, 0, 1) on 4 kg m^2, for a second
+        let world = app.world();
+        let vel = world.get::<Velocity>(body).unwrap().0;
+        let angvel = world.get::<AngularVelocity>(body).unwrap().0;
+
+        assert_approx_eq!(&[f32], &vel.to_array(), &[0.5, 1.5, 0.0]);
+        assert_approx_eq!(&[f32], &angvel.to_array(), &[0.0, 0.0, 0.25]);
+    }
+}
+
+#[cfg(test)]
+mod groups {
+    use bevy::{
+        ecs::{
+            query::With,
+            system::{Query, RunSystemOnce},
+            world::World,
+        },
+        math::Vec3,
+    };
+
+    use super::apply_to_group;
+    use crate::components::{force::ForceAccumulator, BodyGroup, Simulated};
+
+    #[test]
+    fn only_members() {
+        let mut world = World::new();
+
+        let mut spawn = |group: Option<u32>| {
+            let mut body = world.spawn((Simulated, ForceAccumulator::default()));
+            if let Some(group) = group {
+                body.insert(BodyGroup(group));
+            }
+            body.id()
+        };
+
+        let members = [spawn(Some(7)), spawn(Some(7))];
+        let others = [spawn(Some(2)), spawn(None)];
+
+        world.run_system_once(
+            |mut bodies: Query<(&BodyGroup, &mut ForceAccumulator), With<Simulated>>| {
+                apply_to_group(&mut bodies, 7, Vec3::X * 3.0);
+                apply_to_group(&mut bodies, 7, Vec3::Y);
+            },
+        );
+
+        for member in members {
+            let forces = world.get::<ForceAccumulator>(member).unwrap();
+            assert_eq!(forces.force, Vec3::new(3.0, 1.0, 0.0));
+        }
+        for other in others {
+            let forces = world.get::<ForceAccumulator>(other).unwrap();
+            assert_eq!(forces.force, Vec3::ZERO);
+        }
+    }
+}
+
+#[cfg(test)]
+mod motor {
+    use std::time::Duration;
+
+    use bevy::{
+        app::{App, Update},
+        ecs::{entity::Entity, schedule::IntoSystemConfigs},
+        math::Vec3,
+        time::Time,
+    };
+    use float_cmp::assert_approx_eq;
+
+    use super::motors;
+    use crate::{
+        components::{
+            acceleration::Accelerator,
+            force::MotorTorque,
+            inertia::Inertia,
+            velocity::{AngularVelocity, Velocity},
+            SimulationBundle,
+        },
+        resources::{ForceToggles, PhysicsSettings},
+        update_simulated,
+    };
+
+    fn spin_up(axis: Vec3) -> (App, Entity) {
+        let mut app = App::new();
+        let mut time = Time::<()>::default();
+        time.advance_by(Duration::from_millis(100));
+
+        app.insert_resource(time);
+        app.insert_resource(PhysicsSettings {
+            max_delta: None,
+            ..Default::default()
+        });
+        app.init_resource::<ForceToggles>();
+        app.add_systems(Update, (motors, update_simulated).chain());
+
+        let body = app
+            .world_mut()
+            .spawn((
+                SimulationBundle::new(
+                    Velocity::ZERO,
+                    Accelerator::ZERO,
+                    AngularVelocity::ZERO,
+                    Inertia::sphere(0.5, 4.0),
+                ),
+                MotorTorque {
+                    axis,
+                    magnitude: 3.0,
+                },
+            ))
+            .id();
+
+        (app, body)
+    }
+
+    #[test]
+    fn momentum_grows_linearly() {
+        let axis = Vec3::new(1.0, 2.0, -2.0);
+        let (mut app, body) = spin_up(axis * 5.0);
+
+        for step in 1..=50 {
+            app.update();
+
+            let angvel = app.world().get::<AngularVelocity>(body).unwrap().0;
+            let momentum = Inertia::sphere(0.5, 4.0).tensor * angvel;
+
+            // Three newton meters for a tenth of a second every step, all around the axis
+            let expected = axis.normalize() * 0.3 * step as f32;
+            assert_approx_eq!(
+                &[f32],
+                &momentum.to_array(),
+                &expected.to_array(),
+                epsilon = 1e-4
+            );
+        }
+    }
+
+    #[test]
+    fn zero_axis() {
+        let (mut app, body) = spin_up(Vec3::ZERO);
+
+        app.update();
+
+        let angvel = app.world().get::<AngularVelocity>(body).unwrap().0;
+        assert_eq!(angvel, Vec3::ZERO);
+    }
+}
+
+#[cfg(test)]
+mod thinning_air {
+    use bevy::{
+        ecs::{system::RunSystemOnce, world::World},
+        math::Vec3,
+        transform::components::Transform,
+    };
+    use float_cmp::assert_approx_eq;
+
+    use super::{aerodynamics, drag};
+    use crate::{
+        components::{
+            acceleration::Accelerator,
+            aero::{AeroSurface, AIR_DENSITY},
+            drag::QuadraticDrag,
+            force::ForceAccumulator,
+            inertia::Inertia,
+            velocity::{AngularVelocity, Velocity},
+            SimulationBundle,
+        },
+        resources::{Atmosphere, ForceToggles, UpAxis, Wind},
+    };
+
+    #[test]
+    fn exponential() {
+        let atmosphere = Atmosphere::default();
+        let h = atmosphere.scale_height;
+
+        assert_eq!(atmosphere.density(0.0), AIR_DENSITY);
+        assert_approx_eq!(f32, atmosphere.density(h), AIR_DENSITY / 1f32.exp());
+        assert_approx_eq!(f32, atmosphere.density(3.0 * h), AIR_DENSITY / 3f32.exp());
+
+        // Each scale height divides the density by the same factor
+        let ratio = atmosphere.density(2.0 * h) / atmosphere.density(h);
+        assert_approx_eq!(f32, ratio, atmosphere.density(h) / atmosphere.density(0.0));
+    }
+
+    #[test]
+    fn drag_fades_with_altitude() {
+        let mut world = World::new();
+        world.init_resource::<ForceToggles>();
+        world.init_resource::<Wind>();
+        world.init_resource::<Atmosphere>();
+        world.init_resource::<UpAxis>();
+
+        let bodies = [0.0, 8500.0, 100_000.0].map(|altitude| {
+            let mut body = SimulationBundle::new(
+                Velocity(Vec3::X * 100.0),
+                Accelerator::ZERO,
+                AngularVelocity::ZERO,
+                Inertia::sphere(0.1, 1.0),
+            );
+            body.spatial.transform = Transform::from_xyz(0.0, altitude, 0.0);
+
+            world
+                .spawn((
+                    body,
+                    QuadraticDrag {
+                        cd: 0.5,
+                        area: Some(0.1),
+                    },
+                ))
+                .id()
+        });
+
+        world.run_system_once(drag);
+
+        let [ground, scale, space] =
+            bodies.map(|body| world.get::<ForceAccumulator>(body).unwrap().force.x);
+
+        assert_approx_eq!(f32, ground, -0.5 * AIR_DENSITY * 0.5 * 0.1 * 100.0 * 100.0);
+        assert_approx_eq!(f32, scale, ground / 1f32.exp());
+        assert!(
+            space.abs() < ground.abs() * 1e-4,
+            "{space} N left at 100 km"
+        );
+    }
+
+    #[test]
+    fn surfaces_fade_with_altitude() {
+        let mut world = World::new();
+        world.init_resource::<ForceToggles>();
+        world.init_resource::<Wind>();
+        world.init_resource::<Atmosphere>();
+        world.insert_resource(UpAxis::Z);
+
+        let bodies = [0.0, 8500.0].map(|altitude| {
+            let mut body = SimulationBundle::new(
+                Velocity(Vec3::X * 100.0),
+                Accelerator::ZERO,
+                AngularVelocity::ZERO,
+                Inertia::sphere(0.1, 1.0),
+            );
+            body.spatial.transform = Transform::from_xyz(0.0, 0.0, altitude);
+
+            world
+                .spawn((
+                    body,
+                    AeroSurface {
+                        center: Vec3::ZERO,
+                        area: 0.1,
+                        cl: 1.0,
+                        cd: 0.5,
+                    },
+                ))
+                .id()
+        });
+
+        world.run_system_once(aerodynamics);
+
+        let [ground, scale] =
+            bodies.map(|body| world.get::<ForceAccumulator>(body).unwrap().force.x);
+
+        // Flying straight along the surface, so only the drag is left
+        assert_approx_eq!(f32, ground, -0.5 * AIR_DENSITY * 0.1 * 0.5 * 100.0 * 100.0);
+        assert_approx_eq!(f32, scale, ground / 1f32.exp());
+    }
+}
+
+#[cfg(test)]
+mod toggles {
+    use std::time::Duration;
+
+    use bevy::{
+        app::{App, Update},
+        ecs::schedule::IntoSystemConfigs,
+        math::Vec3,
+        time::Time,
+    };
+
+    use super::drag;
+    use crate::{
+        components::{
+            acceleration::Accelerator,
+            drag::QuadraticDrag,
+            inertia::Inertia,
+            velocity::{AngularVelocity, Velocity},
+            SimulationBundle,
+        },
+        resources::{Atmosphere, ForceToggles, PhysicsSettings, UpAxis, Wind},
+        update_simulated,
+    };
+
+    /// Speed of a body with drag after a second of flying, starting at 10 m/s
+    fn speed_after(toggles: ForceToggles) -> f32 {
+        let mut app = App::new();
+        let mut time = Time::<()>::default();
+        time.advance_by(Duration::from_millis(10));
+
+        app.insert_resource(time);
+        app.init_resource::<PhysicsSettings>();
+        app.insert_resource(toggles);
+        app.init_resource::<Wind>();
+        app.init_resource::<Atmosphere>();
+        app.init_resource::<UpAxis>();
+        app.add_systems(Update, (drag, update_simulated).chain());
+
+        let body = app
+            .world_mut()
+            .spawn((
+                SimulationBundle::new(
+                    Velocity(Vec3::X * 10.0),
+                    Accelerator::ZERO,
+                    AngularVelocity::ZERO,
+                    Inertia::sphere(0.1, 1.0),
+                ),
+                QuadraticDrag {
+                    cd: 0.5,
+                    area: Some(0.1),
+                },
+            ))
+            .id();
+
+        for _ in 0..100 {
+            app.update();
+        }
+
+        app.world().get::<Velocity>(body).unwrap().0.length()
+    }
+
+    #[test]
+    fn drag_disabled() {
+        let toggles = ForceToggles {
+            drag: false,
+            ..Default::default()
+        };
+
+        assert!(speed_after(ForceToggles::default()) < 9.0);
+        assert_eq!(speed_after(toggles), 10.0);
+    }
+}
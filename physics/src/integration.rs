@@ -0,0 +1,260 @@
+use bevy::math::{Mat3, Quat, Vec3};
+
+use crate::{components::inertia::Inertia, resources::IntegrationMethod};
+
+/// Moves a position and velocity through `delta` seconds of constant `acceleration`
+///
+/// Returns the new position and velocity.
+///
+/// ```rust
+/// # use physics::{integration::linear, resources::IntegrationMethod};
+/// # use bevy::math::Vec3;
+/// // Falling from rest for a second
+/// let (pos, vel) = linear(IntegrationMethod::Verlet, Vec3::ZERO, Vec3::ZERO, Vec3::NEG_Y, 1.0);
+///
+/// assert_eq!(pos, Vec3::NEG_Y * 0.5);
+/// assert_eq!(vel, Vec3::NEG_Y);
+/// ```
+#[must_use]
+pub fn linear(
+    method: IntegrationMethod,
+    position: Vec3,
+    velocity: Vec3,
+    acceleration: Vec3,
+    delta: f32,
+) -> (Vec3, Vec3) {
+    match method {
+        IntegrationMethod::SemiImplicitEuler => {
+            let velocity = velocity + acceleration * delta;
+
+            (position + velocity * delta, velocity)
+        }
+        IntegrationMethod::Verlet => {
+            let half = velocity + acceleration * delta / 2.0;
+
+            (position + half * delta, half + acceleration * delta / 2.0)
+        }
+        IntegrationMethod::Rk4 => rk4(position, velocity, delta, |_, _| acceleration),
+    }
+}
+
+/// Turns a rotation and angular velocity through `delta` seconds of constant `torque`
+///
+/// Solves Euler's equations for a rigid body, so a body spinning freely about anything but one
+/// of its principal axes wobbles like it should. Both `angular_velocity` and `torque` are in
+/// global cordinates. Returns the new rotation and angular velocity, static objects are left as
+/// they are.
+///
+/// ```rust
+/// # use physics::{components::inertia::Inertia, integration::angular};
+/// # use physics::resources::IntegrationMethod;
+/// # use bevy::math::{Quat, Vec3};
+/// // Spinning freely about an axis of symmetry never changes
+/// let inertia = Inertia::sphere(1.0, 1.0);
+/// let (rotation, angvel) =
+///     angular(IntegrationMethod::Rk4, Quat::IDENTITY, Vec3::Y, &inertia, Vec3::ZERO, 0.1);
+///
+/// assert!(rotation.abs_diff_eq(Quat::from_rotation_y(0.1), 1e-6));
+/// assert!(angvel.abs_diff_eq(Vec3::Y, 1e-6));
+/// ```
+#[must_use]
+pub fn angular(
+    method: IntegrationMethod,
+    rotation: Quat,
+    angular_velocity: Vec3,
+    inertia: &Inertia,
+    torque: Vec3,
+    delta: f32,
+) -> (Quat, Vec3) {
+    if inertia.is_static() {
+        return (rotation, angular_velocity);
+    }
+
+    let euler = EulerEquations {
+        tensor: inertia.tensor,
+        inverse: inertia.tensor.inverse(),
+        torque,
+    };
+
+    let (rotation, angular_velocity) = match method {
+        IntegrationMethod::SemiImplicitEuler => {
+            let angvel = angular_velocity + euler.acceleration(rotation, angular_velocity) * delta;
+
+            (Quat::from_scaled_axis(angvel * delta) * rotation, angvel)
+        }
+        IntegrationMethod::Verlet => {
+            let half =
+                angular_velocity + euler.acceleration(rotation, angular_velocity) * delta / 2.0;
+            let rotation = Quat::from_scaled_axis(half * delta) * rotation;
+
+            (
+                rotation,
+                half + euler.acceleration(rotation, half) * delta / 2.0,
+            )
+        }
+        IntegrationMethod::Rk4 => euler.rk4(rotation, angular_velocity, delta),
+    };
+
+    (rotation.normalize(), angular_velocity)
+}
+
+/// Euler's equations of a rigid body with a fixed torque in global cordinates
+struct EulerEquations {
+    tensor: Mat3,
+    inverse: Mat3,
+    torque: Vec3,
+}
+
+impl EulerEquations {
+    /// Angular acceleration in global cordinates when turned by `rotation` and spinning at
+    /// `angvel`
+    ///
+    /// Solved in the frame of the body, where the tensor stays the same.
+    fn acceleration(&self, rotation: Quat, angvel: Vec3) -> Vec3 {
+        let (torque, spin) = (
+            rotation.inverse() * self.torque,
+            rotation.inverse() * angvel,
+        );
+
+        rotation * (self.inverse * (torque - spin.cross(self.tensor * spin)))
+    }
+
+    /// How fast `rotation` changes when spinning at `angvel`
+    ///
+    /// Half the product of `angvel` as a pure quaternion and the rotation, written out since the
+    /// product of quaternions is only meant for unit ones.
+    fn turning(rotation: Quat, angvel: Vec3) -> Quat {
+        let xyz = angvel * rotation.w + angvel.cross(rotation.xyz());
+
+        Quat::from_xyzw(xyz.x, xyz.y, xyz.z, -angvel.dot(rotation.xyz())) * 0.5
+    }
+
+    /// One classic Runge-Kutta step of the rotation and the angular velocity together
+    fn rk4(&self, rotation: Quat, angvel: Vec3, delta: f32) -> (Quat, Vec3) {
+        let half = delta / 2.0;
+        let stage = |rotation: Quat, angvel: Vec3| {
+            (
+                Self::turning(rotation, angvel),
+                self.acceleration(rotation, angvel),
+            )
+        };
+
+        let (k1q, k1w) = stage(rotation, angvel);
+        let (k2q, k2w) = stage((rotation + k1q * half).normalize(), angvel + k1w * half);
+        let (k3q, k3w) = stage((rotation + k2q * half).normalize(), angvel + k2w * half);
+        let (k4q, k4w) = stage((rotation + k3q * delta).normalize(), angvel + k3w * delta);
+
+        (
+            rotation + (k1q + k2q * 2.0 + k3q * 2.0 + k4q) * (delta / 6.0),
+            angvel + (k1w + 2.0 * k2w + 2.0 * k3w + k4w) * delta / 6.0,
+        )
+    }
+}
+
+/// One classic Runge-Kutta step of a position and velocity, with `acceleration` evaluated at
+/// every stage
+fn rk4(
+    position: Vec3,
+    velocity: Vec3,
+    delta: f32,
+    acceleration: impl Fn(Vec3, Vec3) -> Vec3,
+) -> (Vec3, Vec3) {
+    let half = delta / 2.0;
+
+    let (k1x, k1v) = (velocity, acceleration(position, velocity));
+    let (k2x, k2v) = (
+        velocity + k1v * half,
+        acceleration(position + k1x * half, velocity + k1v * half),
+    );
+    let (k3x, k3v) = (
+        velocity + k2v * half,
+        acceleration(position + k2x * half, velocity + k2v * half),
+    );
+    let (k4x, k4v) = (
+        velocity + k3v * delta,
+        acceleration(position + k3x * delta, velocity + k3v * delta),
+    );
+
+    (
+        position + (k1x + 2.0 * k2x + 2.0 * k3x + k4x) * delta / 6.0,
+        velocity + (k1v + 2.0 * k2v + 2.0 * k3v + k4v) * delta / 6.0,
+    )
+}
+
+#[cfg(test)]
+mod methods {
+    use bevy::math::{Mat3, Quat, Vec3};
+    use float_cmp::assert_approx_eq;
+
+    use super::{angular, linear, rk4};
+    use crate::{components::inertia::Inertia, resources::IntegrationMethod};
+
+    #[test]
+    fn constant_acceleration() {
+        let acc = Vec3::new(1.0, -9.82, 0.5);
+        let vel = Vec3::X * 3.0;
+
+        // Exact for both, only Euler moves the whole step at the new velocity
+        for method in [IntegrationMethod::Verlet, IntegrationMethod::Rk4] {
+            let (pos, new_vel) = linear(method, Vec3::ZERO, vel, acc, 0.5);
+
+            assert!(pos.abs_diff_eq(vel * 0.5 + acc * 0.125, 1e-5), "{method:?}");
+            assert!(new_vel.abs_diff_eq(vel + acc * 0.5, 1e-5), "{method:?}");
+        }
+
+        let (pos, new_vel) = linear(
+            IntegrationMethod::SemiImplicitEuler,
+            Vec3::ZERO,
+            vel,
+            acc,
+            0.5,
+        );
+        assert!(pos.abs_diff_eq(vel * 0.5 + acc * 0.25, 1e-5));
+        assert!(new_vel.abs_diff_eq(vel + acc * 0.5, 1e-5));
+    }
+
+    #[test]
+    fn rk4_oscillator() {
+        // A full period of x'' = -x, back where it started
+        let (mut pos, mut vel) = (Vec3::X, Vec3::ZERO);
+        let steps = 100;
+        let delta = std::f32::consts::TAU / steps as f32;
+
+        for _ in 0..steps {
+            (pos, vel) = rk4(pos, vel, delta, |pos, _| -pos);
+        }
+
+        assert_approx_eq!(f32, pos.x, 1.0, epsilon = 1e-4);
+        assert_approx_eq!(f32, vel.x, 0.0, epsilon = 1e-4);
+    }
+
+    /// Relative change in kinetic energy of a freely spinning asymmetric body after 20 s
+    fn energy_drift(method: IntegrationMethod) -> f32 {
+        let inertia = Inertia::new(1.0, Mat3::from_diagonal(Vec3::new(1.0, 2.0, 3.0)));
+        let energy = |rotation: Quat, angvel: Vec3| {
+            inertia.kinetic_energy(Vec3::ZERO, rotation.inverse() * angvel)
+        };
+
+        // Close to the unstable middle axis, so it keeps tumbling
+        let (mut rotation, mut angvel) = (Quat::IDENTITY, Vec3::new(0.1, 2.0, 0.1));
+        let start = energy(rotation, angvel);
+
+        for _ in 0..1000 {
+            (rotation, angvel) = angular(method, rotation, angvel, &inertia, Vec3::ZERO, 0.02);
+        }
+
+        (energy(rotation, angvel) - start).abs() / start
+    }
+
+    #[test]
+    fn asymmetric_energy() {
+        let euler = energy_drift(IntegrationMethod::SemiImplicitEuler);
+        let rk4 = energy_drift(IntegrationMethod::Rk4);
+
+        assert!(euler > 0.01, "Euler only drifted by {euler}");
+        assert!(
+            rk4 * 100.0 < euler,
+            "RK4 drifted by {rk4}, Euler by {euler}"
+        );
+    }
+}
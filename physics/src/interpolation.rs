@@ -0,0 +1,342 @@
+use std::time::Duration;
+
+use bevy::{
+    ecs::{
+        component::Component,
+        entity::Entity,
+        query::With,
+        system::{Commands, Query, Res},
+        world::World,
+    },
+    time::{Fixed, Time},
+    transform::components::Transform,
+};
+
+use crate::{
+    components::Simulated,
+    pause::{self, PendingSteps, SimulationPaused},
+    replay::{self, ReplayBuffer},
+};
+
+/// The [Transform] of an object at the end of the last two fixed physics steps
+///
+/// Objects are drawn somewhere between the two, depending on how far the render time has moved
+/// past the last step. Added to every simulated object on its first step.
+#[derive(Component, Debug, Clone, Copy, PartialEq)]
+pub struct StepTransforms {
+    pub previous: Transform,
+    pub current: Transform,
+}
+
+impl StepTransforms {
+    /// Create [StepTransforms] for an object that is standing still at `transform`
+    #[must_use]
+    pub const fn new(transform: Transform) -> Self {
+        Self {
+            previous: transform,
+            current: transform,
+        }
+    }
+
+    /// Gets the [Transform] a fraction of the way from the previous step to the current one
+    #[must_use]
+    pub fn interpolate(&self, fraction: f32) -> Transform {
+        let (from, to) = (self.previous, self.current);
+
+        Transform {
+            translation: from.translation.lerp(to.translation, fraction),
+            rotation: from.rotation.slerp(to.rotation, fraction),
+            scale: from.scale.lerp(to.scale, fraction),
+        }
+    }
+}
+
+/// How far into the next fixed step the render time is, from 0 to 1
+///
+/// A zero `timestep` counts as being all the way through.
+///
+/// ```rust
+/// # use physics::interpolation::step_fraction;
+/// # use std::time::Duration;
+/// let fraction = step_fraction(Duration::from_millis(5), Duration::from_millis(20));
+///
+/// assert_eq!(fraction, 0.25);
+/// ```
+#[must_use]
+pub fn step_fraction(overstep: Duration, timestep: Duration) -> f32 {
+    if timestep.is_zero() {
+        return 1.0;
+    }
+
+    (overstep.as_secs_f32() / timestep.as_secs_f32()).clamp(0.0, 1.0)
+}
+
+/// Puts every object back at its last stepped [Transform] before the physics runs
+///
+/// Rendering moves the objects between steps, so without this the physics would continue from the
+/// interpolated position. Objects seen for the first time get their [StepTransforms] here.
+pub fn begin_step(
+    mut cmds: Commands,
+    mut query: Query<(Entity, &mut Transform, Option<&StepTransforms>), With<Simulated>>,
+) {
+    for (entity, mut trans, steps) in query.iter_mut() {
+        match steps {
+            Some(steps) => *trans = steps.current,
+            None => {
+                cmds.entity(entity).insert(StepTransforms::new(*trans));
+            }
+        }
+    }
+}
+
+/// Stores the result of the physics step as the current [StepTransforms]
+pub fn end_step(mut query: Query<(&Transform, &mut StepTransforms), With<Simulated>>) {
+    for (trans, mut steps) in query.iter_mut() {
+        steps.previous = steps.current;
+        steps.current = *trans;
+    }
+}
+
+/// Moves every object between its last two steps, based on the overstep of the fixed time
+///
+/// The fixed time keeps running while paused or replaying, so then the previous step is collapsed
+/// into the current one instead. Objects stay put at their last step, and resuming blends onwards
+/// from there.
+pub fn interpolate(
+    time: Res<Time<Fixed>>,
+    paused: Res<SimulationPaused>,
+    pending: Res<PendingSteps>,
+    replay: Res<ReplayBuffer>,
+    mut query: Query<(&mut Transform, &mut StepTransforms), With<Simulated>>,
+) {
+    let running = replay::is_live(replay) && pause::is_running(paused, pending);
+    let fraction = step_fraction(time.overstep(), time.timestep());
+
+    for (mut trans, mut steps) in query.iter_mut() {
+        if !running {
+            steps.previous = steps.current;
+        }
+
+        *trans = steps.interpolate(fraction);
+    }
+}
+
+/// Moves an object to `transform` without drawing it anywhere in between
+///
+/// Sets both [StepTransforms] along with the [Transform], so the interpolation doesn't streak
+/// from the old position to the new one. Velocities are left alone. Queue it from a system with
+/// `cmds.add(move |world: &mut World| teleport(world, entity, transform))`.
+pub fn teleport(world: &mut World, entity: Entity, transform: Transform) {
+    let Some(mut entity) = world.get_entity_mut(entity) else {
+        return;
+    };
+
+    if let Some(mut trans) = entity.get_mut::<Transform>() {
+        *trans = transform;
+    }
+
+    if let Some(mut steps) = entity.get_mut::<StepTransforms>() {
+        *steps = StepTransforms::new(transform);
+    }
+}
+
+#[cfg(test)]
+mod fraction {
+    use std::time::Duration;
+
+    use bevy::{
+        math::{Quat, Vec3},
+        transform::components::Transform,
+    };
+    use float_cmp::assert_approx_eq;
+
+    use super::{step_fraction, StepTransforms};
+
+    #[test]
+    fn overstep() {
+        let step = Duration::from_millis(16);
+
+        assert_eq!(step_fraction(Duration::ZERO, step), 0.0);
+        assert_approx_eq!(f32, step_fraction(Duration::from_millis(4), step), 0.25);
+        assert_approx_eq!(f32, step_fraction(Duration::from_millis(12), step), 0.75);
+
+        // Never extrapolates past the current step
+        assert_eq!(step_fraction(Duration::from_millis(40), step), 1.0);
+        assert_eq!(step_fraction(Duration::from_millis(1), Duration::ZERO), 1.0);
+    }
+
+    #[test]
+    fn between_steps() {
+        let steps = StepTransforms {
+            previous: Transform::from_translation(Vec3::ZERO),
+            current: Transform::from_translation(Vec3::X * 4.0)
+                .with_rotation(Quat::from_rotation_y(1.0)),
+        };
+
+        let halfway = steps.interpolate(0.5);
+        assert_approx_eq!(&[f32], &halfway.translation.to_array(), &[2.0, 0.0, 0.0]);
+        assert_approx_eq!(
+            &[f32],
+            &halfway.rotation.to_array(),
+            &Quat::from_rotation_y(0.5).to_array()
+        );
+
+        assert_eq!(steps.interpolate(0.0), steps.previous);
+        assert_eq!(steps.interpolate(1.0), steps.current);
+    }
+}
+
+#[cfg(test)]
+mod teleporting {
+    use std::time::Duration;
+
+    use bevy::{
+        app::App,
+        asset::{AssetApp, AssetPlugin},
+        gizmos::GizmoPlugin,
+        math::{Quat, Vec3},
+        render::render_resource::Shader,
+        time::TimeUpdateStrategy,
+        transform::components::Transform,
+        MinimalPlugins,
+    };
+
+    use super::{teleport, StepTransforms};
+    use crate::{
+        components::{
+            acceleration::Accelerator,
+            inertia::Inertia,
+            velocity::{AngularVelocity, Velocity},
+            SimulationBundle,
+        },
+        resources::PhysicsSettings,
+        SimulatiorPlugin,
+    };
+
+    #[test]
+    fn no_streak() {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, AssetPlugin::default()));
+        app.init_asset::<Shader>();
+        app.add_plugins((GizmoPlugin, SimulatiorPlugin::default()));
+
+        // Frames that don't line up with the steps, so the overstep differs every frame
+        app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(
+            33,
+        )));
+        app.insert_resource(PhysicsSettings {
+            gravity: Some(Vec3::ZERO),
+            timestep: 0.02,
+            ..Default::default()
+        });
+
+        let body = app
+            .world_mut()
+            .spawn(SimulationBundle::new(
+                Velocity::ZERO,
+                Accelerator::ZERO,
+                AngularVelocity::ZERO,
+                Inertia::sphere(0.1, 1.0),
+            ))
+            .id();
+
+        for _ in 0..5 {
+            app.update();
+        }
+
+        let target =
+            Transform::from_xyz(50.0, 10.0, -3.0).with_rotation(Quat::from_rotation_z(1.0));
+        teleport(app.world_mut(), body, target);
+
+        let steps = *app.world().get::<StepTransforms>(body).unwrap();
+        for fraction in [0.0, 0.3, 0.7, 1.0] {
+            assert_eq!(steps.interpolate(fraction), target);
+        }
+
+        for _ in 0..6 {
+            app.update();
+
+            let trans = app.world().get::<Transform>(body).unwrap();
+            assert_eq!(trans.translation, target.translation);
+        }
+    }
+}
+
+#[cfg(test)]
+mod paused {
+    use std::time::Duration;
+
+    use bevy::{
+        app::App,
+        asset::{AssetApp, AssetPlugin},
+        gizmos::GizmoPlugin,
+        math::Vec3,
+        render::render_resource::Shader,
+        time::TimeUpdateStrategy,
+        transform::components::Transform,
+        MinimalPlugins,
+    };
+
+    use super::StepTransforms;
+    use crate::{
+        components::{
+            acceleration::Accelerator,
+            inertia::Inertia,
+            velocity::{AngularVelocity, Velocity},
+            SimulationBundle,
+        },
+        pause::SimulationPaused,
+        resources::PhysicsSettings,
+        SimulatiorPlugin,
+    };
+
+    #[test]
+    fn stays_put() {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, AssetPlugin::default()));
+        app.init_asset::<Shader>();
+        app.add_plugins((GizmoPlugin, SimulatiorPlugin::default()));
+
+        // Frames shorter than the steps, so the overstep keeps cycling while paused
+        app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(7)));
+        app.insert_resource(PhysicsSettings {
+            gravity: Some(Vec3::ZERO),
+            timestep: 0.02,
+            ..Default::default()
+        });
+
+        let body = app
+            .world_mut()
+            .spawn(SimulationBundle::new(
+                Velocity(Vec3::X * 10.0),
+                Accelerator::ZERO,
+                AngularVelocity::ZERO,
+                Inertia::sphere(0.1, 1.0),
+            ))
+            .id();
+
+        for _ in 0..20 {
+            app.update();
+        }
+
+        app.insert_resource(SimulationPaused(true));
+        app.update();
+
+        let drawn = *app.world().get::<Transform>(body).unwrap();
+        let steps = *app.world().get::<StepTransforms>(body).unwrap();
+        assert_eq!(drawn, steps.current);
+
+        for _ in 0..20 {
+            app.update();
+
+            assert_eq!(*app.world().get::<Transform>(body).unwrap(), drawn);
+        }
+
+        // Picks up from where it stopped, without jumping back a step
+        app.insert_resource(SimulationPaused(false));
+        app.update();
+
+        let resumed = app.world().get::<Transform>(body).unwrap().translation;
+        assert!(resumed.x >= drawn.translation.x);
+    }
+}
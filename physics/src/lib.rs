@@ -1,68 +1,1090 @@
-use bevy::app::{Plugin, PostUpdate, Update};
-use bevy::ecs::query::With;
-use bevy::ecs::system::{Query, Res};
-use bevy::math::{Quat, Vec3};
-use bevy::time::Time;
+use bevy::app::{FixedUpdate, Plugin, PostUpdate, PreUpdate, Update};
+use bevy::ecs::query::{Has, With};
+use bevy::ecs::schedule::common_conditions::resource_exists_and_changed;
+use bevy::ecs::schedule::{Condition, IntoSystemConfigs, IntoSystemSetConfigs, SystemSet};
+use bevy::ecs::system::{Query, Res, ResMut};
+use bevy::math::Quat;
+use bevy::time::{Fixed, Time};
 use bevy::transform::components::Transform;
 
-use components::acceleration::Accelerator;
-use components::force::Moment;
+use analysis::{BodySamples, EnergyMonitor, SceneStats};
+use collisions::{CollisionEvent, CollisionSettings};
+use components::acceleration::{Accelerator, MaxAcceleration};
+use components::force::{Force, ForceAccumulator, ImpulseQueue, LastStepForces};
 use components::inertia::Inertia;
+use pause::{PauseOnCollision, PendingSteps, SimulationPaused};
+use replay::{ReplayBuffer, ReplayStep};
+use reset::{PerturbBody, Perturbation, ResetSimulation};
+use resources::{
+    Atmosphere, ForceToggles, GroundPlane, MaxBodies, PhysicsSettings, SimClock, UpAxis, Wind,
+};
+use rng::SimRng;
+use scene::ReloadScene;
+use sleep::{SleepSettings, Sleeping};
+use vector_arrows::{ArrowSettings, GizmosEnabled};
 
+pub mod analysis;
+pub mod broad_phase;
+pub mod collisions;
 pub mod components;
-mod vector_arrows;
+pub mod constraints;
+pub mod forces;
+pub mod integration;
+pub mod interpolation;
+pub mod names;
+pub mod pause;
+pub mod prediction;
+pub mod presets;
+pub mod replay;
+pub mod reset;
+pub mod resources;
+pub mod rng;
+pub mod scene;
+pub mod sleep;
+pub mod spawning;
+pub mod timestep;
+pub mod trail;
+pub mod vector_arrows;
 
-pub struct SimulatiorPlugin;
+#[derive(Default)]
+pub struct SimulatiorPlugin {
+    /// Inserted when building, keeping settings inserted before the plugin or the defaults
+    /// when [None]
+    settings: Option<PhysicsSettings>,
+}
+
+impl SimulatiorPlugin {
+    /// Create a [SimulatiorPlugin] configured by `settings`
+    ///
+    /// Overrides any [PhysicsSettings] inserted before the plugin.
+    #[must_use]
+    pub fn with_settings(settings: PhysicsSettings) -> Self {
+        Self {
+            settings: Some(settings),
+        }
+    }
+}
+
+/// The stages of a simulation step
+///
+/// [PhysicsSet::Forces], [PhysicsSet::Integrate] and [PhysicsSet::Collision] run in that order
+/// every fixed step in [FixedUpdate]. [PhysicsSet::Interpolate] runs every frame in [Update].
+/// Order other systems against these instead of the individual physics systems.
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub enum PhysicsSet {
+    /// Systems adding forces to the [ForceAccumulator]
+    Forces,
+
+    /// Integration of the accumulated forces, along with constraints on the result
+    Integrate,
+
+    /// Detecting and resolving collisions
+    Collision,
+
+    /// Moving the objects between the last two fixed steps for rendering
+    Interpolate,
+}
 
 impl Plugin for SimulatiorPlugin {
     fn build(&self, app: &mut bevy::prelude::App) {
-        app.add_systems(Update, update_simulated);
-        app.add_systems(Update, update_simulated);
+        match self.settings {
+            Some(settings) => app.insert_resource(settings),
+            None => app.init_resource::<PhysicsSettings>(),
+        };
+
+        app.init_resource::<Wind>();
+        app.init_resource::<Atmosphere>();
+        app.init_resource::<UpAxis>();
+        app.init_resource::<ForceToggles>();
+        app.init_resource::<MaxBodies>();
+        app.init_resource::<SimClock>();
+        app.init_resource::<CollisionSettings>();
+        app.init_resource::<GroundPlane>();
+        app.init_resource::<SleepSettings>();
+        app.init_resource::<SimulationPaused>();
+        app.init_resource::<PendingSteps>();
+        app.init_resource::<PauseOnCollision>();
+        app.init_resource::<Perturbation>();
+        app.init_resource::<SimRng>();
+        app.init_resource::<BodySamples>();
+        app.init_resource::<SceneStats>();
+        app.init_resource::<EnergyMonitor>();
+        app.init_resource::<ArrowSettings>();
+        app.init_resource::<GizmosEnabled>();
+        app.init_resource::<ReplayBuffer>();
+        app.add_event::<ReplayStep>();
+        app.add_event::<CollisionEvent>();
+        app.add_event::<ResetSimulation>();
+        app.add_event::<PerturbBody>();
+        app.add_event::<ReloadScene>();
+        app.configure_sets(
+            FixedUpdate,
+            (
+                PhysicsSet::Forces,
+                PhysicsSet::Integrate,
+                PhysicsSet::Collision,
+            )
+                .chain()
+                .run_if(replay::is_live.and_then(pause::is_running)),
+        );
+        app.add_systems(
+            FixedUpdate,
+            (reset::capture_initial_state, interpolation::begin_step).before(PhysicsSet::Forces),
+        );
+        app.add_systems(
+            FixedUpdate,
+            (
+                forces::gravity,
+                forces::central_gravity,
+                forces::aerodynamics,
+                forces::drag,
+                forces::magnus,
+                forces::moments,
+                forces::springs,
+                forces::motors,
+            )
+                .in_set(PhysicsSet::Forces),
+        );
+        app.add_systems(
+            FixedUpdate,
+            (
+                advance_clock,
+                sleep::wake_up,
+                apply_impulses,
+                constraints::axis_lock,
+                update_simulated,
+                constraints::ball_joints,
+                constraints::max_angular_speed,
+                constraints::align_to_velocity,
+                constraints::planar,
+            )
+                .chain()
+                .in_set(PhysicsSet::Integrate),
+        );
+        app.add_systems(
+            FixedUpdate,
+            (collisions::spheres, constraints::pin_locked_axes)
+                .chain()
+                .in_set(PhysicsSet::Collision),
+        );
+        app.add_systems(
+            FixedUpdate,
+            (
+                (
+                    interpolation::end_step,
+                    replay::record,
+                    trail::record_trail,
+                    sleep::fall_asleep,
+                    (pause::pause_on_collision, pause::finish_step).chain(),
+                )
+                    .run_if(replay::is_live.and_then(pause::is_running)),
+                analysis::sample_bodies,
+                timestep::adapt_timestep,
+                (
+                    analysis::scene_stats,
+                    analysis::energy_drift,
+                    analysis::trace_energy.run_if(replay::is_live.and_then(pause::is_running)),
+                )
+                    .chain(),
+            )
+                .after(PhysicsSet::Collision),
+        );
+        app.add_systems(Update, names::warn_duplicate_names);
+        app.add_systems(Update, (scene::watch_scene, scene::reload_scene).chain());
+        app.add_systems(
+            PreUpdate,
+            apply_settings.run_if(resource_exists_and_changed::<PhysicsSettings>),
+        );
+        app.add_systems(
+            Update,
+            (
+                reset::reset_simulation,
+                reset::perturb,
+                replay::scrub,
+                interpolation::interpolate.in_set(PhysicsSet::Interpolate),
+                constraints::orient_with_parent_velocity,
+            )
+                .chain(),
+        );
         app.add_systems(
             PostUpdate,
-            (vector_arrows::velocity, vector_arrows::acceleration),
+            (
+                vector_arrows::smooth_velocity.before(vector_arrows::velocity),
+                vector_arrows::velocity,
+                vector_arrows::acceleration,
+                vector_arrows::spin_axis,
+                vector_arrows::angle_of_attack,
+                vector_arrows::contacts,
+                prediction::predict_impact,
+                prediction::show_prediction,
+                prediction::show_ghost,
+                prediction::show_drag_prediction,
+                trail::draw_trail,
+            ),
         );
     }
 }
 
-/// Updates objects with acceleration
+/// Sets the fixed timestep to the step of the [PhysicsSettings]
+///
+/// Runs before the fixed steps of the frame, so a change applies to all of them. A step that
+/// isn't positive and finite keeps the last one instead.
+pub fn apply_settings(settings: Res<PhysicsSettings>, fixed: Option<ResMut<Time<Fixed>>>) {
+    if let (Some(mut fixed), Some(step)) = (fixed, settings.step()) {
+        fixed.set_timestep(step);
+    }
+}
+
+/// Applies and clears every queued impulse, in the order they were queued
+///
+/// The impulses are given in world space, so the angular part goes through the inertia tensor as
+/// the object is currently rotated.
+pub fn apply_impulses(
+    mut query: Query<
+        (
+            &Transform,
+            &mut components::velocity::Velocity,
+            &mut components::velocity::AngularVelocity,
+            &Inertia,
+            &mut ImpulseQueue,
+        ),
+        With<components::Simulated>,
+    >,
+) {
+    for (trans, mut vel, mut angvel, inertia, mut queue) in query.iter_mut() {
+        let inverse_tensor = inertia.world_inverse_tensor(trans.rotation);
+
+        for impulse in queue.0.drain(..) {
+            let (angular, linear) = impulse.get_parts();
+
+            // An impulse is a force over a second, so the acceleration is the change in velocity
+            vel.0 += inertia.get_acceleration(Force(linear));
+            angvel.0 += inverse_tensor * angular;
+        }
+    }
+}
+
+/// Moves the [SimClock] on by the same step [update_simulated] integrates over
+pub fn advance_clock(time: Res<Time>, settings: Res<PhysicsSettings>, mut clock: ResMut<SimClock>) {
+    clock.advance(settings.clamp_delta(time.delta_seconds()));
+}
+
+/// Updates objects with acceleration, using the [IntegrationMethod](resources::IntegrationMethod)
+/// of the [PhysicsSettings]
 #[allow(clippy::type_complexity)]
 pub fn update_simulated(
     time: Res<Time>,
+    settings: Res<PhysicsSettings>,
     mut accelerators: Query<
         (
             &mut Transform,
             &mut components::velocity::Velocity,
             &mut components::velocity::AngularVelocity,
             &Inertia,
+            &mut ForceAccumulator,
             Option<&Accelerator>,
+            Option<&MaxAcceleration>,
+            Option<&mut LastStepForces>,
+            Has<Sleeping>,
         ),
         With<components::Simulated>,
     >,
 ) {
-    let delta = time.delta_seconds();
-    let half_delta = delta / 2.0;
+    let delta = settings.clamp_delta(time.delta_seconds());
+
+    for (mut trans, mut vel, mut angvel, inertia, mut forces, acc, max_acc, last, sleeping) in
+        accelerators.iter_mut()
+    {
+        if let Some(mut last) = last {
+            last.force = forces.force;
+            last.torque = forces.torque;
+        }
+
+        let (torque, force) = forces.take();
+
+        // Static and sleeping objects never move, only the forces are drained
+        if inertia.is_static() || sleeping {
+            continue;
+        }
 
-    for (mut trans, mut vel, mut angvel, inertia, acc) in accelerators.iter_mut() {
-        let acc = acc.unwrap_or(&Accelerator::ZERO);
+        let mut acc =
+            Accelerator(acc.unwrap_or(&Accelerator::ZERO).0 + inertia.get_acceleration(force));
 
-        let (torque, _force) = Moment::new(Vec3::Z, Vec3::new(0.0, 10.0, 0.0)).get_parts();
-        let angacc = inertia.get_angular_acceleration(torque);
+        if let Some(max) = max_acc {
+            acc.0 = acc.0.clamp_length_max(max.0);
+        }
 
         // Accelerate and move
-        vel.accelerate(acc, half_delta);
-        angvel.0 += angacc * half_delta;
+        (trans.translation, vel.0) =
+            integration::linear(settings.integration, trans.translation, vel.0, acc.0, delta);
+
+        // Renormalized before and after every step, even without any spin, so neither rounding
+        // errors nor a rotation set from outside build up over a long run
+        let (rotation, spin) = integration::angular(
+            settings.integration,
+            renormalize(trans.rotation),
+            angvel.0,
+            inertia,
+            torque.0,
+            delta,
+        );
+
+        trans.rotation = renormalize(rotation);
+        angvel.0 = spin;
+    }
+}
+
+/// Scales a quaternion back to unit length, falling back to no rotation if it has degenerated
+fn renormalize(rotation: Quat) -> Quat {
+    let length = rotation.length();
+
+    if length.is_normal() {
+        rotation / length
+    } else {
+        Quat::IDENTITY
+    }
+}
+
+#[cfg(test)]
+mod ordering {
+    use std::time::Duration;
+
+    use bevy::{
+        app::{App, FixedUpdate, Update},
+        asset::{AssetApp, AssetPlugin},
+        ecs::{
+            query::With,
+            schedule::IntoSystemConfigs,
+            system::{Query, ResMut, Resource},
+        },
+        gizmos::GizmoPlugin,
+        math::Vec3,
+        render::render_resource::Shader,
+        time::TimeUpdateStrategy,
+        transform::components::Transform,
+        MinimalPlugins,
+    };
+
+    use super::{PhysicsSet, SimulatiorPlugin};
+    use crate::{
+        components::{
+            acceleration::Accelerator, inertia::Inertia, velocity::AngularVelocity,
+            velocity::Velocity, Simulated, SimulationBundle,
+        },
+        resources::PhysicsSettings,
+    };
+
+    #[derive(Resource, Default)]
+    struct Seen {
+        before: Vec<Vec3>,
+        after: Vec<Vec3>,
+    }
+
+    /// App rendering a frame every `frame` while stepping the physics every `step`
+    fn app(frame: Duration, step: Duration) -> App {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, AssetPlugin::default()));
+        app.init_asset::<Shader>();
+        app.add_plugins((GizmoPlugin, SimulatiorPlugin::default()));
+        app.insert_resource(TimeUpdateStrategy::ManualDuration(frame));
+        app.insert_resource(PhysicsSettings {
+            gravity: Some(Vec3::ZERO),
+            timestep: step.as_secs_f32(),
+            ..Default::default()
+        });
+
+        app
+    }
+
+    fn body() -> SimulationBundle {
+        SimulationBundle::new(
+            Velocity(Vec3::X),
+            Accelerator::ZERO,
+            AngularVelocity::ZERO,
+            Inertia::cylinder_y(1.0, 1.0, 1.0),
+        )
+    }
+
+    #[test]
+    fn external_systems() {
+        let step = Duration::from_millis(100);
+        let mut app = app(step, step);
+        app.init_resource::<Seen>();
+        app.add_systems(
+            FixedUpdate,
+            (
+                (|mut seen: ResMut<Seen>, q: Query<&Transform, With<Simulated>>| {
+                    seen.before.push(q.single().translation);
+                })
+                .before(PhysicsSet::Forces),
+                (|mut seen: ResMut<Seen>, q: Query<&Transform, With<Simulated>>| {
+                    seen.after.push(q.single().translation);
+                })
+                .after(PhysicsSet::Integrate),
+            ),
+        );
+
+        app.world_mut().spawn(body());
+
+        // The timestep is an f32, a hair longer than the frames, so one more frame for 3 steps
+        for _ in 0..5 {
+            app.update();
+        }
+
+        // Every step, the system after integration sees the body one step further than the one
+        // running before the forces
+        let seen = app.world().resource::<Seen>();
+        assert!(seen.after.len() >= 3, "only {} steps ran", seen.after.len());
+
+        for (before, after) in seen.before.iter().zip(seen.after.iter()).skip(1) {
+            assert!(after.x > before.x, "{before} should be behind {after}");
+        }
+    }
+
+    #[test]
+    fn interpolated() {
+        // Four frames for every physics step
+        let mut app = app(Duration::from_millis(25), Duration::from_millis(100));
+        app.init_resource::<Seen>();
+        app.add_systems(
+            Update,
+            (|mut seen: ResMut<Seen>, q: Query<&Transform, With<Simulated>>| {
+                seen.after.push(q.single().translation);
+            })
+            .after(PhysicsSet::Interpolate),
+        );
+
+        app.world_mut().spawn(body());
+
+        for _ in 0..20 {
+            app.update();
+        }
+
+        // Once the first steps are done, the body moves every frame instead of every fourth
+        let seen = &app.world().resource::<Seen>().after;
+        for frames in seen[8..].windows(2) {
+            assert!(
+                frames[1].x > frames[0].x,
+                "stuttered between {} and {}",
+                frames[0],
+                frames[1]
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod up_axis {
+    use bevy::{app::App, math::Vec3};
+
+    use super::SimulatiorPlugin;
+    use crate::resources::{PhysicsSettings, UpAxis};
+
+    /// Gets the gravity the systems of `app` apply
+    fn gravity(app: &App) -> Vec3 {
+        let world = app.world();
+
+        world
+            .resource::<PhysicsSettings>()
+            .gravity_for(*world.resource::<UpAxis>())
+            .0
+    }
+
+    #[test]
+    fn gravity_follows() {
+        let mut app = App::new();
+        app.insert_resource(UpAxis::Z);
+        app.add_plugins(SimulatiorPlugin::default());
+
+        assert_eq!(gravity(&app), Vec3::Z * -9.82);
+
+        let mut app = App::new();
+        app.add_plugins(SimulatiorPlugin::default());
+
+        assert_eq!(gravity(&app), Vec3::Y * -9.82);
+        assert_eq!(*app.world().resource::<UpAxis>(), UpAxis::Y);
+    }
+
+    #[test]
+    fn keeps_inserted_gravity() {
+        let mut app = App::new();
+        app.insert_resource(UpAxis::Z);
+        app.insert_resource(PhysicsSettings {
+            gravity: Some(Vec3::X),
+            ..Default::default()
+        });
+        app.add_plugins(SimulatiorPlugin::default());
+
+        assert_eq!(gravity(&app), Vec3::X);
+    }
+}
+
+#[cfg(test)]
+mod settings {
+    use std::time::Duration;
+
+    use bevy::{
+        app::{App, FixedUpdate},
+        asset::{AssetApp, AssetPlugin},
+        ecs::system::{ResMut, Resource},
+        gizmos::GizmoPlugin,
+        math::Vec3,
+        render::render_resource::Shader,
+        time::{Fixed, Time, TimeUpdateStrategy},
+        MinimalPlugins,
+    };
+    use float_cmp::assert_approx_eq;
+
+    use super::SimulatiorPlugin;
+    use crate::{
+        components::{
+            acceleration::Accelerator,
+            inertia::Inertia,
+            velocity::{AngularVelocity, Velocity},
+            SimulationBundle,
+        },
+        interpolation::StepTransforms,
+        resources::{IntegrationMethod, PhysicsSettings},
+    };
+
+    #[derive(Resource, Default)]
+    struct Steps(u32);
+
+    /// App rendering a frame every 100 ms, with the simulation configured by `settings`
+    fn app(settings: PhysicsSettings) -> App {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, AssetPlugin::default()));
+        app.init_asset::<Shader>();
+        app.add_plugins((GizmoPlugin, SimulatiorPlugin::with_settings(settings)));
+        app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(
+            100,
+        )));
+
+        app
+    }
+
+    #[test]
+    fn respected() {
+        let mut app = app(PhysicsSettings {
+            gravity: Some(Vec3::Y * -2.0),
+            timestep: 0.1,
+            substeps: 4,
+            max_delta: None,
+            ..Default::default()
+        });
+        app.init_resource::<Steps>();
+        app.add_systems(FixedUpdate, |mut steps: ResMut<Steps>| steps.0 += 1);
+
+        let body = app
+            .world_mut()
+            .spawn(SimulationBundle::new(
+                Velocity::ZERO,
+                Accelerator::ZERO,
+                AngularVelocity::ZERO,
+                Inertia::sphere(1.0, 1.0),
+            ))
+            .id();
 
-        trans.translation += vel.0 * delta;
+        for _ in 0..5 {
+            app.update();
+        }
+
+        let world = app.world();
+        assert_eq!(
+            world.resource::<Time<Fixed>>().timestep(),
+            Duration::from_millis(25)
+        );
+
+        // Four steps for every 100 ms frame, each falling for 25 ms at 2 m/s^2
+        let steps = world.resource::<Steps>().0;
+        assert!(steps >= 12, "only {steps} steps ran");
+
+        let vel = world.get::<Velocity>(body).unwrap().0;
+        assert_approx_eq!(f32, vel.y, -2.0 * 0.025 * steps as f32, epsilon = 1e-4);
+    }
+
+    #[test]
+    fn changed_later() {
+        let mut app = app(PhysicsSettings::default());
+        let body = app
+            .world_mut()
+            .spawn(SimulationBundle::new(
+                Velocity::ZERO,
+                Accelerator::ZERO,
+                AngularVelocity::ZERO,
+                Inertia::sphere(1.0, 1.0),
+            ))
+            .id();
+        app.update();
+
+        // Read by the systems directly, so the next step already pulls sideways
+        app.world_mut().resource_mut::<PhysicsSettings>().gravity = Some(Vec3::X);
+        app.world_mut().get_mut::<Velocity>(body).unwrap().0 = Vec3::ZERO;
+        app.update();
+
+        let vel = app.world().get::<Velocity>(body).unwrap().0;
+        assert!(vel.x > 0.0 && vel.y == 0.0, "{vel}");
+    }
+
+    #[test]
+    fn invalid_timestep() {
+        let mut app = app(PhysicsSettings::default());
+        app.update();
+
+        for timestep in [-1.0, 0.0, f32::NAN, f32::INFINITY] {
+            app.world_mut().resource_mut::<PhysicsSettings>().timestep = timestep;
+            app.update();
+
+            // Keeps the last step instead of panicking
+            assert_eq!(
+                app.world().resource::<Time<Fixed>>().timestep(),
+                PhysicsSettings::default().step().unwrap()
+            );
+        }
+    }
+
+    /// Time fallen and height after falling from rest for about a second in steps of 1/64 s with
+    /// `integration`
+    fn fallen(integration: IntegrationMethod) -> (f32, f32) {
+        let mut app = app(PhysicsSettings {
+            integration,
+            gravity: Some(Vec3::Y * -10.0),
+            ..Default::default()
+        });
+        app.insert_resource(TimeUpdateStrategy::ManualDuration(
+            PhysicsSettings::default().step().unwrap(),
+        ));
+        let body = app
+            .world_mut()
+            .spawn(SimulationBundle::new(
+                Velocity::ZERO,
+                Accelerator::ZERO,
+                AngularVelocity::ZERO,
+                Inertia::sphere(1.0, 1.0),
+            ))
+            .id();
+
+        while app.world().resource::<Time<Fixed>>().elapsed_seconds() < 1.0 {
+            app.update();
+        }
+
+        let world = app.world();
+
+        // Every method gets the velocity right under constant gravity
+        let time = world.get::<Velocity>(body).unwrap().0.y / -10.0;
+        let step = world.get::<StepTransforms>(body).unwrap();
+
+        (time, step.current.translation.y)
+    }
+
+    #[test]
+    fn integration_method() {
+        // Exact for these two, while Euler falls half a step further
+        for method in [IntegrationMethod::Verlet, IntegrationMethod::Rk4] {
+            let (time, height) = fallen(method);
+            assert!(time > 0.9);
+            assert_approx_eq!(f32, height, -5.0 * time * time, epsilon = 1e-3);
+        }
+
+        let (time, height) = fallen(IntegrationMethod::SemiImplicitEuler);
+        let extra = 5.0 * time / 64.0;
+        assert_approx_eq!(f32, height, -5.0 * time * time - extra, epsilon = 1e-3);
+    }
+}
+
+#[cfg(test)]
+mod clock {
+    use std::time::Duration;
+
+    use bevy::{
+        app::App,
+        asset::{AssetApp, AssetPlugin},
+        gizmos::GizmoPlugin,
+        render::render_resource::Shader,
+        time::{Fixed, Time, TimeUpdateStrategy},
+        MinimalPlugins,
+    };
+    use float_cmp::assert_approx_eq;
 
-        let delta_rot =
-            Quat::from_vec4((angvel.0 * delta / 2.0).extend(trans.rotation.w * delta / 2.0));
+    use super::SimulatiorPlugin;
+    use crate::{
+        pause::{PendingSteps, SimulationPaused},
+        resources::{PhysicsSettings, SimClock},
+    };
 
-        if delta_rot.w != 0.0 {
-            trans.rotation = (trans.rotation + delta_rot.normalize() * trans.rotation).normalize();
+    fn elapsed(app: &App) -> f32 {
+        app.world().resource::<SimClock>().elapsed()
+    }
+
+    #[test]
+    fn paused_and_stepped() {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, AssetPlugin::default()));
+        app.init_asset::<Shader>();
+        app.add_plugins((GizmoPlugin, SimulatiorPlugin::default()));
+        app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(
+            100,
+        )));
+        app.insert_resource(PhysicsSettings {
+            timestep: 0.05,
+            ..Default::default()
+        });
+
+        for _ in 0..5 {
+            app.update();
+        }
+
+        // Every step that ran moved the clock by the timestep, as far as the fixed time got
+        let fixed = app.world().resource::<Time<Fixed>>().elapsed_seconds();
+        assert!(fixed > 0.0);
+        assert_approx_eq!(f32, elapsed(&app), fixed, epsilon = 1e-5);
+
+        app.world_mut().resource_mut::<SimulationPaused>().0 = true;
+        let paused = elapsed(&app);
+
+        for _ in 0..5 {
+            app.update();
         }
+        assert_eq!(elapsed(&app), paused);
+
+        app.world_mut().resource_mut::<PendingSteps>().0 = 1;
+        for _ in 0..3 {
+            app.update();
+        }
+        assert_approx_eq!(f32, elapsed(&app), paused + 0.05, epsilon = 1e-5);
+    }
+
+    #[test]
+    fn set() {
+        let mut clock = SimClock::default();
+        clock.advance(0.25);
+        clock.advance(0.5);
+        assert_eq!(clock.elapsed(), 0.75);
+
+        clock.set_elapsed(10.0);
+        clock.advance(1.0);
+        assert_eq!(clock.elapsed(), 11.0);
+    }
+}
+
+#[cfg(test)]
+mod impulses {
+    use std::f32::consts::FRAC_PI_2;
+
+    use bevy::{
+        ecs::{system::RunSystemOnce, world::World},
+        math::{Mat3, Quat, Vec3},
+    };
+    use float_cmp::assert_approx_eq;
+
+    use super::apply_impulses;
+    use crate::components::{
+        acceleration::Accelerator,
+        force::{Impulse, ImpulseQueue},
+        inertia::Inertia,
+        velocity::{AngularVelocity, Velocity},
+        SimulationBundle,
+    };
+
+    #[test]
+    fn two_hits() {
+        let mut world = World::new();
+
+        let mut queue = ImpulseQueue::default();
+        queue.push(Impulse::new(Vec3::ZERO, Vec3::X * 4.0));
+        queue.push(Impulse::new(Vec3::Y, Vec3::new(1.0, 2.0, 0.0)));
+
+        let body = world
+            .spawn((
+                SimulationBundle::new(
+                    Velocity(Vec3::Z),
+                    Accelerator::ZERO,
+                    AngularVelocity::ZERO,
+                    Inertia::new(2.0, Mat3::IDENTITY * 4.0),
+                ),
+                queue,
+            ))
+            .id();
+
+        world.run_system_once(apply_impulses);
+
+        // 4 + 0 along x and 0 + 2 along y on 2 kg, with the second hit also turning the body
+        let vel = world.get::<Velocity>(body).unwrap().0;
+        let angvel = world.get::<AngularVelocity>(body).unwrap().0;
+
+        assert_approx_eq!(&[f32], &vel.to_array(), &[2.0, 1.0, 1.0]);
+        assert_approx_eq!(&[f32], &angvel.to_array(), &[0.0, 0.0, -0.25]);
+        assert!(world.get::<ImpulseQueue>(body).unwrap().0.is_empty());
+    }
+
+    #[test]
+    fn rotated_cuboid() {
+        let mut world = World::new();
+
+        let mut queue = ImpulseQueue::default();
+        queue.push(Impulse::new(Vec3::Z, Vec3::X));
+
+        // Turned a quarter around z, so the long local x axis lies along world y
+        let mut bundle = SimulationBundle::new(
+            Velocity(Vec3::ZERO),
+            Accelerator::ZERO,
+            AngularVelocity::ZERO,
+            Inertia::cuboid(1.0, 2.0, 3.0, 12.0),
+        );
+        bundle.spatial.transform.rotation = Quat::from_rotation_z(FRAC_PI_2);
+        let body = world.spawn((bundle, queue)).id();
+
+        world.run_system_once(apply_impulses);
+
+        // Turning around world y is turning around local x, which has 2^2 + 3^2 kg m^2
+        let angvel = world.get::<AngularVelocity>(body).unwrap().0;
+
+        assert_approx_eq!(&[f32], &angvel.to_array(), &[0.0, 1.0 / 13.0, 0.0]);
+        assert_eq!(world.get::<Velocity>(body).unwrap().0, Vec3::ZERO);
+    }
+}
+
+#[cfg(test)]
+mod last_step {
+    use std::time::Duration;
+
+    use bevy::{
+        app::{App, Update},
+        ecs::schedule::IntoSystemConfigs,
+        math::Vec3,
+        time::Time,
+    };
+    use float_cmp::assert_approx_eq;
+
+    use super::update_simulated;
+    use crate::{
+        components::{
+            acceleration::Accelerator,
+            force::{ForceAccumulator, LastStepForces, Moment, MomentList, MotorTorque},
+            inertia::Inertia,
+            velocity::{AngularVelocity, Velocity},
+            SimulationBundle,
+        },
+        forces::{gravity, moments, motors},
+        resources::{ForceToggles, PhysicsSettings, UpAxis},
+    };
+
+    #[test]
+    fn matches_applied() {
+        let mut app = App::new();
+        let mut time = Time::<()>::default();
+        time.advance_by(Duration::from_millis(10));
+
+        app.insert_resource(time);
+        app.init_resource::<PhysicsSettings>();
+        app.init_resource::<UpAxis>();
+        app.init_resource::<ForceToggles>();
+        app.add_systems(
+            Update,
+            ((gravity, moments, motors), update_simulated).chain(),
+        );
+
+        let body = app
+            .world_mut()
+            .spawn((
+                SimulationBundle::new(
+                    Velocity::ZERO,
+                    Accelerator::ZERO,
+                    AngularVelocity::ZERO,
+                    Inertia::sphere(1.0, 2.0),
+                ),
+                MomentList(vec![Moment::new(Vec3::X, Vec3::new(3.0, 4.0, 0.0))]),
+                MotorTorque {
+                    axis: Vec3::X,
+                    magnitude: 2.0,
+                },
+                LastStepForces::default(),
+            ))
+            .id();
+
+        app.update();
+
+        // Gravity on 2 kg plus the moment along x, the part of it across x turns around z
+        let last = app.world().get::<LastStepForces>(body).unwrap();
+        assert_approx_eq!(&[f32], &last.force.to_array(), &[3.0, -2.0 * 9.82, 0.0]);
+        assert_approx_eq!(&[f32], &last.torque.to_array(), &[2.0, 0.0, 4.0]);
+
+        // The accumulator itself was still drained
+        let forces = app.world().get::<ForceAccumulator>(body).unwrap();
+        assert_eq!(forces.force, Vec3::ZERO);
+        assert_eq!(forces.torque, Vec3::ZERO);
+    }
+}
+
+#[cfg(test)]
+mod clamping {
+    use std::time::Duration;
+
+    use bevy::{
+        app::{App, Update},
+        math::Vec3,
+        time::Time,
+        transform::components::Transform,
+    };
+    use float_cmp::assert_approx_eq;
+
+    use super::update_simulated;
+    use crate::{
+        components::{
+            acceleration::{Accelerator, MaxAcceleration},
+            force::Force,
+            inertia::Inertia,
+            velocity::{AngularVelocity, Velocity},
+            SimulationBundle,
+        },
+        resources::PhysicsSettings,
+    };
+
+    #[test]
+    fn max_acceleration() {
+        let mut app = App::new();
+        let mut time = Time::<()>::default();
+        time.advance_by(Duration::from_secs(1));
+
+        app.insert_resource(time);
+        app.insert_resource(PhysicsSettings {
+            max_delta: None,
+            ..Default::default()
+        });
+        app.add_systems(Update, update_simulated);
+
+        let mut body = SimulationBundle::new(
+            Velocity::ZERO,
+            Accelerator::ZERO,
+            AngularVelocity::ZERO,
+            Inertia::sphere(0.1, 1e-6),
+        );
+        body.forces.add_force(Force(Vec3::new(1e6, 1e6, 0.0)));
+
+        let body = app.world_mut().spawn((body, MaxAcceleration(10.0))).id();
+
+        app.update();
+
+        // One second at the clamped acceleration
+        let vel = app.world().get::<Velocity>(body).unwrap().0;
+        assert_approx_eq!(f32, vel.length(), 10.0, epsilon = 1e-4);
+        assert_approx_eq!(f32, vel.x, vel.y);
+    }
+
+    #[test]
+    fn max_delta_time() {
+        let mut app = App::new();
+        let mut time = Time::<()>::default();
+        time.advance_by(Duration::from_secs(5));
+
+        app.insert_resource(time);
+        app.init_resource::<PhysicsSettings>();
+        app.add_systems(Update, update_simulated);
+
+        let body = app
+            .world_mut()
+            .spawn(SimulationBundle::new(
+                Velocity(Vec3::X * 10.0),
+                Accelerator::ZERO,
+                AngularVelocity::ZERO,
+                Inertia::sphere(0.1, 1.0),
+            ))
+            .id();
+
+        app.update();
+
+        // Moved for 1/20 of a second, not 5
+        let pos = app.world().get::<Transform>(body).unwrap().translation;
+        assert_approx_eq!(f32, pos.x, 0.5);
+    }
+}
+
+#[cfg(test)]
+mod orientation {
+    use std::time::Duration;
+
+    use bevy::{
+        app::{App, Update},
+        math::{Quat, Vec3},
+        time::Time,
+        transform::components::Transform,
+    };
+    use float_cmp::assert_approx_eq;
+
+    use super::{renormalize, update_simulated};
+    use crate::{
+        components::{
+            acceleration::Accelerator,
+            inertia::Inertia,
+            velocity::{AngularVelocity, Velocity},
+            SimulationBundle,
+        },
+        resources::PhysicsSettings,
+    };
+
+    fn app() -> App {
+        let mut app = App::new();
+        let mut time = Time::<()>::default();
+        time.advance_by(Duration::from_secs_f32(1.0 / 60.0));
+
+        app.insert_resource(time);
+        app.init_resource::<PhysicsSettings>();
+        app.add_systems(Update, update_simulated);
+
+        app
+    }
+
+    #[test]
+    fn long_spin() {
+        let mut app = app();
+        let body = app
+            .world_mut()
+            .spawn(SimulationBundle::new(
+                Velocity::ZERO,
+                Accelerator::ZERO,
+                AngularVelocity(Vec3::new(1.0, 2.0, 3.0)),
+                Inertia::sphere(1.0, 1.0),
+            ))
+            .id();
+
+        for _ in 0..10000 {
+            app.update();
+        }
+
+        let rotation = app.world().get::<Transform>(body).unwrap().rotation;
+        assert!(rotation.is_finite());
+        assert_approx_eq!(f32, rotation.length(), 1.0, epsilon = 1e-4);
+    }
+
+    #[test]
+    fn corrects_outside_changes() {
+        let mut app = app();
+        let mut bundle = SimulationBundle::new(
+            Velocity::ZERO,
+            Accelerator::ZERO,
+            AngularVelocity::ZERO,
+            Inertia::sphere(1.0, 1.0),
+        );
+        bundle.spatial.transform.rotation = Quat::from_rotation_x(1.0) * 3.0;
+        let body = app.world_mut().spawn(bundle).id();
+
+        app.update();
+
+        let rotation = app.world().get::<Transform>(body).unwrap().rotation;
+        assert_approx_eq!(
+            &[f32],
+            &rotation.to_array(),
+            &Quat::from_rotation_x(1.0).to_array()
+        );
+    }
+
+    #[test]
+    fn degenerate() {
+        let zero = Quat::from_xyzw(0.0, 0.0, 0.0, 0.0);
+        let nan = Quat::from_xyzw(f32::NAN, 0.0, 0.0, 1.0);
 
-        angvel.0 += angacc * half_delta;
-        vel.accelerate(acc, half_delta);
+        assert_eq!(renormalize(zero), Quat::IDENTITY);
+        assert_eq!(renormalize(nan), Quat::IDENTITY);
+        assert_eq!(renormalize(Quat::IDENTITY * 0.5), Quat::IDENTITY);
     }
 }
@@ -0,0 +1,90 @@
+use bevy::ecs::{component::Component, entity::Entity, query::Added, system::Query, world::World};
+
+/// Human readable name of an object, for scripts, the ui and exported data
+///
+/// Meant to be unique, duplicates are warned about by [warn_duplicate_names] but still allowed.
+/// [find_body] returns one of them.
+#[derive(Component, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BodyName(pub String);
+
+/// Finds an object by its [BodyName]
+#[must_use]
+pub fn find_body(world: &World, name: &str) -> Option<Entity> {
+    world
+        .iter_entities()
+        .find(|entity| entity.get::<BodyName>().is_some_and(|n| n.0 == name))
+        .map(|entity| entity.id())
+}
+
+/// Gets the label of an object in exported data, its [BodyName] or the [Entity] otherwise
+#[must_use]
+pub fn label(entity: Entity, name: Option<&BodyName>) -> String {
+    name.map_or_else(|| entity.to_string(), |name| name.0.clone())
+}
+
+/// Logs a warning when an object gets a [BodyName] that is already taken
+pub fn warn_duplicate_names(
+    added: Query<(Entity, &BodyName), Added<BodyName>>,
+    names: Query<(Entity, &BodyName)>,
+) {
+    for (entity, name) in added.iter() {
+        if names
+            .iter()
+            .any(|(other, other_name)| other != entity && other_name == name)
+        {
+            log::warn!("Body name {:?} is used more than once", name.0);
+        }
+    }
+}
+
+#[cfg(test)]
+mod lookup {
+    use bevy::{ecs::world::World, math::Vec3, transform::components::Transform};
+
+    use super::{find_body, label, BodyName};
+    use crate::replay::{BodyState, ReplayBuffer, SimState};
+
+    #[test]
+    fn by_name() {
+        let mut world = World::new();
+        world.spawn(BodyName("moon".into()));
+        let probe = world.spawn(BodyName("probe".into())).id();
+
+        assert_eq!(find_body(&world, "probe"), Some(probe));
+        assert_eq!(find_body(&world, "mars"), None);
+    }
+
+    #[test]
+    fn csv_column() {
+        let mut world = World::new();
+        let probe = world.spawn(BodyName("probe".into())).id();
+        let unnamed = world.spawn_empty().id();
+
+        let state = |x: f32| BodyState {
+            transform: Transform::from_xyz(x, 0.0, 0.0),
+            velocity: Vec3::X,
+            angular_velocity: Vec3::ZERO,
+        };
+
+        let mut replay = ReplayBuffer::new(10);
+        replay.push(SimState(vec![(probe, state(0.0)), (unnamed, state(5.0))]));
+        replay.push(SimState(vec![(probe, state(1.0))]));
+
+        let mut csv = Vec::new();
+        replay
+            .write_csv(&mut csv, |entity| {
+                label(entity, world.get::<BodyName>(entity))
+            })
+            .unwrap();
+
+        assert_eq!(
+            String::from_utf8(csv).unwrap(),
+            format!(
+                "step,body,x,y,z,vx,vy,vz\n\
+                 0,probe,0,0,0,1,0,0\n\
+                 0,{unnamed},5,0,0,1,0,0\n\
+                 1,probe,1,0,0,1,0,0\n"
+            )
+        );
+    }
+}
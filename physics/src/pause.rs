@@ -0,0 +1,124 @@
+use bevy::ecs::{
+    event::EventReader,
+    system::{Res, ResMut, Resource},
+};
+
+use crate::collisions::CollisionEvent;
+
+/// Stops every physics step while true, apart from the ones asked for with [PendingSteps]
+#[derive(Resource, Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct SimulationPaused(pub bool);
+
+/// Physics steps left to run while paused, for stepping through the simulation by hand
+#[derive(Resource, Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct PendingSteps(pub u32);
+
+/// Pauses the simulation in the step any collision happens, when true
+///
+/// Leaves the colliding objects right where they bounced for inspecting, step onwards with
+/// [PendingSteps].
+#[derive(Resource, Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct PauseOnCollision(pub bool);
+
+/// Run condition that is true while the simulation isn't paused or has steps pending
+pub fn is_running(paused: Res<SimulationPaused>, pending: Res<PendingSteps>) -> bool {
+    !paused.0 || pending.0 > 0
+}
+
+/// Counts down the [PendingSteps] once a step has run
+pub fn finish_step(paused: Res<SimulationPaused>, mut pending: ResMut<PendingSteps>) {
+    if paused.0 {
+        pending.0 = pending.0.saturating_sub(1);
+    }
+}
+
+/// Pauses the simulation on any [CollisionEvent], when [PauseOnCollision] is enabled
+pub fn pause_on_collision(
+    setting: Res<PauseOnCollision>,
+    mut collisions: EventReader<CollisionEvent>,
+    mut paused: ResMut<SimulationPaused>,
+    mut pending: ResMut<PendingSteps>,
+) {
+    // Read either way, so old collisions don't pause the moment it is enabled
+    if collisions.read().count() == 0 || !setting.0 {
+        return;
+    }
+
+    paused.0 = true;
+    pending.0 = 0;
+}
+
+#[cfg(test)]
+mod collision {
+    use bevy::{
+        app::{App, Update},
+        ecs::{entity::Entity, event::Events, schedule::IntoSystemConfigs},
+        math::Vec3,
+    };
+
+    use super::{
+        finish_step, is_running, pause_on_collision, PauseOnCollision, PendingSteps,
+        SimulationPaused,
+    };
+    use crate::collisions::CollisionEvent;
+
+    fn app(enabled: bool) -> App {
+        let mut app = App::new();
+        app.insert_resource(PauseOnCollision(enabled));
+        app.init_resource::<SimulationPaused>();
+        app.init_resource::<PendingSteps>();
+        app.add_event::<CollisionEvent>();
+        app.add_systems(Update, (pause_on_collision, finish_step).chain());
+
+        app
+    }
+
+    fn collide(app: &mut App) {
+        app.world_mut()
+            .resource_mut::<Events<CollisionEvent>>()
+            .send(CollisionEvent {
+                a: Entity::from_raw(0),
+                b: Entity::from_raw(1),
+                point: Vec3::ZERO,
+                normal: Vec3::X,
+                impulse: 1.0,
+            });
+    }
+
+    #[test]
+    fn pauses() {
+        let mut app = app(true);
+
+        app.update();
+        assert!(!app.world().resource::<SimulationPaused>().0);
+
+        collide(&mut app);
+        app.update();
+        assert!(app.world().resource::<SimulationPaused>().0);
+    }
+
+    #[test]
+    fn disabled() {
+        let mut app = app(false);
+
+        collide(&mut app);
+        app.update();
+        assert!(!app.world().resource::<SimulationPaused>().0);
+    }
+
+    #[test]
+    fn stepping() {
+        let mut app = App::new();
+        app.insert_resource(SimulationPaused(true));
+        app.insert_resource(PendingSteps(2));
+        app.add_systems(Update, finish_step.run_if(is_running));
+
+        for _ in 0..5 {
+            app.update();
+        }
+
+        // Ran the two steps and then stayed paused
+        assert_eq!(app.world().resource::<PendingSteps>().0, 0);
+        assert!(app.world().resource::<SimulationPaused>().0);
+    }
+}
@@ -0,0 +1,695 @@
+use bevy::{
+    color::Color,
+    ecs::{
+        component::Component,
+        query::With,
+        system::{Query, Res},
+    },
+    gizmos::gizmos::Gizmos,
+    math::{Dir3, Quat, Vec3},
+    transform::components::Transform,
+};
+
+use crate::{
+    components::{
+        drag::{DragTable, QuadraticDrag},
+        inertia::Inertia,
+        shape::Shape,
+        velocity::Velocity,
+        GravityOverride, Simulated,
+    },
+    resources::{Atmosphere, ForceToggles, GroundPlane, PhysicsSettings, UpAxis, Wind},
+    vector_arrows::GizmosEnabled,
+};
+
+/// Draws a marker where the object will hit the [GroundPlane], ignoring everything but gravity
+#[derive(Component, Debug)]
+pub struct PredictImpact;
+
+/// Draws the path of the object for the next few seconds, ignoring everything but gravity
+#[derive(Component, Debug)]
+pub struct ShowPrediction;
+
+/// Draws a faint marker where the object will be `lead_time` seconds from now, ignoring everything
+/// but gravity
+#[derive(Component, Debug, Clone, Copy, PartialEq)]
+pub struct Ghost {
+    /// How far ahead to look, in seconds
+    pub lead_time: f32,
+}
+
+/// Draws the path of the object for the next `horizon` seconds under gravity and its
+/// [QuadraticDrag], stepped forward rather than solved
+#[derive(Component, Debug, Clone, Copy, PartialEq)]
+pub struct ShowDragPrediction {
+    /// How far ahead to look, in seconds
+    pub horizon: f32,
+}
+
+/// Time between the points of a stepped [ShowDragPrediction] path, in seconds
+const DRAG_PREVIEW_STEP: f32 = 1.0 / 64.0;
+
+/// Time between the points of a drawn [ShowPrediction] path, in seconds
+const PREVIEW_STEP: f32 = 0.1;
+
+/// Number of segments in a drawn [ShowPrediction] path
+const PREVIEW_STEPS: usize = 200;
+
+/// Solves for where an object will hit a horizontal plane at `height` under constant `gravity`
+///
+/// Returns [None] if the object is already below the plane or never reaches it, like when it is
+/// moving upwards without any gravity pulling it back.
+///
+/// ```rust
+/// # use physics::prediction::impact_point;
+/// # use bevy::math::Vec3;
+/// // Falls 4.9 meters in one second while moving 2 m/s sideways
+/// let point = impact_point(Vec3::Y * 4.9, Vec3::X * 2.0, Vec3::Y * -9.8, 0.0);
+///
+/// assert!(point.unwrap().abs_diff_eq(Vec3::X * 2.0, 1e-5));
+/// ```
+#[must_use]
+pub fn impact_point(position: Vec3, velocity: Vec3, gravity: Vec3, height: f32) -> Option<Vec3> {
+    let above = position.y - height;
+
+    if above < 0.0 {
+        return None;
+    }
+
+    // Solve gravity.y / 2 * t^2 + velocity.y * t + above = 0 for the first t after now
+    let a = gravity.y / 2.0;
+    let b = velocity.y;
+
+    let t = if a == 0.0 {
+        Some(-above / b).filter(|t| t.is_finite() && *t >= 0.0)
+    } else {
+        let discriminant = b.powi(2) - 4.0 * a * above;
+
+        if discriminant < 0.0 {
+            return None;
+        }
+
+        let root = discriminant.sqrt();
+        [(-b - root) / (2.0 * a), (-b + root) / (2.0 * a)]
+            .into_iter()
+            .filter(|t| *t >= 0.0)
+            .reduce(f32::min)
+    }?;
+
+    Some(position + velocity * t + gravity * t.powi(2) / 2.0)
+}
+
+/// Samples the path of an object under a constant acceleration, `dt` seconds apart
+///
+/// Every point is taken from the closed form parabola rather than stepping, so the error doesn't
+/// grow along the path. The first of the `steps + 1` points is the current position.
+///
+/// ```rust
+/// # use physics::prediction::predict_trajectory;
+/// # use bevy::math::Vec3;
+/// let path = predict_trajectory(Vec3::ZERO, Vec3::X, Vec3::Y * -2.0, 0.5, 2);
+///
+/// assert_eq!(path, vec![Vec3::ZERO, Vec3::new(0.5, -0.25, 0.0), Vec3::new(1.0, -1.0, 0.0)]);
+/// ```
+#[must_use]
+pub fn predict_trajectory(
+    position: Vec3,
+    velocity: Vec3,
+    acceleration: Vec3,
+    dt: f32,
+    steps: usize,
+) -> Vec<Vec3> {
+    (0..=steps)
+        .map(|i| {
+            let t = i as f32 * dt;
+
+            position + velocity * t + acceleration * t.powi(2) / 2.0
+        })
+        .collect()
+}
+
+/// Steps the path of an object forward, `dt` seconds at a time
+///
+/// `acceleration` gets the acceleration at a position and velocity, so it can include forces
+/// like drag that change along the way. Uses semi-implicit Euler, the velocity is updated before
+/// the position every step. The first of the `steps + 1` points is the current position.
+///
+/// ```rust
+/// # use physics::prediction::predict_stepped;
+/// # use bevy::math::Vec3;
+/// // Slowing down by half its speed every second
+/// let path = predict_stepped(Vec3::ZERO, Vec3::X * 2.0, 1.0, 2, |_, vel| vel * -0.5);
+///
+/// assert_eq!(path, vec![Vec3::ZERO, Vec3::X, Vec3::X * 1.5]);
+/// ```
+#[must_use]
+pub fn predict_stepped(
+    position: Vec3,
+    velocity: Vec3,
+    dt: f32,
+    steps: usize,
+    acceleration: impl Fn(Vec3, Vec3) -> Vec3,
+) -> Vec<Vec3> {
+    let (mut pos, mut vel) = (position, velocity);
+    let mut path = Vec::with_capacity(steps + 1);
+    path.push(pos);
+
+    for _ in 0..steps {
+        vel += acceleration(pos, vel) * dt;
+        pos += vel * dt;
+        path.push(pos);
+    }
+
+    path
+}
+
+/// Gets where an object under a constant acceleration will be `lead_time` seconds from now
+///
+/// ```rust
+/// # use physics::prediction::ghost_position;
+/// # use bevy::math::Vec3;
+/// let ghost = ghost_position(Vec3::ZERO, Vec3::X, Vec3::Y * -2.0, 2.0);
+///
+/// assert_eq!(ghost, Vec3::new(2.0, -4.0, 0.0));
+/// ```
+#[must_use]
+pub fn ghost_position(position: Vec3, velocity: Vec3, acceleration: Vec3, lead_time: f32) -> Vec3 {
+    predict_trajectory(position, velocity, acceleration, lead_time, 1)[1]
+}
+
+/// Velocity of `b` as seen from `a`
+#[must_use]
+pub fn relative_velocity(a: &Velocity, b: &Velocity) -> Vec3 {
+    b.0 - a.0
+}
+
+/// How fast the distance between two objects is shrinking
+///
+/// Positive when they are approaching each other and negative when they are separating. Zero if
+/// they are at the same position.
+///
+/// ```rust
+/// # use physics::prediction::closing_speed;
+/// # use bevy::math::Vec3;
+/// // Chasing something 1 m/s slower
+/// let speed = closing_speed(Vec3::ZERO, Vec3::X * 3.0, Vec3::X * 10.0, Vec3::X * 2.0);
+///
+/// assert_eq!(speed, 1.0);
+/// ```
+#[must_use]
+pub fn closing_speed(a_pos: Vec3, a_vel: Vec3, b_pos: Vec3, b_vel: Vec3) -> f32 {
+    let direction = (b_pos - a_pos).normalize_or_zero();
+
+    -(b_vel - a_vel).dot(direction)
+}
+
+/// When and where two objects moving at constant velocities are nearest each other
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ClosestApproach {
+    /// Seconds from now, zero if they are already separating
+    pub time: f32,
+
+    /// Position of the first object at that time
+    pub a: Vec3,
+
+    /// Position of the second object at that time
+    pub b: Vec3,
+}
+
+impl ClosestApproach {
+    /// Gets the distance between the objects at the closest approach
+    #[must_use]
+    pub fn distance(&self) -> f32 {
+        self.a.distance(self.b)
+    }
+}
+
+/// Solves for the closest approach of two objects, assuming both keep their velocity
+///
+/// Never looks back in time, so objects that are separating or not moving relative to each other
+/// are nearest right now.
+///
+/// ```rust
+/// # use physics::prediction::closest_approach;
+/// # use bevy::math::Vec3;
+/// // Passing 2 m apart, nearest after 5 seconds
+/// let closest = closest_approach(Vec3::ZERO, Vec3::X, Vec3::new(10.0, 2.0, 0.0), Vec3::NEG_X);
+///
+/// assert_eq!(closest.time, 5.0);
+/// assert_eq!(closest.distance(), 2.0);
+/// ```
+#[must_use]
+pub fn closest_approach(a_pos: Vec3, a_vel: Vec3, b_pos: Vec3, b_vel: Vec3) -> ClosestApproach {
+    let (offset, rel_vel) = (b_pos - a_pos, b_vel - a_vel);
+    let speed2 = rel_vel.length_squared();
+
+    let time = if speed2 > 0.0 {
+        (-offset.dot(rel_vel) / speed2).max(0.0)
+    } else {
+        0.0
+    };
+
+    ClosestApproach {
+        time,
+        a: a_pos + a_vel * time,
+        b: b_pos + b_vel * time,
+    }
+}
+
+/// Draws the predicted impact point of every object with [PredictImpact]
+#[allow(clippy::type_complexity)]
+pub fn predict_impact(
+    enabled: Res<GizmosEnabled>,
+    settings: Res<PhysicsSettings>,
+    up: Res<UpAxis>,
+    ground: Res<GroundPlane>,
+    query: Query<
+        (&Transform, &Velocity, Option<&GravityOverride>),
+        (With<PredictImpact>, With<Simulated>),
+    >,
+    mut gizmos: Gizmos,
+) {
+    if !enabled.0 {
+        return;
+    }
+
+    let gravity = settings.gravity_for(*up);
+
+    for (trans, vel, over) in query.iter() {
+        let g = gravity.for_body(over);
+        let Some(point) = impact_point(trans.translation, vel.0, g, ground.height) else {
+            continue;
+        };
+
+        gizmos.circle(point, Dir3::Y, 1.0, Color::srgb(0.65, 0.65, 0.0));
+    }
+}
+
+/// Draws the predicted path of every object with [ShowPrediction], until it passes the
+/// [GroundPlane]
+#[allow(clippy::type_complexity)]
+pub fn show_prediction(
+    enabled: Res<GizmosEnabled>,
+    settings: Res<PhysicsSettings>,
+    up: Res<UpAxis>,
+    ground: Res<GroundPlane>,
+    query: Query<
+        (&Transform, &Velocity, Option<&GravityOverride>),
+        (With<ShowPrediction>, With<Simulated>),
+    >,
+    mut gizmos: Gizmos,
+) {
+    if !enabled.0 {
+        return;
+    }
+
+    let gravity = settings.gravity_for(*up);
+
+    for (trans, vel, over) in query.iter() {
+        let path = predict_trajectory(
+            trans.translation,
+            vel.0,
+            gravity.for_body(over),
+            PREVIEW_STEP,
+            PREVIEW_STEPS,
+        );
+
+        gizmos.linestrip(
+            path.into_iter().take_while(|p| p.y >= ground.height),
+            Color::srgb(0.65, 0.65, 0.0),
+        );
+    }
+}
+
+/// Draws the stepped path of every object with [ShowDragPrediction], until it passes the
+/// [GroundPlane]
+///
+/// Uses the same drag as [drag](crate::forces::drag), in the air of the [Atmosphere] along the
+/// way, keeping the current orientation of the object. Nothing on the object is changed.
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
+pub fn show_drag_prediction(
+    enabled: Res<GizmosEnabled>,
+    toggles: Res<ForceToggles>,
+    settings: Res<PhysicsSettings>,
+    ground: Res<GroundPlane>,
+    wind: Res<Wind>,
+    atmosphere: Res<Atmosphere>,
+    up: Option<Res<UpAxis>>,
+    query: Query<
+        (
+            &Transform,
+            &Velocity,
+            &Inertia,
+            &ShowDragPrediction,
+            Option<&QuadraticDrag>,
+            Option<&DragTable>,
+            Option<&Shape>,
+            Option<&GravityOverride>,
+        ),
+        With<Simulated>,
+    >,
+    mut gizmos: Gizmos,
+) {
+    if !enabled.0 {
+        return;
+    }
+
+    let up = up.map_or(UpAxis::Y, |up| *up);
+    let gravity = settings.gravity_for(up);
+
+    for (trans, vel, inertia, preview, drag, table, shape, over) in query.iter() {
+        let g = if toggles.gravity {
+            gravity.for_body(over)
+        } else {
+            Vec3::ZERO
+        };
+        let drag = drag.filter(|_| toggles.drag);
+
+        let acceleration = |pos: Vec3, vel: Vec3| {
+            let Some(drag) = drag else {
+                return g;
+            };
+
+            let airflow = vel - wind.0;
+            let density = atmosphere.density(up.height(pos));
+            let force = drag.with_table(table, airflow.length()).get_force(
+                trans.rotation,
+                airflow,
+                density,
+                shape,
+            );
+
+            g + inertia.get_acceleration(force)
+        };
+
+        let steps = (preview.horizon / DRAG_PREVIEW_STEP).ceil().max(0.0) as usize;
+        let path = predict_stepped(
+            trans.translation,
+            vel.0,
+            DRAG_PREVIEW_STEP,
+            steps,
+            acceleration,
+        );
+
+        gizmos.linestrip(
+            path.into_iter().take_while(|p| p.y >= ground.height),
+            Color::srgba(0.0, 0.65, 0.65, 0.5),
+        );
+    }
+}
+
+/// Draws the future position of every object with a [Ghost]
+#[allow(clippy::type_complexity)]
+pub fn show_ghost(
+    enabled: Res<GizmosEnabled>,
+    settings: Res<PhysicsSettings>,
+    up: Res<UpAxis>,
+    query: Query<(&Transform, &Velocity, &Ghost, Option<&GravityOverride>), With<Simulated>>,
+    mut gizmos: Gizmos,
+) {
+    if !enabled.0 {
+        return;
+    }
+
+    let gravity = settings.gravity_for(*up);
+
+    for (trans, vel, ghost, over) in query.iter() {
+        let point = ghost_position(
+            trans.translation,
+            vel.0,
+            gravity.for_body(over),
+            ghost.lead_time,
+        );
+
+        gizmos.sphere(
+            point,
+            Quat::IDENTITY,
+            0.5,
+            Color::srgba(0.65, 0.65, 0.0, 0.3),
+        );
+    }
+}
+
+#[cfg(test)]
+mod impact {
+    use bevy::math::Vec3;
+    use float_cmp::assert_approx_eq;
+
+    use super::impact_point;
+
+    const G: Vec3 = Vec3::new(0.0, -10.0, 0.0);
+
+    #[test]
+    fn thrown_up() {
+        // Back at the start after 2 seconds, then the last 15 meters down take 1 second
+        let point = impact_point(Vec3::Y * 15.0, Vec3::new(3.0, 10.0, 1.0), G, 0.0).unwrap();
+
+        assert_approx_eq!(&[f32], &point.to_array(), &[9.0, 0.0, 3.0], epsilon = 1e-4);
+    }
+
+    #[test]
+    fn raised_ground() {
+        let point = impact_point(Vec3::Y * 25.0, Vec3::X, G, 5.0).unwrap();
+
+        assert_approx_eq!(&[f32], &point.to_array(), &[2.0, 5.0, 0.0], epsilon = 1e-5);
+    }
+
+    #[test]
+    fn no_gravity() {
+        let down = impact_point(Vec3::Y * 10.0, Vec3::new(1.0, -2.0, 0.0), Vec3::ZERO, 0.0);
+        assert_approx_eq!(&[f32], &down.unwrap().to_array(), &[5.0, 0.0, 0.0]);
+
+        // Moving up or sideways without gravity never comes down
+        assert_eq!(impact_point(Vec3::Y, Vec3::Y, Vec3::ZERO, 0.0), None);
+        assert_eq!(impact_point(Vec3::Y, Vec3::X, Vec3::ZERO, 0.0), None);
+    }
+
+    #[test]
+    fn never_hits() {
+        // Gravity pointing up while already above the ground
+        assert_eq!(impact_point(Vec3::Y, Vec3::Y, -G, 0.0), None);
+
+        // Already below the ground
+        assert_eq!(impact_point(Vec3::NEG_Y, Vec3::ZERO, G, 0.0), None);
+    }
+}
+
+#[cfg(test)]
+mod trajectory {
+    use bevy::math::Vec3;
+    use float_cmp::assert_approx_eq;
+
+    use super::predict_trajectory;
+
+    #[test]
+    fn parabola() {
+        let (pos, vel, g) = (Vec3::new(1.0, 20.0, -2.0), Vec3::new(4.0, 12.0, 1.0), -9.82);
+        let path = predict_trajectory(pos, vel, Vec3::Y * g, 0.05, 80);
+
+        assert_eq!(path.len(), 81);
+        assert_eq!(path[0], pos);
+
+        for (i, point) in path.iter().enumerate() {
+            let t = i as f32 * 0.05;
+            let expected = [
+                pos.x + vel.x * t,
+                pos.y + vel.y * t + g * t * t / 2.0,
+                pos.z + vel.z * t,
+            ];
+
+            assert_approx_eq!(&[f32], &point.to_array(), &expected, epsilon = 1e-4);
+        }
+
+        // Peaks when the vertical speed runs out
+        let peak = path.iter().map(|p| p.y).fold(f32::MIN, f32::max);
+        assert_approx_eq!(
+            f32,
+            peak,
+            pos.y + vel.y.powi(2) / (2.0 * -g),
+            epsilon = 0.05
+        );
+    }
+
+    #[test]
+    fn no_steps() {
+        assert_eq!(
+            predict_trajectory(Vec3::ONE, Vec3::X, Vec3::NEG_Y, 0.1, 0),
+            vec![Vec3::ONE]
+        );
+    }
+}
+
+#[cfg(test)]
+mod stepped {
+    use bevy::math::{Quat, Vec3};
+    use float_cmp::assert_approx_eq;
+
+    use super::{predict_stepped, predict_trajectory};
+    use crate::components::{drag::QuadraticDrag, inertia::Inertia};
+
+    const G: Vec3 = Vec3::new(0.0, -9.82, 0.0);
+
+    #[test]
+    fn without_drag() {
+        let (pos, vel) = (Vec3::Y * 2.0, Vec3::new(10.0, 10.0, 0.0));
+        let stepped = predict_stepped(pos, vel, 1e-3, 2000, |_, _| G);
+        let exact = predict_trajectory(pos, vel, G, 2.0, 1);
+
+        // Within the first order error of the stepping
+        assert_approx_eq!(
+            &[f32],
+            &stepped[2000].to_array(),
+            &exact[1].to_array(),
+            epsilon = 0.05
+        );
+    }
+
+    #[test]
+    fn falls_short() {
+        let (pos, vel) = (Vec3::ZERO, Vec3::new(20.0, 20.0, 0.0));
+        let inertia = Inertia::sphere(0.1, 0.5);
+        let drag = QuadraticDrag {
+            cd: 0.47,
+            area: Some(0.03),
+        };
+
+        let with_drag = predict_stepped(pos, vel, 1.0 / 64.0, 256, |_, vel| {
+            G + inertia.get_acceleration(drag.get_force(Quat::IDENTITY, vel, 1.2, None))
+        });
+        let parabola = predict_trajectory(pos, vel, G, 1.0 / 64.0, 256);
+
+        // Lower and shorter at every point after the start
+        for (dragged, free) in with_drag.iter().zip(&parabola).skip(1) {
+            assert!(dragged.x < free.x, "{dragged} isn't short of {free}");
+            assert!(dragged.y < free.y, "{dragged} isn't below {free}");
+        }
+
+        let peak = |path: &[Vec3]| path.iter().map(|p| p.y).fold(f32::MIN, f32::max);
+        assert!(peak(&with_drag) < peak(&parabola) - 1.0);
+    }
+}
+
+#[cfg(test)]
+mod ghost {
+    use bevy::math::Vec3;
+    use float_cmp::assert_approx_eq;
+
+    use super::ghost_position;
+
+    #[test]
+    fn parabola() {
+        let (pos, vel, g) = (Vec3::new(-3.0, 2.0, 5.0), Vec3::new(1.5, 8.0, -2.0), -9.82);
+
+        for lead in [0.0, 0.25, 2.0, 3.7] {
+            let ghost = ghost_position(pos, vel, Vec3::Y * g, lead);
+            let expected = [
+                pos.x + vel.x * lead,
+                pos.y + vel.y * lead + g * lead * lead / 2.0,
+                pos.z + vel.z * lead,
+            ];
+
+            assert_approx_eq!(&[f32], &ghost.to_array(), &expected, epsilon = 1e-4);
+        }
+    }
+}
+
+#[cfg(test)]
+mod closest {
+    use bevy::math::Vec3;
+    use float_cmp::assert_approx_eq;
+
+    use super::closest_approach;
+
+    #[test]
+    fn crossing() {
+        // One heading along x and one along y, both towards the origin but arriving at
+        // different times
+        let closest = closest_approach(
+            Vec3::new(-10.0, 0.0, 0.0),
+            Vec3::X,
+            Vec3::new(0.0, -5.0, 0.0),
+            Vec3::Y,
+        );
+
+        assert_approx_eq!(f32, closest.time, 7.5);
+        assert_approx_eq!(&[f32], &closest.a.to_array(), &[-2.5, 0.0, 0.0]);
+        assert_approx_eq!(&[f32], &closest.b.to_array(), &[0.0, 2.5, 0.0]);
+        assert_approx_eq!(f32, closest.distance(), 2.5 * 2f32.sqrt());
+    }
+
+    #[test]
+    fn collision_course() {
+        let closest = closest_approach(Vec3::ZERO, Vec3::Z * 2.0, Vec3::Z * 12.0, Vec3::Z * -1.0);
+
+        assert_approx_eq!(f32, closest.time, 4.0);
+        assert_approx_eq!(f32, closest.distance(), 0.0);
+    }
+
+    #[test]
+    fn separating() {
+        let closest = closest_approach(Vec3::ZERO, Vec3::NEG_X, Vec3::X, Vec3::X);
+
+        assert_eq!(closest.time, 0.0);
+        assert_eq!((closest.a, closest.b), (Vec3::ZERO, Vec3::X));
+    }
+
+    #[test]
+    fn same_velocity() {
+        let vel = Vec3::new(1.0, 2.0, 3.0);
+        let closest = closest_approach(Vec3::ZERO, vel, Vec3::Y, vel);
+
+        assert_eq!(closest.time, 0.0);
+        assert_eq!(closest.distance(), 1.0);
+    }
+}
+
+#[cfg(test)]
+mod relative {
+    use bevy::math::Vec3;
+    use float_cmp::assert_approx_eq;
+
+    use super::{closing_speed, relative_velocity};
+    use crate::components::velocity::Velocity;
+
+    #[test]
+    fn velocity() {
+        let rel = relative_velocity(&Velocity(Vec3::X), &Velocity(Vec3::new(-1.0, 2.0, 0.0)));
+
+        assert_eq!(rel, Vec3::new(-2.0, 2.0, 0.0));
+    }
+
+    #[test]
+    fn head_on() {
+        let speed = closing_speed(Vec3::ZERO, Vec3::X * 2.0, Vec3::X * 10.0, Vec3::X * -3.0);
+
+        assert_approx_eq!(f32, speed, 5.0);
+
+        // Swapped around it is the same
+        let speed = closing_speed(Vec3::X * 10.0, Vec3::X * -3.0, Vec3::ZERO, Vec3::X * 2.0);
+
+        assert_approx_eq!(f32, speed, 5.0);
+    }
+
+    #[test]
+    fn separating() {
+        let speed = closing_speed(Vec3::ZERO, Vec3::NEG_Y, Vec3::Y * 4.0, Vec3::Y);
+
+        assert_approx_eq!(f32, speed, -2.0);
+    }
+
+    #[test]
+    fn parallel() {
+        let vel = Vec3::new(1.0, 0.0, 3.0);
+
+        assert_eq!(closing_speed(Vec3::ZERO, vel, Vec3::X * 5.0, vel), 0.0);
+        assert_eq!(closing_speed(Vec3::ZERO, vel, Vec3::Y * 5.0, vel), 0.0);
+    }
+
+    #[test]
+    fn same_position() {
+        assert_eq!(
+            closing_speed(Vec3::ONE, Vec3::X, Vec3::ONE, Vec3::NEG_X),
+            0.0
+        );
+    }
+}
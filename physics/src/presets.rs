@@ -0,0 +1,313 @@
+use bevy::{
+    ecs::{entity::Entity, system::Commands},
+    math::{Quat, Vec3},
+    transform::components::Transform,
+};
+
+use crate::{
+    analysis::circular_speed,
+    components::{
+        acceleration::Accelerator,
+        inertia::Inertia,
+        spring::Spring,
+        velocity::{AngularVelocity, Velocity},
+        GravityOverride, Orbiting, SimulationBundle,
+    },
+};
+
+/// Mass-spring-damper along the x axis, released from rest
+///
+/// The [Spring] is anchored at the origin, so the x cordinate of the object is the displacement.
+/// [DampedOscillator::displacement] gives the analytic solution to validate the simulation against.
+/// The object has a zero [GravityOverride], gravity would otherwise pull the equilibrium away from
+/// the anchor.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DampedOscillator {
+    /// Mass in kilograms
+    pub mass: f32,
+
+    /// Spring constant in newtons per meter
+    pub stiffness: f32,
+
+    /// Damping coefficient in newton seconds per meter
+    pub damping: f32,
+
+    /// Displacement along x the object starts at, in meters
+    pub initial_displacement: f32,
+}
+
+impl DampedOscillator {
+    /// Spawns the object with its [Spring]
+    pub fn spawn(&self, commands: &mut Commands) -> Entity {
+        commands.spawn(self.bundle()).id()
+    }
+
+    /// Gets the components of the object
+    #[must_use]
+    pub fn bundle(&self) -> (SimulationBundle, Spring, GravityOverride) {
+        let mut body = SimulationBundle::new(
+            Velocity::ZERO,
+            Accelerator::ZERO,
+            AngularVelocity::ZERO,
+            Inertia::sphere(0.1, self.mass),
+        );
+        body.spatial.transform = Transform::from_translation(Vec3::X * self.initial_displacement);
+
+        (
+            body,
+            Spring {
+                anchor: Vec3::ZERO,
+                stiffness: self.stiffness,
+                damping: self.damping,
+            },
+            GravityOverride(Vec3::ZERO),
+        )
+    }
+
+    /// Undamped angular frequency in radians per second
+    #[must_use]
+    pub fn natural_frequency(&self) -> f32 {
+        (self.stiffness / self.mass).sqrt()
+    }
+
+    /// Damping ratio, below 1 is underdamped, 1 is critically damped and above 1 is overdamped
+    #[must_use]
+    pub fn damping_ratio(&self) -> f32 {
+        self.damping / (2.0 * (self.stiffness * self.mass).sqrt())
+    }
+
+    /// Gets the analytic displacement `t` seconds after being released
+    #[must_use]
+    pub fn displacement(&self, t: f32) -> f32 {
+        let (x0, w0, zeta) = (
+            self.initial_displacement,
+            self.natural_frequency(),
+            self.damping_ratio(),
+        );
+
+        if (zeta - 1.0).abs() < 1e-4 {
+            return x0 * (1.0 + w0 * t) * (-w0 * t).exp();
+        }
+
+        if zeta < 1.0 {
+            let wd = w0 * (1.0 - zeta * zeta).sqrt();
+
+            return x0
+                * (-zeta * w0 * t).exp()
+                * ((wd * t).cos() + zeta * w0 / wd * (wd * t).sin());
+        }
+
+        // Two decaying exponentials, weighted to start at x0 with no velocity
+        let root = (zeta * zeta - 1.0).sqrt();
+        let (r1, r2) = (-w0 * (zeta - root), -w0 * (zeta + root));
+
+        x0 * (r2 * (r1 * t).exp() - r1 * (r2 * t).exp()) / (r2 - r1)
+    }
+}
+
+/// Circular orbit around a point, starting on its x axis
+///
+/// With no inclination the orbit lies in the horizontal plane, moving towards +z at the start.
+/// The plane is tilted around the x axis by the inclination.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CircularOrbit {
+    /// Distance from the center in meters
+    pub radius: f32,
+
+    /// Tilt of the orbit from the horizontal plane in radians
+    pub inclination: f32,
+}
+
+impl CircularOrbit {
+    /// Gets the starting position and velocity around `center`, pulled in by a body with the
+    /// gravitational parameter `mu`
+    #[must_use]
+    pub fn initial_state(&self, center: Vec3, mu: f32) -> (Vec3, Velocity) {
+        let direction = Quat::from_rotation_x(-self.inclination) * Vec3::Z;
+
+        (
+            center + Vec3::X * self.radius,
+            Velocity(direction * circular_speed(mu, self.radius)),
+        )
+    }
+
+    /// Gets the components of an object in the orbit, marked as [Orbiting]
+    ///
+    /// The [CentralBody](crate::resources::CentralBody) has to be at `center` with the same
+    /// `mu` for the orbit to stay circular.
+    #[must_use]
+    pub fn bundle(&self, center: Vec3, mu: f32, inertia: Inertia) -> (SimulationBundle, Orbiting) {
+        let (position, vel) = self.initial_state(center, mu);
+
+        let mut body =
+            SimulationBundle::new(vel, Accelerator::ZERO, AngularVelocity::ZERO, inertia);
+        body.spatial.transform = Transform::from_translation(position);
+
+        (body, Orbiting)
+    }
+}
+
+#[cfg(test)]
+mod circular_orbit {
+    use std::time::Duration;
+
+    use bevy::{
+        app::{App, Update},
+        ecs::schedule::IntoSystemConfigs,
+        math::Vec3,
+        time::Time,
+        transform::components::Transform,
+    };
+    use float_cmp::assert_approx_eq;
+
+    use super::CircularOrbit;
+    use crate::{
+        components::inertia::Inertia,
+        forces::central_gravity,
+        resources::{CentralBody, ForceToggles, PhysicsSettings},
+        update_simulated,
+    };
+
+    #[test]
+    fn inclined() {
+        let orbit = CircularOrbit {
+            radius: 10.0,
+            inclination: 0.5,
+        };
+        let (pos, vel) = orbit.initial_state(Vec3::ZERO, 100.0);
+
+        assert_eq!(pos, Vec3::X * 10.0);
+        assert_approx_eq!(f32, vel.0.length(), 10f32.sqrt());
+        assert_approx_eq!(f32, vel.0.dot(pos), 0.0);
+        assert_approx_eq!(f32, vel.0.y.atan2(vel.0.z), 0.5);
+    }
+
+    #[test]
+    fn stays_circular() {
+        let (center, mu) = (Vec3::new(5.0, 20.0, -3.0), 100.0);
+        let orbit = CircularOrbit {
+            radius: 10.0,
+            inclination: 1.0,
+        };
+
+        let mut app = App::new();
+        let mut time = Time::<()>::default();
+        time.advance_by(Duration::from_millis(1));
+
+        app.insert_resource(time);
+        app.init_resource::<PhysicsSettings>();
+        app.init_resource::<ForceToggles>();
+        app.insert_resource(CentralBody {
+            mu,
+            position: center,
+        });
+        app.add_systems(Update, (central_gravity, update_simulated).chain());
+
+        let body = app
+            .world_mut()
+            .spawn(orbit.bundle(center, mu, Inertia::sphere(0.1, 1.0)))
+            .id();
+
+        // About one orbit, 2 pi sqrt(r^3 / mu) is just under 20 seconds
+        for _ in 0..20_000 {
+            app.update();
+
+            let pos = app.world().get::<Transform>(body).unwrap().translation;
+            let r = pos.distance(center);
+            assert!((r - orbit.radius).abs() < 0.1, "radius drifted to {r}");
+        }
+    }
+}
+
+#[cfg(test)]
+mod oscillator {
+    use std::time::Duration;
+
+    use bevy::{
+        app::{App, Update},
+        ecs::schedule::IntoSystemConfigs,
+        time::Time,
+        transform::components::Transform,
+    };
+
+    use super::DampedOscillator;
+    use crate::{
+        forces::{gravity, springs},
+        resources::{ForceToggles, PhysicsSettings, UpAxis},
+        update_simulated,
+    };
+
+    /// Simulates the oscillator for two seconds under the default gravity and compares it with the
+    /// analytic solution
+    fn matches_analytic(oscillator: DampedOscillator) {
+        let mut app = App::new();
+        let mut time = Time::<()>::default();
+        time.advance_by(Duration::from_millis(1));
+
+        app.insert_resource(time);
+        app.init_resource::<PhysicsSettings>();
+        app.init_resource::<ForceToggles>();
+        app.init_resource::<UpAxis>();
+        app.add_systems(Update, (gravity, springs, update_simulated).chain());
+
+        let body = app.world_mut().spawn(oscillator.bundle()).id();
+
+        for step in 1..=2000 {
+            app.update();
+
+            if step % 100 != 0 {
+                continue;
+            }
+
+            let t = step as f32 * 1e-3;
+            let pos = app.world().get::<Transform>(body).unwrap().translation;
+            let (x, expected) = (pos.x, oscillator.displacement(t));
+
+            // Gravity doesn't sag it below the anchor
+            assert_eq!(pos.y, 0.0);
+            assert!(
+                (x - expected).abs() < 0.01 * oscillator.initial_displacement,
+                "at {t}s simulated {x} but expected {expected}"
+            );
+        }
+    }
+
+    #[test]
+    fn underdamped() {
+        let oscillator = DampedOscillator {
+            mass: 2.0,
+            stiffness: 50.0,
+            damping: 2.0,
+            initial_displacement: 1.0,
+        };
+        assert!(oscillator.damping_ratio() < 1.0);
+
+        matches_analytic(oscillator);
+    }
+
+    #[test]
+    fn critically_damped() {
+        let oscillator = DampedOscillator {
+            mass: 2.0,
+            stiffness: 50.0,
+            damping: 20.0,
+            initial_displacement: 1.0,
+        };
+        assert_eq!(oscillator.damping_ratio(), 1.0);
+
+        matches_analytic(oscillator);
+    }
+
+    #[test]
+    fn overdamped() {
+        let oscillator = DampedOscillator {
+            mass: 2.0,
+            stiffness: 50.0,
+            damping: 60.0,
+            initial_displacement: 1.0,
+        };
+        assert!(oscillator.damping_ratio() > 1.0);
+
+        matches_analytic(oscillator);
+    }
+}
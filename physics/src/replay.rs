@@ -0,0 +1,312 @@
+use std::{collections::VecDeque, io};
+
+use bevy::{
+    ecs::{
+        entity::Entity,
+        event::{Event, EventReader},
+        query::With,
+        system::{Query, Res, ResMut, Resource},
+    },
+    math::Vec3,
+    transform::components::Transform,
+};
+
+use crate::{
+    components::{
+        velocity::{AngularVelocity, Velocity},
+        Simulated,
+    },
+    interpolation::StepTransforms,
+};
+
+/// The state of a single object inside a [SimState]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BodyState {
+    pub transform: Transform,
+    pub velocity: Vec3,
+    pub angular_velocity: Vec3,
+}
+
+/// A snapshot of every simulated object at the end of a frame
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SimState(pub Vec<(Entity, BodyState)>);
+
+/// Moves the replay cursor one recorded frame
+#[derive(Event, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReplayStep {
+    Back,
+    Forward,
+}
+
+/// Ring buffer of the latest [SimState]s
+///
+/// While scrubbing, the live simulation is frozen and the objects are restored from the recorded
+/// snapshots instead of being integrated, since the integrator can't be run backwards. Stepping
+/// forward past the newest snapshot resumes the live simulation.
+#[derive(Resource, Debug)]
+pub struct ReplayBuffer {
+    snapshots: VecDeque<SimState>,
+    depth: usize,
+
+    /// How many frames back from the newest snapshot is currently shown, [None] when live
+    cursor: Option<usize>,
+}
+
+impl ReplayBuffer {
+    /// Create a new [ReplayBuffer] that keeps at most `depth` snapshots
+    #[must_use]
+    pub fn new(depth: usize) -> Self {
+        Self {
+            snapshots: VecDeque::with_capacity(depth),
+            depth,
+            cursor: None,
+        }
+    }
+
+    /// Checks if the simulation is currently being scrubbed
+    #[must_use]
+    pub fn is_scrubbing(&self) -> bool {
+        self.cursor.is_some()
+    }
+
+    /// Stores a snapshot, dropping the oldest one if the buffer is full
+    pub fn push(&mut self, state: SimState) {
+        if self.depth == 0 {
+            return;
+        }
+
+        if self.snapshots.len() == self.depth {
+            self.snapshots.pop_front();
+        }
+
+        self.snapshots.push_back(state);
+    }
+
+    /// Moves the cursor and returns the snapshot that should be shown
+    ///
+    /// Returns [None] if there is nothing to move to.
+    pub fn step(&mut self, step: ReplayStep) -> Option<&SimState> {
+        let newest = self.snapshots.len().checked_sub(1)?;
+        let current = self.cursor.unwrap_or(0);
+
+        let next = match step {
+            ReplayStep::Back => (current + 1).min(newest),
+            ReplayStep::Forward => current.checked_sub(1)?,
+        };
+
+        // Reaching the newest snapshot again means the live state has been fully restored
+        self.cursor = (next != 0).then_some(next);
+        self.snapshots.get(newest - next)
+    }
+
+    /// Writes the trajectory of every object as CSV, one row per object and snapshot
+    ///
+    /// Steps count up from the oldest snapshot. `label` names the object in the `body` column,
+    /// like [names::label](crate::names::label).
+    pub fn write_csv(
+        &self,
+        mut writer: impl io::Write,
+        label: impl Fn(Entity) -> String,
+    ) -> io::Result<()> {
+        writeln!(writer, "step,body,x,y,z,vx,vy,vz")?;
+
+        for (step, state) in self.snapshots.iter().enumerate() {
+            for (entity, body) in state.0.iter() {
+                let (pos, vel) = (body.transform.translation, body.velocity);
+
+                writeln!(
+                    writer,
+                    "{step},{},{},{},{},{},{},{}",
+                    label(*entity),
+                    pos.x,
+                    pos.y,
+                    pos.z,
+                    vel.x,
+                    vel.y,
+                    vel.z
+                )?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Default for ReplayBuffer {
+    fn default() -> Self {
+        Self::new(600)
+    }
+}
+
+/// Run condition that is true while the simulation isn't being scrubbed
+pub fn is_live(replay: Res<ReplayBuffer>) -> bool {
+    !replay.is_scrubbing()
+}
+
+/// Records a snapshot of every simulated object
+pub fn record(
+    mut replay: ResMut<ReplayBuffer>,
+    query: Query<(Entity, &Transform, &Velocity, &AngularVelocity), With<Simulated>>,
+) {
+    let state = query
+        .iter()
+        .map(|(entity, trans, vel, angvel)| {
+            let body = BodyState {
+                transform: *trans,
+                velocity: vel.0,
+                angular_velocity: angvel.0,
+            };
+
+            (entity, body)
+        })
+        .collect();
+
+    replay.push(SimState(state));
+}
+
+/// Moves through the recorded snapshots and restores them onto the objects
+#[allow(clippy::type_complexity)]
+pub fn scrub(
+    mut steps: EventReader<ReplayStep>,
+    mut replay: ResMut<ReplayBuffer>,
+    mut query: Query<
+        (
+            &mut Transform,
+            &mut Velocity,
+            &mut AngularVelocity,
+            Option<&mut StepTransforms>,
+        ),
+        With<Simulated>,
+    >,
+) {
+    for step in steps.read() {
+        let Some(state) = replay.step(*step) else {
+            continue;
+        };
+
+        for (entity, body) in state.0.iter() {
+            // Objects that have been despawned since can't be restored
+            let Ok((mut trans, mut vel, mut angvel, steps)) = query.get_mut(*entity) else {
+                continue;
+            };
+
+            // Don't let the interpolation blend between the live and the restored transform
+            if let Some(mut steps) = steps {
+                *steps = StepTransforms::new(body.transform);
+            }
+
+            *trans = body.transform;
+            vel.0 = body.velocity;
+            angvel.0 = body.angular_velocity;
+        }
+    }
+}
+
+#[cfg(test)]
+mod scrubbing {
+    use std::time::Duration;
+
+    use bevy::{
+        app::{App, Update},
+        ecs::{event::Events, schedule::IntoSystemConfigs},
+        math::Vec3,
+        time::Time,
+        transform::components::Transform,
+    };
+
+    use super::{is_live, record, scrub, ReplayBuffer, ReplayStep};
+    use crate::{
+        components::{
+            acceleration::Accelerator, inertia::Inertia, velocity::AngularVelocity,
+            velocity::Velocity, SimulationBundle,
+        },
+        resources::PhysicsSettings,
+        update_simulated,
+    };
+
+    fn app() -> App {
+        let mut app = App::new();
+        let mut time = Time::<()>::default();
+        time.advance_by(Duration::from_millis(100));
+
+        app.insert_resource(time);
+        app.insert_resource(PhysicsSettings {
+            max_delta: None,
+            ..Default::default()
+        });
+        app.insert_resource(ReplayBuffer::new(10));
+        app.add_event::<ReplayStep>();
+        app.add_systems(
+            Update,
+            (scrub, (update_simulated, record).chain().run_if(is_live)).chain(),
+        );
+
+        app
+    }
+
+    fn position(app: &mut App) -> Vec3 {
+        let mut query = app.world_mut().query::<&Transform>();
+        query.single(app.world()).translation
+    }
+
+    fn send(app: &mut App, step: ReplayStep, times: usize) {
+        let mut events = app.world_mut().resource_mut::<Events<ReplayStep>>();
+        for _ in 0..times {
+            events.send(step);
+        }
+    }
+
+    #[test]
+    fn step_back() {
+        let mut app = app();
+        app.world_mut().spawn(SimulationBundle::new(
+            Velocity(Vec3::X),
+            Accelerator(Vec3::Y),
+            AngularVelocity::ZERO,
+            Inertia::cylinder_y(1.0, 1.0, 1.0),
+        ));
+
+        let mut history = vec![];
+        for _ in 0..6 {
+            app.update();
+            history.push(position(&mut app));
+        }
+
+        send(&mut app, ReplayStep::Back, 3);
+        app.update();
+
+        assert!(app.world().resource::<ReplayBuffer>().is_scrubbing());
+        assert_eq!(position(&mut app), history[history.len() - 1 - 3]);
+
+        // The live simulation is frozen while scrubbing
+        app.update();
+        assert_eq!(position(&mut app), history[history.len() - 1 - 3]);
+
+        send(&mut app, ReplayStep::Forward, 3);
+        app.update();
+
+        assert!(!app.world().resource::<ReplayBuffer>().is_scrubbing());
+    }
+
+    #[test]
+    fn ring_buffer() {
+        let mut replay = ReplayBuffer::new(3);
+        for _ in 0..5 {
+            replay.push(Default::default());
+        }
+
+        assert_eq!(replay.snapshots.len(), 3);
+
+        // Can only go back to the oldest snapshot kept
+        assert!(replay.step(ReplayStep::Back).is_some());
+        assert!(replay.step(ReplayStep::Back).is_some());
+        assert_eq!(replay.cursor, Some(2));
+        replay.step(ReplayStep::Back);
+        assert_eq!(replay.cursor, Some(2));
+
+        // Nothing to move forward to while live
+        let mut live = ReplayBuffer::new(3);
+        live.push(Default::default());
+        assert!(live.step(ReplayStep::Forward).is_none());
+    }
+}
@@ -0,0 +1,304 @@
+use bevy::{
+    ecs::{
+        component::Component,
+        entity::Entity,
+        event::{Event, EventReader},
+        query::{With, Without},
+        system::{Commands, Query, ResMut, Resource},
+    },
+    math::Vec3,
+    transform::components::Transform,
+};
+
+use crate::{
+    components::{
+        force::ForceAccumulator,
+        velocity::{AngularVelocity, Velocity},
+        Simulated,
+    },
+    interpolation::StepTransforms,
+    resources::SimClock,
+    rng::SimRng,
+};
+
+/// The state an object had when it was first simulated, restored by [ResetSimulation]
+#[derive(Component, Debug, Clone, Copy, PartialEq)]
+pub struct InitialState {
+    pub transform: Transform,
+    pub velocity: Vec3,
+    pub angular_velocity: Vec3,
+}
+
+/// Puts every simulated object back at its [InitialState]
+#[derive(Event, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ResetSimulation;
+
+/// Nudges the velocity of an object by [Perturbation::epsilon] in a random direction
+///
+/// Send along with a [ResetSimulation] to restart from almost the same state, to show how far
+/// a tiny difference grows in a chaotic system.
+#[derive(Event, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PerturbBody(pub Entity);
+
+/// How much a [PerturbBody] changes a velocity, drawn from a seeded generator so a run can be
+/// repeated exactly
+#[derive(Resource, Debug, Clone, Copy, PartialEq)]
+pub struct Perturbation {
+    /// Change in speed in m/s
+    pub epsilon: f32,
+
+    /// Generator for the directions, every perturbation advances it
+    rng: SimRng,
+}
+
+impl Perturbation {
+    /// Create a new [Perturbation] of `epsilon` m/s, drawing its directions from `seed`
+    #[must_use]
+    pub fn new(epsilon: f32, seed: u64) -> Self {
+        Self {
+            epsilon,
+            rng: SimRng::new(seed),
+        }
+    }
+
+    /// Gets the next change in velocity, `epsilon` long in a uniformly random direction
+    pub fn next_offset(&mut self) -> Vec3 {
+        self.rng.direction() * self.epsilon
+    }
+}
+
+impl Default for Perturbation {
+    fn default() -> Self {
+        Self::new(1e-3, 0)
+    }
+}
+
+/// Stores the [InitialState] of every simulated object that doesn't have one yet
+#[allow(clippy::type_complexity)]
+pub fn capture_initial_state(
+    mut cmds: Commands,
+    query: Query<
+        (Entity, &Transform, &Velocity, &AngularVelocity),
+        (With<Simulated>, Without<InitialState>),
+    >,
+) {
+    for (entity, trans, vel, angvel) in query.iter() {
+        cmds.entity(entity).insert(InitialState {
+            transform: *trans,
+            velocity: vel.0,
+            angular_velocity: angvel.0,
+        });
+    }
+}
+
+/// Restores the [InitialState] of every simulated object when a [ResetSimulation] is sent
+#[allow(clippy::type_complexity)]
+pub fn reset_simulation(
+    mut resets: EventReader<ResetSimulation>,
+    mut query: Query<
+        (
+            &InitialState,
+            &mut Transform,
+            &mut Velocity,
+            &mut AngularVelocity,
+            Option<&mut ForceAccumulator>,
+            Option<&mut StepTransforms>,
+        ),
+        With<Simulated>,
+    >,
+    clock: Option<ResMut<SimClock>>,
+) {
+    // Resetting more than once a frame does nothing extra
+    if resets.read().count() == 0 {
+        return;
+    }
+
+    if let Some(mut clock) = clock {
+        clock.set_elapsed(0.0);
+    }
+
+    for (initial, mut trans, mut vel, mut angvel, forces, steps) in query.iter_mut() {
+        *trans = initial.transform;
+        vel.0 = initial.velocity;
+        angvel.0 = initial.angular_velocity;
+
+        // Forces added before the reset shouldn't carry over into the first step after it
+        if let Some(mut forces) = forces {
+            let _ = forces.take();
+        }
+
+        if let Some(mut steps) = steps {
+            *steps = StepTransforms::new(initial.transform);
+        }
+    }
+}
+
+/// Adds the next [Perturbation] to the velocity of every object in a [PerturbBody]
+///
+/// Runs after [reset_simulation], so the nudge lands on the restored state. The [InitialState]
+/// is left alone, the next reset starts from the same place.
+pub fn perturb(
+    mut events: EventReader<PerturbBody>,
+    mut perturbation: ResMut<Perturbation>,
+    mut query: Query<&mut Velocity, With<Simulated>>,
+) {
+    for PerturbBody(entity) in events.read() {
+        if let Ok(mut vel) = query.get_mut(*entity) {
+            vel.0 += perturbation.next_offset();
+        }
+    }
+}
+
+#[cfg(test)]
+mod restoring {
+    use std::time::Duration;
+
+    use bevy::{
+        app::{App, Update},
+        ecs::{event::Events, schedule::IntoSystemConfigs, system::RunSystemOnce},
+        math::Vec3,
+        time::Time,
+        transform::components::Transform,
+    };
+
+    use super::{capture_initial_state, reset_simulation, InitialState, ResetSimulation};
+    use crate::{
+        components::{
+            acceleration::Accelerator,
+            inertia::Inertia,
+            velocity::{AngularVelocity, Velocity},
+            SimulationBundle,
+        },
+        resources::PhysicsSettings,
+        update_simulated,
+    };
+
+    #[test]
+    fn back_to_start() {
+        let mut app = App::new();
+        let mut time = Time::<()>::default();
+        time.advance_by(Duration::from_millis(100));
+
+        app.insert_resource(time);
+        app.insert_resource(PhysicsSettings {
+            max_delta: None,
+            ..Default::default()
+        });
+        app.add_event::<ResetSimulation>();
+        app.add_systems(Update, (capture_initial_state, update_simulated).chain());
+
+        let mut body = SimulationBundle::new(
+            Velocity(Vec3::new(1.0, 2.0, 0.0)),
+            Accelerator(Vec3::NEG_Y),
+            AngularVelocity(Vec3::Z),
+            Inertia::sphere(1.0, 1.0),
+        );
+        body.spatial.transform = Transform::from_xyz(1.0, 5.0, 0.0);
+        let body = app.world_mut().spawn(body).id();
+
+        for _ in 0..5 {
+            app.update();
+        }
+
+        let initial = *app.world().get::<InitialState>(body).unwrap();
+        assert_eq!(initial.transform, Transform::from_xyz(1.0, 5.0, 0.0));
+        assert_ne!(
+            app.world().get::<Transform>(body).unwrap(),
+            &initial.transform
+        );
+
+        app.world_mut()
+            .resource_mut::<Events<ResetSimulation>>()
+            .send(ResetSimulation);
+        app.world_mut().run_system_once(reset_simulation);
+
+        let world = app.world();
+        assert_eq!(world.get::<Transform>(body).unwrap(), &initial.transform);
+        assert_eq!(
+            world.get::<Velocity>(body).unwrap().0,
+            Vec3::new(1.0, 2.0, 0.0)
+        );
+        assert_eq!(world.get::<AngularVelocity>(body).unwrap().0, Vec3::Z);
+    }
+}
+
+#[cfg(test)]
+mod perturbing {
+    use bevy::{
+        app::{App, Update},
+        ecs::{entity::Entity, event::Events, schedule::IntoSystemConfigs},
+        math::Vec3,
+    };
+    use float_cmp::assert_approx_eq;
+
+    use super::{
+        capture_initial_state, perturb, reset_simulation, PerturbBody, Perturbation,
+        ResetSimulation,
+    };
+    use crate::components::{
+        acceleration::Accelerator,
+        inertia::Inertia,
+        velocity::{AngularVelocity, Velocity},
+        SimulationBundle,
+    };
+
+    /// Restarts a body moving along x with a perturbation, returning its velocity afterwards
+    fn restart(seed: u64) -> Vec3 {
+        let mut app = App::new();
+        app.insert_resource(Perturbation::new(0.01, seed));
+        app.add_event::<ResetSimulation>();
+        app.add_event::<PerturbBody>();
+        app.add_systems(
+            Update,
+            (capture_initial_state, reset_simulation, perturb).chain(),
+        );
+
+        let body = app
+            .world_mut()
+            .spawn(SimulationBundle::new(
+                Velocity(Vec3::X),
+                Accelerator::ZERO,
+                AngularVelocity::ZERO,
+                Inertia::sphere(1.0, 1.0),
+            ))
+            .id();
+        app.update();
+
+        app.world_mut().get_mut::<Velocity>(body).unwrap().0 = Vec3::Y * 5.0;
+        app.world_mut()
+            .resource_mut::<Events<ResetSimulation>>()
+            .send(ResetSimulation);
+        send(&mut app, body);
+        app.update();
+
+        app.world().get::<Velocity>(body).unwrap().0
+    }
+
+    fn send(app: &mut App, body: Entity) {
+        app.world_mut()
+            .resource_mut::<Events<PerturbBody>>()
+            .send(PerturbBody(body));
+    }
+
+    #[test]
+    fn deterministic() {
+        let vel = restart(42);
+
+        assert_eq!(restart(42), vel);
+        assert_ne!(restart(43), vel);
+
+        // Back at the start, nudged by exactly epsilon
+        assert_approx_eq!(f32, vel.distance(Vec3::X), 0.01, epsilon = 1e-6);
+    }
+
+    #[test]
+    fn sequence() {
+        let mut a = Perturbation::new(1.0, 7);
+        let mut b = Perturbation::new(1.0, 7);
+
+        let first = a.next_offset();
+        assert_eq!(b.next_offset(), first);
+        assert_ne!(a.next_offset(), first);
+        assert_approx_eq!(f32, first.length(), 1.0, epsilon = 1e-6);
+    }
+}
@@ -0,0 +1,320 @@
+use std::{f32::consts::FRAC_PI_2, time::Duration};
+
+use bevy::{
+    ecs::system::Resource,
+    math::{Quat, Vec3},
+};
+
+use crate::components::{aero::AIR_DENSITY, GravityOverride};
+
+/// Global wind, the velocity of the air in global cordinates
+#[derive(Resource, Debug, Default)]
+pub struct Wind(pub Vec3);
+
+/// Air that thins out exponentially with altitude, used by the drag systems
+///
+/// The density at a height `y` is `sea_level_density * exp(-y / scale_height)`, so drag fades
+/// away for objects flying high enough.
+#[derive(Resource, Debug, Clone, Copy, PartialEq)]
+pub struct Atmosphere {
+    /// Density at a height of zero, in kg/m^3
+    pub sea_level_density: f32,
+
+    /// Height in meters over which the density falls by a factor of e
+    pub scale_height: f32,
+}
+
+impl Atmosphere {
+    /// Gets the air density in kg/m^3 at a height along the [UpAxis]
+    ///
+    /// A scale height of zero or less gives the same density everywhere.
+    #[must_use]
+    pub fn density(&self, altitude: f32) -> f32 {
+        if self.scale_height <= 0.0 {
+            return self.sea_level_density;
+        }
+
+        self.sea_level_density * (-altitude / self.scale_height).exp()
+    }
+}
+
+impl Default for Atmosphere {
+    fn default() -> Self {
+        Self {
+            sea_level_density: AIR_DENSITY,
+            scale_height: 8500.0,
+        }
+    }
+}
+
+/// Which global axis points up, for scenes modeled with z up instead of the default y
+///
+/// Consulted for the default [Gravity], by the directional helpers of
+/// [Velocity](crate::components::velocity::Velocity) and for the altitude in the [Atmosphere].
+/// Read again on every step, so changing it turns the default gravity along with it. The ground
+/// plane and the grid stay horizontal in y.
+#[derive(Resource, Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum UpAxis {
+    #[default]
+    Y,
+    Z,
+}
+
+impl UpAxis {
+    /// Unit vector pointing up
+    #[must_use]
+    pub fn up(&self) -> Vec3 {
+        match self {
+            UpAxis::Y => Vec3::Y,
+            UpAxis::Z => Vec3::Z,
+        }
+    }
+
+    /// Gets the height of a position along the up axis
+    #[must_use]
+    pub fn height(&self, position: Vec3) -> f32 {
+        position.dot(self.up())
+    }
+
+    /// Rotation taking vectors from this convention to the y up one
+    ///
+    /// Keeps the x axis in place, so forward stays forward.
+    #[must_use]
+    pub fn to_y_up(&self) -> Quat {
+        match self {
+            UpAxis::Y => Quat::IDENTITY,
+            UpAxis::Z => Quat::from_rotation_x(-FRAC_PI_2),
+        }
+    }
+}
+
+/// Global gravity, the acceleration every non-static object gets towards the ground
+///
+/// Set through [PhysicsSettings::gravity], read with [PhysicsSettings::gravity_for].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Gravity(pub Vec3);
+
+impl Gravity {
+    /// Standard gravity of 9.82 m/s^2 pulling down along the [UpAxis]
+    #[must_use]
+    pub fn towards_ground(up: UpAxis) -> Self {
+        Self(up.up() * -9.82)
+    }
+
+    /// Gets the gravity acting on an object, its [GravityOverride] if it has one
+    #[must_use]
+    pub fn for_body(&self, body: Option<&GravityOverride>) -> Vec3 {
+        body.map_or(self.0, |g| g.0)
+    }
+}
+
+/// A heavy body everything [Orbiting](crate::components::Orbiting) is pulled towards
+///
+/// Only used when inserted, there is no central body by default.
+#[derive(Resource, Debug)]
+pub struct CentralBody {
+    /// Standard gravitational parameter, the gravitational constant times the mass of the body
+    pub mu: f32,
+
+    /// Position in global cordinates
+    pub position: Vec3,
+}
+
+impl CentralBody {
+    /// Gets the gravitational acceleration at a position
+    ///
+    /// Zero at the center of the body itself.
+    #[must_use]
+    pub fn acceleration(&self, position: Vec3) -> Vec3 {
+        let offset = position - self.position;
+        let r2 = offset.length_squared();
+
+        if r2 == 0.0 {
+            return Vec3::ZERO;
+        }
+
+        offset.normalize() * -self.mu / r2
+    }
+}
+
+/// The horizontal plane the ground is at
+#[derive(Resource, Debug, Default)]
+pub struct GroundPlane {
+    /// Height of the plane along the y axis
+    pub height: f32,
+}
+
+/// Longest time in seconds a single step may cover by default, see [PhysicsSettings::max_delta]
+pub const DEFAULT_MAX_DELTA: f32 = 1.0 / 20.0;
+
+/// Simulated time in seconds, advanced by every physics step
+///
+/// Differs from the time of the app once the simulation has been paused or steps have been
+/// clamped by [PhysicsSettings::max_delta]. Stands still while paused, and moves on by one step for every
+/// step taken by hand. Set back to zero on a reset.
+#[derive(Resource, Debug, Default, Clone, Copy, PartialEq)]
+pub struct SimClock {
+    elapsed: f32,
+}
+
+impl SimClock {
+    /// Gets the simulated seconds since the start
+    #[must_use]
+    pub fn elapsed(&self) -> f32 {
+        self.elapsed
+    }
+
+    /// Jumps to `elapsed` seconds, for restoring a saved state
+    pub fn set_elapsed(&mut self, elapsed: f32) {
+        self.elapsed = elapsed;
+    }
+
+    /// Moves the clock on by a step of `delta` seconds
+    pub fn advance(&mut self, delta: f32) {
+        self.elapsed += delta;
+    }
+}
+
+/// How [update_simulated](crate::update_simulated) moves objects through a step
+///
+/// The forces are only gathered once per step, so the methods differ in how the velocity and
+/// position are advanced over it.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum IntegrationMethod {
+    /// Velocity first, then the position with the new velocity
+    ///
+    /// First order and the cheapest, but loses or gains energy over long runs.
+    SemiImplicitEuler,
+
+    /// Half the velocity change before moving and half after, like velocity Verlet
+    #[default]
+    Verlet,
+
+    /// Classic fourth order Runge-Kutta
+    Rk4,
+}
+
+/// The core settings of the simulation in one place, see
+/// [SimulatiorPlugin::with_settings](crate::SimulatiorPlugin::with_settings)
+///
+/// Always present once the plugin is added, and read directly by the physics systems, so a
+/// change applies from the next step. The only copy is the fixed timestep, which is pushed to
+/// [Time<Fixed>](bevy::time::Time) whenever this changes.
+#[derive(Resource, Debug, Clone, Copy, PartialEq)]
+pub struct PhysicsSettings {
+    /// How objects are moved through every step
+    pub integration: IntegrationMethod,
+
+    /// Acceleration of every non-static object, [None] for the standard gravity pulling down
+    /// along the [UpAxis], see [PhysicsSettings::gravity_for]
+    pub gravity: Option<Vec3>,
+
+    /// Simulated time between two fixed updates before splitting into substeps, in seconds
+    ///
+    /// While an [AdaptiveTimestep](crate::timestep::AdaptiveTimestep) exists this is only the
+    /// longest the step may get, it picks the actual one.
+    pub timestep: f32,
+
+    /// How many physics steps every timestep is split into, at least one
+    pub substeps: u32,
+
+    /// Longest time a single step may cover in seconds, [None] for no limit
+    ///
+    /// A long frame, like one after hitting a breakpoint, would otherwise be integrated in one
+    /// huge step that can launch objects to infinity. Time past the limit is dropped, slowing the
+    /// simulation down instead.
+    pub max_delta: Option<f32>,
+}
+
+impl PhysicsSettings {
+    /// Gets how long a single physics step is, the timestep split into the substeps
+    ///
+    /// [None] when that isn't a positive and finite duration, like for a negative or NaN
+    /// timestep.
+    #[must_use]
+    pub fn step(&self) -> Option<Duration> {
+        Duration::try_from_secs_f32(self.timestep / self.substeps.max(1) as f32)
+            .ok()
+            .filter(|step| !step.is_zero())
+    }
+
+    /// Gets the global gravity when `up` is the up axis of the world
+    ///
+    /// ```rust
+    /// # use physics::resources::{PhysicsSettings, UpAxis};
+    /// # use bevy::math::Vec3;
+    /// let settings = PhysicsSettings::default();
+    ///
+    /// assert_eq!(settings.gravity_for(UpAxis::Z).0, Vec3::Z * -9.82);
+    /// ```
+    #[must_use]
+    pub fn gravity_for(&self, up: UpAxis) -> Gravity {
+        self.gravity
+            .map_or_else(|| Gravity::towards_ground(up), Gravity)
+    }
+
+    /// Clamps `delta` seconds to [PhysicsSettings::max_delta]
+    #[must_use]
+    pub fn clamp_delta(&self, delta: f32) -> f32 {
+        match self.max_delta {
+            Some(max) => delta.min(max),
+            None => delta,
+        }
+    }
+}
+
+impl Default for PhysicsSettings {
+    fn default() -> Self {
+        Self {
+            integration: IntegrationMethod::default(),
+            gravity: None,
+            timestep: 1.0 / 64.0,
+            substeps: 1,
+            max_delta: Some(DEFAULT_MAX_DELTA),
+        }
+    }
+}
+
+/// Soft cap on the number of simulated objects, respected by
+/// [SpawnBody](crate::spawning::SpawnBody)
+///
+/// Guards against accidentally spawning so many objects that everything freezes. Objects spawned
+/// any other way are not stopped, but still count towards the cap.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MaxBodies(pub usize);
+
+impl Default for MaxBodies {
+    fn default() -> Self {
+        Self(10_000)
+    }
+}
+
+/// Switches for every force system, a disabled force is skipped entirely
+///
+/// Useful to find out which force is causing trouble. Everything is enabled by default.
+#[derive(Resource, Debug, Clone, Copy, PartialEq)]
+pub struct ForceToggles {
+    pub gravity: bool,
+    pub central_gravity: bool,
+    pub aerodynamics: bool,
+    pub drag: bool,
+    pub magnus: bool,
+    pub moments: bool,
+    pub springs: bool,
+    pub motors: bool,
+}
+
+impl Default for ForceToggles {
+    fn default() -> Self {
+        Self {
+            gravity: true,
+            central_gravity: true,
+            aerodynamics: true,
+            drag: true,
+            magnus: true,
+            moments: true,
+            springs: true,
+            motors: true,
+        }
+    }
+}
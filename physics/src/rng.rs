@@ -0,0 +1,121 @@
+use std::f32::consts::TAU;
+
+use bevy::{ecs::system::Resource, math::Vec3};
+
+/// Seeded random number generator for building scenes that can be repeated exactly
+///
+/// A splitmix64 generator, small and fast but not meant for anything cryptographic. The same
+/// seed always gives the same sequence, on every platform.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SimRng {
+    /// Seed the generator was created or last reseeded with
+    seed: u64,
+
+    /// State of the generator, every draw advances it
+    state: u64,
+}
+
+impl SimRng {
+    /// Create a new [SimRng] starting the sequence of `seed`
+    #[must_use]
+    pub fn new(seed: u64) -> Self {
+        Self { seed, state: seed }
+    }
+
+    /// Gets the seed the current sequence started from
+    #[must_use]
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Restarts the sequence from `seed`
+    pub fn reseed(&mut self, seed: u64) {
+        *self = Self::new(seed);
+    }
+
+    /// Draws the next 64 random bits
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Draws a number in `[0, 1)`
+    pub fn unit(&mut self) -> f32 {
+        (self.next_u64() >> 40) as f32 / (1u64 << 24) as f32
+    }
+
+    /// Draws a number in `[min, max)`
+    pub fn range(&mut self, min: f32, max: f32) -> f32 {
+        min + (max - min) * self.unit()
+    }
+
+    /// Draws a point in the box between the corners `min` and `max`
+    pub fn vec3_in(&mut self, min: Vec3, max: Vec3) -> Vec3 {
+        Vec3::new(
+            self.range(min.x, max.x),
+            self.range(min.y, max.y),
+            self.range(min.z, max.z),
+        )
+    }
+
+    /// Draws a unit vector in a uniformly random direction
+    pub fn direction(&mut self) -> Vec3 {
+        let z = self.unit() * 2.0 - 1.0;
+        let angle = self.unit() * TAU;
+        let radius = (1.0 - z * z).sqrt();
+
+        Vec3::new(radius * angle.cos(), radius * angle.sin(), z)
+    }
+}
+
+impl Default for SimRng {
+    fn default() -> Self {
+        Self::new(0)
+    }
+}
+
+#[cfg(test)]
+mod determinism {
+    use bevy::math::Vec3;
+    use float_cmp::assert_approx_eq;
+
+    use super::SimRng;
+
+    fn draw(rng: &mut SimRng) -> Vec<Vec3> {
+        (0..16)
+            .map(|_| rng.vec3_in(Vec3::splat(-5.0), Vec3::new(5.0, 10.0, 0.0)))
+            .collect()
+    }
+
+    #[test]
+    fn reseeded() {
+        let mut rng = SimRng::new(3);
+        let first = draw(&mut rng);
+
+        rng.reseed(3);
+        assert_eq!(draw(&mut rng), first);
+
+        rng.reseed(4);
+        assert_ne!(draw(&mut rng), first);
+        assert_eq!(rng.seed(), 4);
+    }
+
+    #[test]
+    fn in_range() {
+        let mut rng = SimRng::new(11);
+
+        for point in draw(&mut rng) {
+            assert!((-5.0..5.0).contains(&point.x));
+            assert!((-5.0..10.0).contains(&point.y));
+            assert!((-5.0..0.0).contains(&point.z));
+        }
+
+        for _ in 0..16 {
+            assert_approx_eq!(f32, rng.direction().length(), 1.0, epsilon = 1e-6);
+        }
+    }
+}
@@ -0,0 +1,607 @@
+use std::{
+    fmt, fs, io,
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
+
+use bevy::{
+    ecs::{
+        entity::Entity,
+        event::{Event, EventReader, EventWriter},
+        query::With,
+        system::{Commands, Query, ResMut, Resource},
+        world::World,
+    },
+    hierarchy::DespawnRecursiveExt,
+    math::{Mat3, Quat, Vec3},
+    transform::components::Transform,
+};
+use ron::{extensions::Extensions, ser::PrettyConfig};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    components::{
+        acceleration::Accelerator,
+        inertia::Inertia,
+        shape::Shape,
+        velocity::{AngularVelocity, Velocity},
+        Simulated, SimulationBundle,
+    },
+    names::BodyName,
+};
+
+/// Every object of a scene, as stored in a RON scene file
+///
+/// Optional fields can be left out and are written without `Some(..)` around them.
+///
+/// ```rust
+/// # use physics::scene::{SceneFile, SceneInertia};
+/// # use physics::components::shape::Shape;
+/// let scene = SceneFile::parse(
+///     r#"(
+///         bodies: [
+///             (
+///                 name: "ball",
+///                 position: (0.0, 5.0, 0.0),
+///                 velocity: (1.0, 0.0, 0.0),
+///                 inertia: Shape(shape: Sphere(radius: 0.5), mass: 2.0),
+///             ),
+///             (position: (0.0, 0.0, 0.0), inertia: Static),
+///         ],
+///     )"#,
+/// )
+/// .unwrap();
+///
+/// assert_eq!(scene.bodies[0].name.as_deref(), Some("ball"));
+/// assert_eq!(scene.bodies[1].inertia, SceneInertia::Static);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SceneFile {
+    pub bodies: Vec<SceneBody>,
+}
+
+/// How an object of a [SceneFile] starts out
+///
+/// Only the position and the inertia are required, everything else defaults to an unnamed
+/// object at rest that isn't rotated.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SceneBody {
+    /// Given to the object as its [BodyName]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+
+    pub position: Vec3,
+
+    #[serde(default)]
+    pub rotation: Quat,
+
+    #[serde(default)]
+    pub velocity: Vec3,
+
+    #[serde(default)]
+    pub angular_velocity: Vec3,
+
+    pub inertia: SceneInertia,
+}
+
+/// Mass and mass distribution of an object in a [SceneFile]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum SceneInertia {
+    /// Generated from a [Shape], which the object keeps for drag and collisions
+    Shape { shape: Shape, mass: f32 },
+
+    /// Any other mass distribution, as the inertia tensor around the center of mass
+    Tensor { mass: f32, tensor: Mat3 },
+
+    /// Static object with infinite mass, see [Inertia::static_body]
+    Static,
+}
+
+/// Why a scene file couldn't be read or written
+#[derive(Debug)]
+pub enum SceneError {
+    Io(io::Error),
+    Parse(ron::error::SpannedError),
+    Serialize(ron::Error),
+}
+
+/// Scene file that is spawned, and spawned again every time it changes on disk
+///
+/// Checked every frame by [watch_scene], which sends a [ReloadScene] for the first version of
+/// the file as well. Nothing is spawned until the resource is inserted.
+#[derive(Resource, Debug)]
+pub struct SceneWatcher {
+    pub path: PathBuf,
+
+    /// Modification time of the file when it was last checked, `None` if it couldn't be read
+    modified: Option<SystemTime>,
+
+    /// If the file has been checked at all yet
+    checked: bool,
+}
+
+/// Replaces every simulated object with the objects of a scene, see [reload_scene]
+#[derive(Event, Debug, Clone, PartialEq)]
+pub struct ReloadScene(pub SceneFile);
+
+impl SceneFile {
+    /// Parses a scene from RON
+    pub fn parse(text: &str) -> Result<Self, SceneError> {
+        Ok(ron_options().from_str(text)?)
+    }
+
+    /// Reads and parses a scene file
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, SceneError> {
+        Self::parse(&fs::read_to_string(path)?)
+    }
+
+    /// Spawns every object of the scene
+    pub fn spawn(&self, cmds: &mut Commands) -> Vec<Entity> {
+        self.bodies.iter().map(|body| body.spawn(cmds)).collect()
+    }
+
+    /// Describes every simulated object as it is right now, in the order they were spawned
+    ///
+    /// Spawning the result starts a simulation from the current state.
+    #[must_use]
+    pub fn from_world(world: &mut World) -> Self {
+        let mut bodies: Vec<(Entity, SceneBody)> = world
+            .query_filtered::<(
+                Entity,
+                &Transform,
+                &Velocity,
+                &AngularVelocity,
+                &Inertia,
+                Option<&Shape>,
+                Option<&BodyName>,
+            ), With<Simulated>>()
+            .iter(world)
+            .map(|(entity, trans, vel, angvel, inertia, shape, name)| {
+                let body = SceneBody {
+                    name: name.map(|name| name.0.clone()),
+                    position: trans.translation,
+                    rotation: trans.rotation,
+                    velocity: vel.0,
+                    angular_velocity: angvel.0,
+                    inertia: SceneInertia::from_inertia(inertia, shape),
+                };
+
+                (entity, body)
+            })
+            .collect();
+        bodies.sort_by_key(|(entity, _)| *entity);
+
+        Self {
+            bodies: bodies.into_iter().map(|(_, body)| body).collect(),
+        }
+    }
+
+    /// Writes the scene as RON, in the same format [SceneFile::load] reads
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), SceneError> {
+        let text = ron_options().to_string_pretty(self, PrettyConfig::default())?;
+        fs::write(path, text)?;
+
+        Ok(())
+    }
+}
+
+impl SceneBody {
+    /// Spawns the object, along with its [Shape] and [BodyName] if it has them
+    pub fn spawn(&self, cmds: &mut Commands) -> Entity {
+        let mut body = SimulationBundle::new(
+            Velocity(self.velocity),
+            Accelerator::ZERO,
+            AngularVelocity(self.angular_velocity),
+            self.inertia.inertia(),
+        );
+        body.spatial.transform =
+            Transform::from_translation(self.position).with_rotation(self.rotation);
+
+        let mut entity = cmds.spawn(body);
+
+        if let SceneInertia::Shape { shape, .. } = self.inertia {
+            entity.insert(shape);
+        }
+
+        if let Some(name) = &self.name {
+            entity.insert(BodyName(name.clone()));
+        }
+
+        entity.id()
+    }
+}
+
+impl SceneInertia {
+    /// Describes an [Inertia], through the [Shape] of the object if it has one
+    ///
+    /// The shape is only used if it gives the same inertia, otherwise the tensor is kept as is.
+    #[must_use]
+    pub fn from_inertia(inertia: &Inertia, shape: Option<&Shape>) -> Self {
+        if inertia.is_static() {
+            return SceneInertia::Static;
+        }
+
+        let mass = inertia.mass();
+        let largest = inertia
+            .tensor
+            .abs()
+            .to_cols_array()
+            .into_iter()
+            .fold(1.0, f32::max);
+
+        match shape {
+            Some(&shape)
+                if shape
+                    .inertia(mass)
+                    .tensor
+                    .abs_diff_eq(inertia.tensor, largest * 1e-5) =>
+            {
+                SceneInertia::Shape { shape, mass }
+            }
+            _ => SceneInertia::Tensor {
+                mass,
+                tensor: inertia.tensor,
+            },
+        }
+    }
+
+    /// Computes the [Inertia] of the object
+    #[must_use]
+    pub fn inertia(&self) -> Inertia {
+        match *self {
+            SceneInertia::Shape { shape, mass } => shape.inertia(mass),
+            SceneInertia::Tensor { mass, tensor } => Inertia::new(mass, tensor),
+            SceneInertia::Static => Inertia::static_body(),
+        }
+    }
+}
+
+impl SceneWatcher {
+    /// Create a [SceneWatcher] for the scene file at `path`
+    #[must_use]
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            modified: None,
+            checked: false,
+        }
+    }
+}
+
+impl fmt::Display for SceneError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SceneError::Io(err) => write!(f, "couldn't read the scene file: {err}"),
+            SceneError::Parse(err) => write!(f, "couldn't parse the scene: {err}"),
+            SceneError::Serialize(err) => write!(f, "couldn't write the scene: {err}"),
+        }
+    }
+}
+
+impl std::error::Error for SceneError {}
+
+impl From<io::Error> for SceneError {
+    fn from(value: io::Error) -> Self {
+        Self::Io(value)
+    }
+}
+
+impl From<ron::error::SpannedError> for SceneError {
+    fn from(value: ron::error::SpannedError) -> Self {
+        Self::Parse(value)
+    }
+}
+
+impl From<ron::Error> for SceneError {
+    fn from(value: ron::Error) -> Self {
+        Self::Serialize(value)
+    }
+}
+
+/// RON options every scene is read and written with, `Some` is left implicit
+fn ron_options() -> ron::Options {
+    ron::Options::default().with_default_extension(Extensions::IMPLICIT_SOME)
+}
+
+/// Saves every simulated object to a scene file, see [SceneFile::from_world]
+///
+/// Objects with a [Shape] that matches their [Inertia] are written with the shape, any other
+/// object with its raw inertia tensor.
+pub fn save_scene(world: &mut World, path: impl AsRef<Path>) -> Result<(), SceneError> {
+    SceneFile::from_world(world).save(path)
+}
+
+/// Sends a [ReloadScene] whenever the file of the [SceneWatcher] has been modified
+///
+/// A file that can't be read or parsed is logged, and the current scene is kept rather than
+/// cleared, so saving a half finished edit doesn't lose anything. Does nothing without a
+/// [SceneWatcher].
+pub fn watch_scene(watcher: Option<ResMut<SceneWatcher>>, mut reloads: EventWriter<ReloadScene>) {
+    let Some(mut watcher) = watcher else {
+        return;
+    };
+
+    let modified = fs::metadata(&watcher.path)
+        .and_then(|meta| meta.modified())
+        .ok();
+
+    if watcher.checked && modified == watcher.modified {
+        return;
+    }
+
+    watcher.checked = true;
+    watcher.modified = modified;
+
+    match SceneFile::load(&watcher.path) {
+        Ok(scene) => {
+            reloads.send(ReloadScene(scene));
+        }
+        Err(err) => log::warn!(
+            "Keeping the current scene, {}: {err}",
+            watcher.path.display()
+        ),
+    }
+}
+
+/// Despawns every simulated object and spawns the scene of the last [ReloadScene] in their place
+pub fn reload_scene(
+    mut cmds: Commands,
+    mut reloads: EventReader<ReloadScene>,
+    bodies: Query<Entity, With<Simulated>>,
+) {
+    let Some(ReloadScene(scene)) = reloads.read().last() else {
+        return;
+    };
+
+    for entity in bodies.iter() {
+        cmds.entity(entity).despawn_recursive();
+    }
+
+    let spawned = scene.spawn(&mut cmds);
+    log::info!("Loaded a scene with {} objects", spawned.len());
+}
+
+#[cfg(test)]
+mod reload {
+    use std::{
+        fs::{self, File},
+        time::{Duration, SystemTime},
+    };
+
+    use bevy::{
+        app::{App, Update},
+        ecs::{entity::Entity, query::With, schedule::IntoSystemConfigs},
+        hierarchy::BuildWorldChildren,
+        math::Vec3,
+        transform::components::Transform,
+    };
+
+    use super::{
+        reload_scene, watch_scene, ReloadScene, SceneBody, SceneFile, SceneInertia, SceneWatcher,
+    };
+    use crate::{
+        components::{
+            acceleration::Accelerator,
+            inertia::Inertia,
+            shape::Shape,
+            velocity::{AngularVelocity, Velocity},
+            Simulated, SimulationBundle,
+        },
+        names::BodyName,
+    };
+
+    fn app() -> App {
+        let mut app = App::new();
+        app.add_event::<ReloadScene>();
+        app.add_systems(Update, (watch_scene, reload_scene).chain());
+
+        app
+    }
+
+    fn ball(x: f32) -> SceneBody {
+        SceneBody {
+            name: None,
+            position: Vec3::X * x,
+            rotation: Default::default(),
+            velocity: Vec3::ZERO,
+            angular_velocity: Vec3::ZERO,
+            inertia: SceneInertia::Shape {
+                shape: Shape::Sphere { radius: 0.5 },
+                mass: 1.0,
+            },
+        }
+    }
+
+    /// Positions of every simulated object, sorted along x
+    fn positions(app: &mut App) -> Vec<f32> {
+        let world = app.world_mut();
+        let mut xs: Vec<f32> = world
+            .query_filtered::<&Transform, With<Simulated>>()
+            .iter(world)
+            .map(|trans| trans.translation.x)
+            .collect();
+        xs.sort_by(f32::total_cmp);
+
+        xs
+    }
+
+    #[test]
+    fn replaces_bodies() {
+        let mut app = app();
+        let old = app
+            .world_mut()
+            .spawn(SimulationBundle::new(
+                Velocity::ZERO,
+                Accelerator::ZERO,
+                AngularVelocity::ZERO,
+                Inertia::sphere(1.0, 1.0),
+            ))
+            .with_children(|parent| {
+                parent.spawn(Transform::default());
+            })
+            .id();
+
+        let mut named = ball(1.0);
+        named.name = Some("ball".into());
+        let scene = SceneFile {
+            bodies: vec![named, ball(2.0), ball(3.0)],
+        };
+        app.world_mut().send_event(ReloadScene(scene));
+        app.update();
+
+        // The old object is gone along with its children
+        assert!(app.world().get_entity(old).is_none());
+        assert_eq!(app.world().entities().len(), 3);
+        assert_eq!(positions(&mut app), [1.0, 2.0, 3.0]);
+
+        let world = app.world_mut();
+        let (shape, name) = world.query::<(&Shape, &BodyName)>().single(world);
+        assert_eq!(*shape, Shape::Sphere { radius: 0.5 });
+        assert_eq!(name.0, "ball");
+    }
+
+    #[test]
+    fn watches_file() {
+        let path = std::env::temp_dir().join(format!("scene_watch_{}.ron", std::process::id()));
+        let write = |text: &str, age: u64| {
+            fs::write(&path, text).unwrap();
+
+            // Written faster than the clock of some file systems ticks, so date them explicitly
+            File::options()
+                .write(true)
+                .open(&path)
+                .unwrap()
+                .set_modified(SystemTime::UNIX_EPOCH + Duration::from_secs(age))
+                .unwrap();
+        };
+
+        write(
+            "(bodies: [(position: (1.0, 0.0, 0.0), inertia: Static)])",
+            1,
+        );
+        let mut app = app();
+        app.insert_resource(SceneWatcher::new(&path));
+        app.update();
+        assert_eq!(positions(&mut app), [1.0]);
+
+        // Unchanged, so nothing is respawned
+        let world = app.world_mut();
+        let first = world
+            .query_filtered::<Entity, With<Simulated>>()
+            .single(world);
+        app.update();
+        assert!(app.world().get_entity(first).is_some());
+
+        // A broken edit keeps what is there
+        write("(bodies: [(position: (2.0, 0.0", 2);
+        app.update();
+        assert!(app.world().get_entity(first).is_some());
+        assert_eq!(positions(&mut app), [1.0]);
+
+        write(
+            "(bodies: [(position: (2.0, 0.0, 0.0), inertia: Static), (position: (3.0, 0.0, 0.0), \
+             inertia: Static)])",
+            3,
+        );
+        app.update();
+        assert_eq!(positions(&mut app), [2.0, 3.0]);
+
+        fs::remove_file(&path).unwrap();
+    }
+}
+
+#[cfg(test)]
+mod round_trip {
+    use std::fs;
+
+    use bevy::{
+        app::{App, Update},
+        ecs::world::World,
+        math::{Mat3, Quat, Vec3},
+    };
+
+    use super::{reload_scene, save_scene, ReloadScene, SceneFile, SceneInertia};
+    use crate::components::{
+        acceleration::Accelerator,
+        inertia::Inertia,
+        shape::{Axis, Shape},
+        velocity::{AngularVelocity, Velocity},
+        SimulationBundle,
+    };
+
+    #[test]
+    fn load_save_reload() {
+        let scene = SceneFile::parse(
+            r#"(
+                bodies: [
+                    (
+                        name: "wheel",
+                        position: (1.0, 2.0, 3.0),
+                        rotation: (0.0, 0.0, 0.70710677, 0.70710677),
+                        velocity: (0.5, 0.0, -1.5),
+                        angular_velocity: (0.0, 4.0, 0.0),
+                        inertia: Shape(shape: Cylinder(axis: Y, height: 0.2, radius: 0.5), mass: 2.0),
+                    ),
+                    (
+                        position: (-4.0, 0.0, 0.0),
+                        inertia: Tensor(mass: 4.0, tensor: (1.0, 0.0, 0.25, 0.0, 2.0, 0.0, 0.25, 0.0, 3.0)),
+                    ),
+                    (position: (0.0, -1.0, 0.0), inertia: Static),
+                ],
+            )"#,
+        )
+        .unwrap();
+
+        let mut app = App::new();
+        app.add_event::<ReloadScene>();
+        app.add_systems(Update, reload_scene);
+        app.world_mut().send_event(ReloadScene(scene.clone()));
+        app.update();
+
+        let path = std::env::temp_dir().join(format!("scene_save_{}.ron", std::process::id()));
+        save_scene(app.world_mut(), &path).unwrap();
+        let saved = SceneFile::load(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(saved, scene);
+    }
+
+    #[test]
+    fn unknown_shape() {
+        let mut world = World::new();
+
+        // A cube that says it is a sphere, so the shape can't be trusted
+        let tensor = Mat3::from_diagonal(Vec3::splat(1.0 / 6.0));
+        let mut body = SimulationBundle::new(
+            Velocity(Vec3::X),
+            Accelerator::ZERO,
+            AngularVelocity::ZERO,
+            Inertia::new(1.0, tensor),
+        );
+        body.spatial.transform.rotation = Quat::from_rotation_y(0.5);
+        world.spawn((body, Shape::Sphere { radius: 0.5 }));
+        world.spawn(SimulationBundle::new(
+            Velocity::ZERO,
+            Accelerator::ZERO,
+            AngularVelocity::ZERO,
+            Shape::Cylinder {
+                axis: Axis::X,
+                height: 1.0,
+                radius: 0.1,
+            }
+            .inertia(2.0),
+        ));
+
+        let scene = SceneFile::from_world(&mut world);
+
+        assert_eq!(
+            scene.bodies[0].inertia,
+            SceneInertia::Tensor { mass: 1.0, tensor }
+        );
+        // Without a Shape component the cylinder is only known by its tensor as well
+        assert!(matches!(
+            scene.bodies[1].inertia,
+            SceneInertia::Tensor { mass, .. } if mass == 2.0
+        ));
+    }
+}
@@ -0,0 +1,378 @@
+use bevy::{
+    ecs::{
+        component::Component,
+        entity::Entity,
+        event::EventReader,
+        query::{With, Without},
+        system::{Commands, Local, Query, Res, Resource},
+    },
+    math::Vec3,
+    time::Time,
+    utils::{HashMap, HashSet},
+};
+
+use crate::{
+    collisions::CollisionEvent,
+    components::{
+        force::{ForceAccumulator, ImpulseQueue},
+        velocity::{AngularVelocity, Velocity},
+        Simulated,
+    },
+    reset::{PerturbBody, ResetSimulation},
+};
+
+/// Marks objects that have come to rest, the integrator skips them until they are woken up
+///
+/// Removed again as soon as anything disturbs the object, see [wake_up].
+#[derive(Component, Debug)]
+pub struct Sleeping;
+
+/// When objects are put to sleep
+#[derive(Resource, Debug, Clone, Copy, PartialEq)]
+pub struct SleepSettings {
+    /// Highest speed in m/s that still counts as resting
+    pub linear_speed: f32,
+
+    /// Highest angular speed in radians per second that still counts as resting
+    pub angular_speed: f32,
+
+    /// Seconds an object has to rest before it falls asleep, 0 disables sleeping
+    pub time: f32,
+}
+
+impl Default for SleepSettings {
+    fn default() -> Self {
+        Self {
+            linear_speed: 0.05,
+            angular_speed: 0.05,
+            time: 1.0,
+        }
+    }
+}
+
+/// Puts every object that has been resting for long enough to sleep
+///
+/// Sleeping objects are stopped completely, so they don't creep along at the threshold speed.
+#[allow(clippy::type_complexity)]
+pub fn fall_asleep(
+    mut cmds: Commands,
+    time: Res<Time>,
+    settings: Res<SleepSettings>,
+    mut resting: Local<HashMap<Entity, f32>>,
+    mut query: Query<
+        (Entity, &mut Velocity, &mut AngularVelocity),
+        (With<Simulated>, Without<Sleeping>),
+    >,
+) {
+    if settings.time <= 0.0 {
+        resting.clear();
+        return;
+    }
+
+    let mut still = HashMap::new();
+
+    for (entity, mut vel, mut angvel) in query.iter_mut() {
+        if vel.0.length() > settings.linear_speed || angvel.0.length() > settings.angular_speed {
+            continue;
+        }
+
+        let rested = resting.get(&entity).copied().unwrap_or(0.0) + time.delta_seconds();
+
+        if rested < settings.time {
+            still.insert(entity, rested);
+            continue;
+        }
+
+        vel.0 = Vec3::ZERO;
+        angvel.0 = Vec3::ZERO;
+        cmds.entity(entity).insert(Sleeping);
+    }
+
+    // Only keep the objects that are still resting, anything that moved starts over
+    *resting = still;
+}
+
+/// Wakes up every sleeping object that was disturbed, or every one of them on a reset
+///
+/// An object is disturbed when it collided or was perturbed, has impulses queued or forces
+/// accumulated, or was given a speed above the [SleepSettings] from outside. Gravity isn't applied
+/// to sleeping objects, so only pushes from outside count. Runs right before the integrator, so the
+/// object moves on the same step it was woken.
+#[allow(clippy::type_complexity)]
+pub fn wake_up(
+    mut cmds: Commands,
+    settings: Res<SleepSettings>,
+    mut collisions: EventReader<CollisionEvent>,
+    mut perturbed: EventReader<PerturbBody>,
+    mut resets: EventReader<ResetSimulation>,
+    sleeping: Query<
+        (
+            Entity,
+            &Velocity,
+            &AngularVelocity,
+            &ForceAccumulator,
+            Option<&ImpulseQueue>,
+        ),
+        (With<Simulated>, With<Sleeping>),
+    >,
+) {
+    if resets.read().count() > 0 {
+        collisions.clear();
+        perturbed.clear();
+
+        for (entity, ..) in sleeping.iter() {
+            cmds.entity(entity).remove::<Sleeping>();
+        }
+        return;
+    }
+
+    let touched: HashSet<Entity> = collisions
+        .read()
+        .flat_map(|ev| [ev.a, ev.b])
+        .chain(perturbed.read().map(|PerturbBody(entity)| *entity))
+        .collect();
+
+    for (entity, vel, angvel, forces, queue) in sleeping.iter() {
+        let pushed = forces.force != Vec3::ZERO
+            || forces.torque != Vec3::ZERO
+            || queue.is_some_and(|queue| !queue.0.is_empty());
+        let moving =
+            vel.0.length() > settings.linear_speed || angvel.0.length() > settings.angular_speed;
+
+        if pushed || moving || touched.contains(&entity) {
+            cmds.entity(entity).remove::<Sleeping>();
+        }
+    }
+}
+
+#[cfg(test)]
+mod resting {
+    use std::time::Duration;
+
+    use bevy::{
+        app::{App, Update},
+        ecs::{entity::Entity, event::Events, schedule::IntoSystemConfigs},
+        math::Vec3,
+        time::Time,
+        transform::components::Transform,
+    };
+
+    use super::{fall_asleep, wake_up, SleepSettings, Sleeping};
+    use crate::{
+        apply_impulses,
+        collisions::CollisionEvent,
+        components::{
+            acceleration::Accelerator,
+            force::{Force, ForceAccumulator, Impulse, ImpulseQueue},
+            inertia::Inertia,
+            velocity::{AngularVelocity, Velocity},
+            SimulationBundle,
+        },
+        forces::gravity,
+        reset::{perturb, PerturbBody, Perturbation, ResetSimulation},
+        resources::{ForceToggles, PhysicsSettings, UpAxis},
+        update_simulated,
+    };
+
+    fn app() -> App {
+        let mut app = App::new();
+        let mut time = Time::<()>::default();
+        time.advance_by(Duration::from_millis(100));
+
+        app.insert_resource(time);
+        app.insert_resource(PhysicsSettings {
+            max_delta: None,
+            ..Default::default()
+        });
+        app.init_resource::<SleepSettings>();
+        app.init_resource::<Perturbation>();
+        app.add_event::<CollisionEvent>();
+        app.add_event::<ResetSimulation>();
+        app.add_event::<PerturbBody>();
+        app.add_systems(
+            Update,
+            (
+                perturb,
+                wake_up,
+                apply_impulses,
+                update_simulated,
+                fall_asleep,
+            )
+                .chain(),
+        );
+
+        app
+    }
+
+    fn body(vel: Vec3) -> SimulationBundle {
+        SimulationBundle::new(
+            Velocity(vel),
+            Accelerator::ZERO,
+            AngularVelocity::ZERO,
+            Inertia::sphere(1.0, 1.0),
+        )
+    }
+
+    /// Spawns a still object and lets it fall asleep
+    fn asleep(app: &mut App) -> Entity {
+        let still = app
+            .world_mut()
+            .spawn((body(Vec3::ZERO), ImpulseQueue::default()))
+            .id();
+
+        for _ in 0..11 {
+            app.update();
+        }
+        assert!(app.world().get::<Sleeping>(still).is_some());
+
+        still
+    }
+
+    /// Wakes up on the next step and moves on it as well
+    fn assert_woken(app: &mut App, body: Entity) {
+        let pos = app.world().get::<Transform>(body).unwrap().translation;
+        app.update();
+
+        let world = app.world();
+        assert!(world.get::<Sleeping>(body).is_none());
+        assert_ne!(world.get::<Transform>(body).unwrap().translation, pos);
+    }
+
+    #[test]
+    fn settles_and_wakes() {
+        let mut app = app();
+        let creeping = app.world_mut().spawn(body(Vec3::X * 0.01)).id();
+        let moving = app.world_mut().spawn(body(Vec3::X)).id();
+
+        // Not a full second of rest yet
+        for _ in 0..9 {
+            app.update();
+        }
+        assert!(app.world().get::<Sleeping>(creeping).is_none());
+
+        app.update();
+        app.update();
+
+        let world = app.world();
+        assert!(world.get::<Sleeping>(creeping).is_some());
+        assert!(world.get::<Sleeping>(moving).is_none());
+        assert_eq!(world.get::<Velocity>(creeping).unwrap().0, Vec3::ZERO);
+
+        // Stays put while nothing disturbs it
+        let pos = world.get::<Transform>(creeping).unwrap().translation;
+        app.update();
+        assert_eq!(
+            app.world().get::<Transform>(creeping).unwrap().translation,
+            pos
+        );
+
+        app.world_mut()
+            .resource_mut::<Events<CollisionEvent>>()
+            .send(CollisionEvent {
+                a: moving,
+                b: creeping,
+                point: pos,
+                normal: Vec3::X,
+                impulse: 1.0,
+            });
+        app.update();
+
+        assert!(app.world().get::<Sleeping>(creeping).is_none());
+    }
+
+    #[test]
+    fn velocity_set() {
+        let mut app = app();
+        let body = asleep(&mut app);
+
+        app.world_mut().get_mut::<Velocity>(body).unwrap().0 = Vec3::Y;
+        assert_woken(&mut app, body);
+    }
+
+    #[test]
+    fn impulse() {
+        let mut app = app();
+        let body = asleep(&mut app);
+
+        app.world_mut()
+            .get_mut::<ImpulseQueue>(body)
+            .unwrap()
+            .0
+            .push(Impulse {
+                offset: Vec3::ZERO,
+                impulse: Vec3::X,
+            });
+        assert_woken(&mut app, body);
+    }
+
+    #[test]
+    fn forced() {
+        let mut app = app();
+        let body = asleep(&mut app);
+
+        app.world_mut()
+            .get_mut::<ForceAccumulator>(body)
+            .unwrap()
+            .add_force(Force(Vec3::X));
+        assert_woken(&mut app, body);
+    }
+
+    #[test]
+    fn perturbed() {
+        let mut app = app();
+        let body = asleep(&mut app);
+
+        // Far below the resting speed, but still a deliberate nudge
+        app.world_mut()
+            .resource_mut::<Events<PerturbBody>>()
+            .send(PerturbBody(body));
+        assert_woken(&mut app, body);
+    }
+
+    #[test]
+    fn disabled() {
+        let mut app = app();
+        app.insert_resource(SleepSettings {
+            time: 0.0,
+            ..Default::default()
+        });
+        let still = app.world_mut().spawn(body(Vec3::ZERO)).id();
+
+        for _ in 0..100 {
+            app.update();
+        }
+
+        assert!(app.world().get::<Sleeping>(still).is_none());
+    }
+
+    #[test]
+    fn stays_asleep_under_gravity() {
+        let mut app = app();
+        app.insert_resource(PhysicsSettings {
+            max_delta: None,
+            gravity: None,
+            ..Default::default()
+        });
+        app.init_resource::<ForceToggles>();
+        app.init_resource::<UpAxis>();
+        app.add_systems(Update, gravity.before(wake_up));
+
+        // Resting on something that holds it up
+        let resting = app
+            .world_mut()
+            .spawn((body(Vec3::ZERO), ImpulseQueue::default(), Sleeping))
+            .id();
+
+        for _ in 0..20 {
+            app.update();
+        }
+
+        let world = app.world();
+        assert!(world.get::<Sleeping>(resting).is_some());
+        assert_eq!(world.get::<Velocity>(resting).unwrap().0, Vec3::ZERO);
+        assert_eq!(
+            world.get::<Transform>(resting).unwrap().translation,
+            Vec3::ZERO
+        );
+    }
+}
@@ -0,0 +1,188 @@
+use bevy::{
+    ecs::{
+        bundle::Bundle, entity::Entity, query::With, system::Commands, world::Command, world::World,
+    },
+    math::{UVec3, Vec3},
+    transform::components::Transform,
+};
+
+use crate::{components::Simulated, resources::MaxBodies};
+
+/// Spawns a simulated object, unless there already are [MaxBodies] of them
+///
+/// The bundle should contain [Simulated], like a
+/// [SimulationBundle](crate::components::SimulationBundle) does, for it to count towards the cap.
+/// Queued like any other command with `commands.add(SpawnBody(bundle))`, and refused with a
+/// warning when the cap is reached. Without the [MaxBodies] resource there is no cap.
+#[derive(Debug)]
+pub struct SpawnBody<B: Bundle>(pub B);
+
+impl<B: Bundle> Command for SpawnBody<B> {
+    fn apply(self, world: &mut World) {
+        if let Some(&MaxBodies(max)) = world.get_resource::<MaxBodies>() {
+            let bodies = world
+                .query_filtered::<(), With<Simulated>>()
+                .iter(world)
+                .count();
+
+            if bodies >= max {
+                log::warn!("Not spawning another object, already at the limit of {max}");
+                return;
+            }
+        }
+
+        world.spawn(self.0);
+    }
+}
+
+/// Gets the points of a `dims` sized grid, `spacing` apart along each axis
+///
+/// Starts at the origin and goes in the positive direction of every axis, x fastest.
+pub fn grid_positions(dims: UVec3, spacing: Vec3) -> impl Iterator<Item = Vec3> {
+    (0..dims.z).flat_map(move |z| {
+        (0..dims.y)
+            .flat_map(move |y| (0..dims.x).map(move |x| UVec3::new(x, y, z).as_vec3() * spacing))
+    })
+}
+
+/// Spawns an object from `template` at every point of [grid_positions], for lattices and stress
+/// tests
+///
+/// The [Transform] of each object is replaced with one at its point, everything else is whatever
+/// `template` returns. Spawned right away rather than through [SpawnBody], so [MaxBodies] doesn't
+/// apply.
+pub fn spawn_grid<B: Bundle>(
+    cmds: &mut Commands,
+    dims: UVec3,
+    spacing: Vec3,
+    template: impl Fn() -> B,
+) -> Vec<Entity> {
+    grid_positions(dims, spacing)
+        .map(|pos| {
+            cmds.spawn(template())
+                .insert(Transform::from_translation(pos))
+                .id()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod cap {
+    use bevy::ecs::{query::With, system::Commands, world::CommandQueue, world::World};
+
+    use super::SpawnBody;
+    use crate::{
+        components::{
+            acceleration::Accelerator,
+            inertia::Inertia,
+            velocity::{AngularVelocity, Velocity},
+            Simulated, SimulationBundle,
+        },
+        resources::MaxBodies,
+    };
+
+    fn body() -> SimulationBundle {
+        SimulationBundle::new(
+            Velocity::ZERO,
+            Accelerator::ZERO,
+            AngularVelocity::ZERO,
+            Inertia::sphere(1.0, 1.0),
+        )
+    }
+
+    /// Tries to spawn `count` objects and returns how many simulated objects there are after
+    fn spawn_many(world: &mut World, count: usize) -> usize {
+        let mut queue = CommandQueue::default();
+        let mut cmds = Commands::new(&mut queue, world);
+
+        for _ in 0..count {
+            cmds.add(SpawnBody(body()));
+        }
+        queue.apply(world);
+
+        world
+            .query_filtered::<(), With<Simulated>>()
+            .iter(world)
+            .count()
+    }
+
+    #[test]
+    fn stops_at_cap() {
+        let mut world = World::new();
+        world.insert_resource(MaxBodies(10));
+
+        // Spawned directly, still counts towards the cap
+        world.spawn(body());
+
+        assert_eq!(spawn_many(&mut world, 5), 6);
+        assert_eq!(spawn_many(&mut world, 20), 10);
+        assert_eq!(spawn_many(&mut world, 1), 10);
+
+        // Other entities don't count
+        world.spawn_empty();
+        assert_eq!(spawn_many(&mut world, 1), 10);
+    }
+
+    #[test]
+    fn uncapped() {
+        let mut world = World::new();
+
+        assert_eq!(spawn_many(&mut world, 50), 50);
+    }
+}
+
+#[cfg(test)]
+mod grid {
+    use bevy::{
+        ecs::{system::Commands, world::CommandQueue, world::World},
+        math::{UVec3, Vec3},
+        transform::components::Transform,
+    };
+
+    use super::spawn_grid;
+    use crate::components::{
+        acceleration::Accelerator,
+        inertia::Inertia,
+        velocity::{AngularVelocity, Velocity},
+        SimulationBundle,
+    };
+
+    #[test]
+    fn two_by_two() {
+        let mut world = World::new();
+        let mut queue = CommandQueue::default();
+        let mut cmds = Commands::new(&mut queue, &world);
+
+        let entities = spawn_grid(&mut cmds, UVec3::splat(2), Vec3::new(1.0, 2.0, 3.0), || {
+            SimulationBundle::new(
+                Velocity(Vec3::X),
+                Accelerator::ZERO,
+                AngularVelocity::ZERO,
+                Inertia::sphere(0.5, 1.0),
+            )
+        });
+        queue.apply(&mut world);
+
+        assert_eq!(entities.len(), 8);
+
+        let positions: Vec<Vec3> = entities
+            .iter()
+            .map(|&e| world.get::<Transform>(e).unwrap().translation)
+            .collect();
+
+        let mut expected = Vec::new();
+        for z in [0.0, 3.0] {
+            for y in [0.0, 2.0] {
+                for x in [0.0, 1.0] {
+                    expected.push(Vec3::new(x, y, z));
+                }
+            }
+        }
+        assert_eq!(positions, expected);
+
+        // Everything else comes from the template
+        for &e in &entities {
+            assert_eq!(world.get::<Velocity>(e).unwrap().0, Vec3::X);
+        }
+    }
+}
@@ -0,0 +1,175 @@
+use std::time::Duration;
+
+use bevy::{
+    ecs::{
+        query::With,
+        system::{Query, Res, ResMut, Resource},
+    },
+    time::{Fixed, Time},
+};
+
+use crate::{
+    components::{velocity::Velocity, Simulated},
+    resources::PhysicsSettings,
+};
+
+/// Shrinks the fixed timestep while anything moves fast, so more steps run within a frame
+///
+/// Every step the timestep is set to `cfl_factor * min_feature / max_speed`, capped at the step
+/// of the [PhysicsSettings], so the fastest object moves at most a fraction of the smallest
+/// feature per step. Slow scenes keep running at the step of the settings. Only used when
+/// inserted, and takes over the fixed timestep from the settings while it exists.
+#[derive(Resource, Debug, Clone, Copy, PartialEq)]
+pub struct AdaptiveTimestep {
+    /// Shortest step in seconds, so a runaway object can't freeze the simulation
+    pub min_dt: f32,
+
+    /// Fraction of the smallest feature the fastest object may move in a step
+    pub cfl_factor: f32,
+
+    /// Size in meters of the smallest object or gap that shouldn't be skipped over
+    pub min_feature: f32,
+}
+
+impl AdaptiveTimestep {
+    /// Gets the timestep in seconds when the fastest object moves at `max_speed`, at most
+    /// `max_dt`
+    #[must_use]
+    pub fn timestep(&self, max_speed: f32, max_dt: f32) -> f32 {
+        if max_speed <= 0.0 {
+            return max_dt;
+        }
+
+        (self.cfl_factor * self.min_feature / max_speed).clamp(self.min_dt.min(max_dt), max_dt)
+    }
+}
+
+impl Default for AdaptiveTimestep {
+    fn default() -> Self {
+        Self {
+            min_dt: 1e-4,
+            cfl_factor: 0.5,
+            min_feature: 0.1,
+        }
+    }
+}
+
+/// Sets the fixed timestep from the fastest simulated object, when [AdaptiveTimestep] exists
+pub fn adapt_timestep(
+    adaptive: Option<Res<AdaptiveTimestep>>,
+    settings: Res<PhysicsSettings>,
+    mut fixed: ResMut<Time<Fixed>>,
+    query: Query<&Velocity, With<Simulated>>,
+) {
+    let (Some(adaptive), Some(max_step)) = (adaptive, settings.step()) else {
+        return;
+    };
+
+    let max_speed = query.iter().map(|vel| vel.0.length()).fold(0.0, f32::max);
+    let dt = adaptive.timestep(max_speed, max_step.as_secs_f32());
+
+    if let Ok(dt) = Duration::try_from_secs_f32(dt) {
+        if !dt.is_zero() {
+            fixed.set_timestep(dt);
+        }
+    }
+}
+
+#[cfg(test)]
+mod adaptive {
+    use std::time::Duration;
+
+    use bevy::{
+        app::{App, FixedUpdate},
+        asset::{AssetApp, AssetPlugin},
+        ecs::system::{ResMut, Resource},
+        gizmos::GizmoPlugin,
+        math::Vec3,
+        render::render_resource::Shader,
+        time::TimeUpdateStrategy,
+        MinimalPlugins,
+    };
+    use float_cmp::assert_approx_eq;
+
+    use super::AdaptiveTimestep;
+    use crate::{
+        components::{
+            acceleration::Accelerator,
+            inertia::Inertia,
+            velocity::{AngularVelocity, Velocity},
+            SimulationBundle,
+        },
+        resources::PhysicsSettings,
+        SimulatiorPlugin,
+    };
+
+    #[derive(Resource, Default)]
+    struct Steps(u32);
+
+    fn settings() -> AdaptiveTimestep {
+        AdaptiveTimestep {
+            min_dt: 1e-3,
+            cfl_factor: 0.5,
+            min_feature: 1.0,
+        }
+    }
+
+    #[test]
+    fn formula() {
+        let settings = settings();
+
+        assert_eq!(settings.timestep(0.0, 0.05), 0.05);
+        assert_eq!(settings.timestep(1.0, 0.05), 0.05);
+        assert_approx_eq!(f32, settings.timestep(100.0, 0.05), 0.005);
+        assert_eq!(settings.timestep(1e6, 0.05), 1e-3);
+
+        // Capped by a shorter step from the settings
+        assert_eq!(settings.timestep(1.0, 0.01), 0.01);
+        assert_eq!(settings.timestep(1e6, 1e-4), 1e-4);
+    }
+
+    /// Counts the fixed steps over ten frames of 100 ms with a single body at `speed`
+    fn steps_at(speed: f32) -> u32 {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, AssetPlugin::default()));
+        app.init_asset::<Shader>();
+        app.add_plugins((GizmoPlugin, SimulatiorPlugin::default()));
+        app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(
+            100,
+        )));
+        app.insert_resource(PhysicsSettings {
+            gravity: Some(Vec3::ZERO),
+            timestep: 0.05,
+            ..Default::default()
+        });
+        app.insert_resource(settings());
+        app.init_resource::<Steps>();
+        app.add_systems(FixedUpdate, |mut steps: ResMut<Steps>| steps.0 += 1);
+
+        app.world_mut().spawn(SimulationBundle::new(
+            Velocity(Vec3::X * speed),
+            Accelerator::ZERO,
+            AngularVelocity::ZERO,
+            Inertia::sphere(0.1, 1.0),
+        ));
+
+        for _ in 0..10 {
+            app.update();
+        }
+
+        app.world().resource::<Steps>().0
+    }
+
+    #[test]
+    fn fast_body_substeps() {
+        let slow = steps_at(1.0);
+        let fast = steps_at(100.0);
+
+        // Ten times shorter steps, so close to ten times as many of them
+        assert!(slow > 0);
+        assert!(
+            fast > slow * 5,
+            "{fast} steps for the fast body, {slow} for the slow one"
+        );
+    }
+}
@@ -0,0 +1,185 @@
+use std::collections::VecDeque;
+
+use bevy::{
+    color::Color,
+    ecs::{
+        component::Component,
+        query::With,
+        system::{Query, Res},
+    },
+    gizmos::gizmos::Gizmos,
+    math::Vec3,
+    transform::components::Transform,
+};
+
+use crate::{
+    components::{BodyColor, Simulated},
+    resources::SimClock,
+    vector_arrows::GizmosEnabled,
+};
+
+/// Seconds of path kept by a [Trail] without a [TrailDuration]
+pub const DEFAULT_TRAIL_DURATION: f32 = 5.0;
+
+/// Draws the path the object took, recorded at every physics step
+#[derive(Component, Debug, Default, Clone, PartialEq)]
+pub struct Trail {
+    /// Simulated time and position of every recorded step, oldest first
+    points: VecDeque<(f32, Vec3)>,
+}
+
+/// How many seconds of simulated time a [Trail] reaches back
+///
+/// Measured in time rather than points, so the trail covers the same stretch of the path no matter
+/// how long the steps are.
+#[derive(Component, Debug, Clone, Copy, PartialEq)]
+pub struct TrailDuration(pub f32);
+
+impl Trail {
+    /// Adds the position at simulated time `now`, dropping every point older than `duration`
+    ///
+    /// Points from after `now` are dropped as well, as the clock only goes backwards on a reset.
+    pub fn record(&mut self, now: f32, position: Vec3, duration: f32) {
+        while self.points.back().is_some_and(|(time, _)| *time > now) {
+            self.points.pop_back();
+        }
+
+        while self
+            .points
+            .front()
+            .is_some_and(|(time, _)| now - *time > duration)
+        {
+            self.points.pop_front();
+        }
+
+        self.points.push_back((now, position));
+    }
+
+    /// Gets the simulated time and position of every point, oldest first
+    pub fn points(&self) -> impl Iterator<Item = (f32, Vec3)> + '_ {
+        self.points.iter().copied()
+    }
+
+    /// Forgets the whole path
+    pub fn clear(&mut self) {
+        self.points.clear();
+    }
+}
+
+/// Adds the current position to the [Trail] of every object that has one
+pub fn record_trail(
+    clock: Res<SimClock>,
+    mut query: Query<(&Transform, &mut Trail, Option<&TrailDuration>), With<Simulated>>,
+) {
+    for (trans, mut trail, duration) in query.iter_mut() {
+        let duration = duration.map_or(DEFAULT_TRAIL_DURATION, |d| d.0);
+
+        trail.record(clock.elapsed(), trans.translation, duration);
+    }
+}
+
+/// Draws every [Trail], in the [BodyColor] of the object if it has one
+pub fn draw_trail(
+    enabled: Res<GizmosEnabled>,
+    query: Query<(&Trail, Option<&BodyColor>), With<Simulated>>,
+    mut gizmos: Gizmos,
+) {
+    if !enabled.0 {
+        return;
+    }
+
+    for (trail, color) in query.iter() {
+        let color = color.map_or(Color::srgb(0.5, 0.5, 0.5), |c| c.0);
+
+        gizmos.linestrip(trail.points().map(|(_, point)| point), color);
+    }
+}
+
+#[cfg(test)]
+mod duration {
+    use std::time::Duration;
+
+    use bevy::{
+        app::App,
+        asset::{AssetApp, AssetPlugin},
+        gizmos::GizmoPlugin,
+        math::Vec3,
+        render::render_resource::Shader,
+        time::TimeUpdateStrategy,
+        MinimalPlugins,
+    };
+
+    use super::{Trail, TrailDuration};
+    use crate::{
+        components::{
+            acceleration::Accelerator,
+            inertia::Inertia,
+            velocity::{AngularVelocity, Velocity},
+            SimulationBundle,
+        },
+        resources::{PhysicsSettings, SimClock},
+        SimulatiorPlugin,
+    };
+
+    #[test]
+    fn window() {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, AssetPlugin::default()));
+        app.init_asset::<Shader>();
+        app.add_plugins((GizmoPlugin, SimulatiorPlugin::default()));
+        app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(
+            100,
+        )));
+        app.insert_resource(PhysicsSettings {
+            gravity: Some(Vec3::ZERO),
+            timestep: 0.02,
+            ..Default::default()
+        });
+
+        let body = app
+            .world_mut()
+            .spawn((
+                SimulationBundle::new(
+                    Velocity(Vec3::X),
+                    Accelerator::ZERO,
+                    AngularVelocity::ZERO,
+                    Inertia::sphere(0.1, 1.0),
+                ),
+                Trail::default(),
+                TrailDuration(0.5),
+            ))
+            .id();
+
+        // Two seconds, four times the duration
+        for _ in 0..20 {
+            app.update();
+        }
+
+        let now = app.world().resource::<SimClock>().elapsed();
+        assert!(now > 1.5);
+
+        let trail = app.world().get::<Trail>(body).unwrap();
+        let points: Vec<_> = trail.points().collect();
+
+        // One point every 20 ms within the half second, plus the newest
+        assert!(points.len() >= 25 && points.len() <= 26, "{points:?}");
+        assert!(points.iter().all(|(time, _)| now - time <= 0.5));
+
+        // Moving at 1 m/s, so the oldest point is half a meter back
+        let (first, last) = (points[0].1, points[points.len() - 1].1);
+        assert!((last.x - first.x - 0.5).abs() < 0.05);
+    }
+
+    #[test]
+    fn rewound() {
+        let mut trail = Trail::default();
+        for i in 0..10 {
+            trail.record(i as f32, Vec3::X * i as f32, 100.0);
+        }
+
+        trail.record(3.5, Vec3::Y, 100.0);
+
+        let times: Vec<_> = trail.points().map(|(time, _)| time).collect();
+        assert_eq!(times, vec![0.0, 1.0, 2.0, 3.0, 3.5]);
+    }
+}
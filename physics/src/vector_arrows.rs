@@ -1,19 +1,255 @@
 use bevy::{
-    color::Color,
-    ecs::{query::With, system::Query},
+    color::{Color, Mix},
+    ecs::{
+        component::Component,
+        event::EventReader,
+        query::With,
+        system::{Query, Res, Resource},
+    },
     gizmos::gizmos::Gizmos,
-    math::Vec3,
+    math::{Quat, Vec3},
+    time::Time,
     transform::components::Transform,
 };
 
-use crate::components::{self, Simulated};
+use crate::{
+    collisions::CollisionEvent,
+    components::{
+        self,
+        acceleration::Accelerator,
+        aero::AeroSurface,
+        force::{Force, LastStepForces},
+        inertia::Inertia,
+        BodyColor, Simulated,
+    },
+    resources::Wind,
+};
+
+/// Settings for the arrows drawn on every simulated object
+#[derive(Resource, Debug)]
+pub struct ArrowSettings {
+    /// Time constant in seconds of the filter applied to the velocity arrow of objects with a
+    /// [SmoothedVelocity], 0 disables the filtering
+    pub velocity_smoothing: f32,
+
+    /// How the length of the velocity arrow follows the speed
+    pub velocity_scale: ArrowScale,
+
+    /// Color of the velocity arrow for objects without a [BodyColor]
+    pub velocity_color: Color,
+
+    /// Colors every velocity arrow by its speed instead, over any [BodyColor]
+    pub velocity_gradient: Option<SpeedGradient>,
+
+    /// Draws a line through every spinning object along its axis of rotation
+    pub spin_axis: bool,
+
+    /// Length in meters of the spin axis line, centered on the object
+    pub spin_axis_length: f32,
+
+    /// Draws a sphere at every contact point and an arrow along its normal, for the frame the
+    /// [CollisionEvent] fires
+    pub contacts: bool,
+
+    /// Colors of the contact gizmos, blended by the impulse in N*s instead of a speed
+    pub contact_gradient: SpeedGradient,
+}
+
+impl ArrowSettings {
+    /// Gets the color of the velocity arrow for an object moving at `speed`
+    ///
+    /// Sampled from the [SpeedGradient] if there is one, otherwise the [BodyColor] of the object
+    /// if it has one.
+    #[must_use]
+    pub fn velocity_color_for(&self, body: Option<&BodyColor>, speed: f32) -> Color {
+        match (self.velocity_gradient, body) {
+            (Some(gradient), _) => gradient.sample(speed),
+            (None, Some(color)) => color.0,
+            (None, None) => self.velocity_color,
+        }
+    }
+}
+
+/// Blends from one color at rest to another at a set speed, for heatmap like arrows
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SpeedGradient {
+    pub slow: Color,
+    pub fast: Color,
+
+    /// Speed in m/s and above that gets the `fast` color
+    pub max_speed: f32,
+}
+
+impl SpeedGradient {
+    /// Gets the color at a speed, blended in the color space of `slow`
+    #[must_use]
+    pub fn sample(&self, speed: f32) -> Color {
+        let t = if self.max_speed > 0.0 {
+            (speed / self.max_speed).clamp(0.0, 1.0)
+        } else {
+            1.0
+        };
+
+        self.slow.mix(&self.fast, t)
+    }
+}
+
+impl Default for SpeedGradient {
+    fn default() -> Self {
+        Self {
+            slow: Color::srgb(0.0, 0.0, 1.0),
+            fast: Color::srgb(1.0, 0.0, 0.0),
+            max_speed: 100.0,
+        }
+    }
+}
+
+/// Switches every debug gizmo on or off at once, regardless of the other settings
+///
+/// Checked at the top of every gizmo system, useful for clean screenshots or to save some frame
+/// time.
+#[derive(Resource, Debug)]
+pub struct GizmosEnabled(pub bool);
 
+impl Default for GizmosEnabled {
+    fn default() -> Self {
+        Self(true)
+    }
+}
+
+/// Maps the magnitude of a vector to the length of its arrow
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ArrowScale {
+    /// Length is the magnitude times a factor
+    Linear(f32),
+
+    /// Length is `base * ln(1 + magnitude)`, so fast and slow objects both get readable arrows
+    Logarithmic { base: f32 },
+}
+
+impl ArrowScale {
+    /// Gets the arrow length for a magnitude
+    #[must_use]
+    pub fn length(&self, magnitude: f32) -> f32 {
+        match *self {
+            ArrowScale::Linear(factor) => magnitude * factor,
+            ArrowScale::Logarithmic { base } => base * magnitude.ln_1p(),
+        }
+    }
+
+    /// Scales a vector to be drawn as an arrow, keeping its direction
+    #[must_use]
+    pub fn apply(&self, vector: Vec3) -> Vec3 {
+        vector.normalize_or_zero() * self.length(vector.length())
+    }
+}
+
+/// Gets the ends of a line `length` long through `center` along the axis of `angvel`
+///
+/// [None] when not spinning, as there is no axis to draw.
+#[must_use]
+pub fn spin_axis_line(
+    center: Vec3,
+    angvel: &components::velocity::AngularVelocity,
+    length: f32,
+) -> Option<(Vec3, Vec3)> {
+    let half = angvel.axis()? * length / 2.0;
+
+    Some((center - half, center + half))
+}
+
+/// Gets the acceleration that moved an object in the last step
+///
+/// The [LastStepForces] over the mass plus the [Accelerator], the same sum the integrator uses.
+/// Only the [Accelerator] for objects without [LastStepForces], which don't keep the forces around.
+#[must_use]
+pub fn net_acceleration(
+    acc: Option<&Accelerator>,
+    last: Option<&LastStepForces>,
+    inertia: &Inertia,
+) -> Vec3 {
+    let acc = acc.map_or(Vec3::ZERO, |acc| acc.0);
+
+    match last {
+        Some(last) => acc + inertia.get_acceleration(Force(last.force)),
+        None => acc,
+    }
+}
+
+/// Low pass filtered velocity, drawn instead of the actual velocity to reduce flickering
+///
+/// Only used for drawing, the simulation always uses the actual velocity.
+#[derive(Component, Debug, Default)]
+pub struct SmoothedVelocity(pub Vec3);
+
+impl SmoothedVelocity {
+    /// Moves the filtered value towards `target`
+    ///
+    /// After `smoothing` seconds of a constant target about 63% of the difference is gone.
+    pub fn update(&mut self, target: Vec3, smoothing: f32, delta: f32) {
+        if smoothing <= 0.0 {
+            self.0 = target;
+            return;
+        }
+
+        let alpha = 1.0 - (-delta / smoothing).exp();
+        self.0 += (target - self.0) * alpha;
+    }
+}
+
+impl Default for ArrowSettings {
+    fn default() -> Self {
+        Self {
+            velocity_smoothing: 0.1,
+            velocity_scale: ArrowScale::Linear(1.0),
+            velocity_color: Color::srgb(0.65, 0.0, 0.0),
+            velocity_gradient: None,
+            spin_axis: false,
+            spin_axis_length: 2.0,
+            contacts: false,
+            contact_gradient: SpeedGradient {
+                slow: Color::srgb(1.0, 1.0, 0.0),
+                fast: Color::srgb(1.0, 0.0, 0.0),
+                max_speed: 10.0,
+            },
+        }
+    }
+}
+
+/// Updates every [SmoothedVelocity] towards the current velocity
+pub fn smooth_velocity(
+    time: Res<Time>,
+    settings: Res<ArrowSettings>,
+    mut query: Query<(&components::velocity::Velocity, &mut SmoothedVelocity), With<Simulated>>,
+) {
+    for (vel, mut smoothed) in query.iter_mut() {
+        smoothed.update(vel.0, settings.velocity_smoothing, time.delta_seconds());
+    }
+}
+
+#[allow(clippy::type_complexity)]
 pub fn velocity(
-    query: Query<(&Transform, &components::velocity::Velocity), With<Simulated>>,
+    enabled: Res<GizmosEnabled>,
+    settings: Res<ArrowSettings>,
+    query: Query<
+        (
+            &Transform,
+            &components::velocity::Velocity,
+            Option<&SmoothedVelocity>,
+            Option<&BodyColor>,
+        ),
+        With<Simulated>,
+    >,
     mut gizmos: Gizmos,
 ) {
-    for (trans, vel) in query.iter() {
-        let (pos, vel) = (trans.translation, vel.0);
+    if !enabled.0 {
+        return;
+    }
+
+    for (trans, vel, smoothed, color) in query.iter() {
+        let shown = smoothed.map_or(vel.0, |s| s.0);
+        let vel = settings.velocity_scale.apply(shown);
+        let pos = trans.translation;
 
         // skip drawing if the velocity is 0
         if vel == Vec3::ZERO {
@@ -23,17 +259,32 @@ pub fn velocity(
         gizmos.arrow(
             pos,       // from object center
             pos + vel, // to object center + acceleration
-            Color::srgb(0.65, 0.0, 0.0),
+            settings.velocity_color_for(color, shown.length()),
         );
     }
 }
 
+/// Draws the [net_acceleration] of every simulated object
+#[allow(clippy::type_complexity)]
 pub fn acceleration(
-    query: Query<(&Transform, &components::acceleration::Accelerator), With<Simulated>>,
+    enabled: Res<GizmosEnabled>,
+    query: Query<
+        (
+            &Transform,
+            &Inertia,
+            Option<&Accelerator>,
+            Option<&LastStepForces>,
+        ),
+        With<Simulated>,
+    >,
     mut gizmos: Gizmos,
 ) {
-    for (trans, acc) in query.iter() {
-        let (pos, acc) = (trans.translation, acc.0);
+    if !enabled.0 {
+        return;
+    }
+
+    for (trans, inertia, acc, last) in query.iter() {
+        let (pos, acc) = (trans.translation, net_acceleration(acc, last, inertia));
 
         // skip drawing if the acceleration is 0
         if acc == Vec3::ZERO {
@@ -47,3 +298,490 @@ pub fn acceleration(
         );
     }
 }
+
+/// Draws the axis every simulated object spins around, when enabled in the [ArrowSettings]
+pub fn spin_axis(
+    enabled: Res<GizmosEnabled>,
+    settings: Res<ArrowSettings>,
+    query: Query<(&Transform, &components::velocity::AngularVelocity), With<Simulated>>,
+    mut gizmos: Gizmos,
+) {
+    if !enabled.0 || !settings.spin_axis {
+        return;
+    }
+
+    for (trans, angvel) in query.iter() {
+        let Some((start, end)) =
+            spin_axis_line(trans.translation, angvel, settings.spin_axis_length)
+        else {
+            continue;
+        };
+
+        gizmos.line(start, end, Color::srgb(0.65, 0.0, 0.65));
+    }
+}
+
+/// Draws every [CollisionEvent] of this frame, when enabled in the [ArrowSettings]
+///
+/// A small sphere at the contact point and an arrow a meter long along the normal, both colored
+/// by the impulse.
+pub fn contacts(
+    enabled: Res<GizmosEnabled>,
+    settings: Res<ArrowSettings>,
+    mut events: EventReader<CollisionEvent>,
+    mut gizmos: Gizmos,
+) {
+    // Marked as read either way, so old collisions don't show up once enabled
+    if !enabled.0 || !settings.contacts {
+        events.clear();
+        return;
+    }
+
+    for ev in events.read() {
+        let (tip, color) = contact_arrow(ev, &settings.contact_gradient);
+
+        gizmos.sphere(ev.point, Quat::IDENTITY, 0.05, color);
+        gizmos.arrow(ev.point, tip, color);
+    }
+}
+
+/// Gets the tip of the arrow drawn for a collision and its color, sampled by the impulse
+#[must_use]
+pub fn contact_arrow(ev: &CollisionEvent, gradient: &SpeedGradient) -> (Vec3, Color) {
+    (ev.point + ev.normal, gradient.sample(ev.impulse))
+}
+
+/// Draws an arc from the direction of the airflow to the forward axis of every [AeroSurface]
+///
+/// The arc spans the angle of attack, so it disappears when flying straight into the airflow.
+#[allow(clippy::type_complexity)]
+pub fn angle_of_attack(
+    enabled: Res<GizmosEnabled>,
+    wind: Res<Wind>,
+    query: Query<
+        (&Transform, &components::velocity::Velocity),
+        (With<AeroSurface>, With<Simulated>),
+    >,
+    mut gizmos: Gizmos,
+) {
+    if !enabled.0 {
+        return;
+    }
+
+    for (trans, vel) in query.iter() {
+        let airflow = vel.0 - wind.0;
+
+        if components::aero::angle_of_attack(trans.rotation, airflow) == 0.0 {
+            continue;
+        }
+
+        let pos = trans.translation;
+        gizmos.short_arc_3d_between(
+            pos,
+            pos + airflow.normalize(),
+            pos + trans.rotation * Vec3::X,
+            Color::srgb(0.0, 0.65, 0.0),
+        );
+    }
+}
+
+#[cfg(test)]
+mod smoothing {
+    use bevy::math::Vec3;
+    use float_cmp::assert_approx_eq;
+
+    use super::SmoothedVelocity;
+
+    #[test]
+    fn converges() {
+        let mut smoothed = SmoothedVelocity::default();
+        let target = Vec3::new(3.0, -1.0, 2.0);
+
+        for _ in 0..1000 {
+            smoothed.update(target, 0.1, 1.0 / 144.0);
+        }
+
+        assert_approx_eq!(
+            &[f32],
+            &smoothed.0.to_array(),
+            &target.to_array(),
+            epsilon = 1e-5
+        );
+    }
+
+    #[test]
+    fn lags_behind() {
+        let mut smoothed = SmoothedVelocity::default();
+
+        smoothed.update(Vec3::X, 0.1, 0.1);
+
+        // One time constant in, 1 - 1/e of the way there
+        assert_approx_eq!(f32, smoothed.0.x, 1.0 - (-1.0f32).exp());
+    }
+
+    #[test]
+    fn disabled() {
+        let mut smoothed = SmoothedVelocity::default();
+
+        smoothed.update(Vec3::X, 0.0, 0.01);
+
+        assert_eq!(smoothed.0, Vec3::X);
+    }
+}
+
+#[cfg(test)]
+mod spin_axis {
+    use bevy::math::Vec3;
+    use float_cmp::assert_approx_eq;
+
+    use super::spin_axis_line;
+    use crate::components::velocity::AngularVelocity;
+
+    #[test]
+    fn centered() {
+        let center = Vec3::new(1.0, 2.0, 3.0);
+        let (start, end) =
+            spin_axis_line(center, &AngularVelocity(Vec3::new(0.0, 3.0, 4.0)), 10.0).unwrap();
+
+        assert_approx_eq!(&[f32], &start.to_array(), &[1.0, -1.0, -1.0]);
+        assert_approx_eq!(&[f32], &end.to_array(), &[1.0, 5.0, 7.0]);
+    }
+
+    #[test]
+    fn not_spinning() {
+        assert_eq!(
+            spin_axis_line(Vec3::ONE, &AngularVelocity::ZERO, 10.0),
+            None
+        );
+    }
+}
+
+#[cfg(test)]
+mod all_forces {
+    use bevy::math::Vec3;
+    use float_cmp::assert_approx_eq;
+
+    use super::net_acceleration;
+    use crate::components::{acceleration::Accelerator, force::LastStepForces, inertia::Inertia};
+
+    #[test]
+    fn force_over_mass() {
+        let inertia = Inertia::sphere(1.0, 4.0);
+        let last = LastStepForces {
+            force: Vec3::new(8.0, -2.0, 0.0),
+            torque: Vec3::ZERO,
+        };
+
+        let shown = net_acceleration(Some(&Accelerator::ZERO), Some(&last), &inertia);
+        let expected = last.force / inertia.mass();
+
+        assert_approx_eq!(&[f32], &shown.to_array(), &expected.to_array());
+
+        // The accelerator is added on top, as in the integrator
+        let shown = net_acceleration(Some(&Accelerator(Vec3::Z)), Some(&last), &inertia);
+        assert_approx_eq!(&[f32], &shown.to_array(), &[2.0, -0.5, 1.0]);
+    }
+
+    #[test]
+    fn falls_back() {
+        let inertia = Inertia::sphere(1.0, 4.0);
+
+        assert_eq!(
+            net_acceleration(Some(&Accelerator(Vec3::Y)), None, &inertia),
+            Vec3::Y
+        );
+        assert_eq!(net_acceleration(None, None, &inertia), Vec3::ZERO);
+    }
+
+    #[test]
+    fn static_object() {
+        let last = LastStepForces {
+            force: Vec3::X * 100.0,
+            torque: Vec3::ZERO,
+        };
+
+        assert_eq!(
+            net_acceleration(None, Some(&last), &Inertia::static_body()),
+            Vec3::ZERO
+        );
+    }
+}
+
+#[cfg(test)]
+mod scale {
+    use bevy::math::Vec3;
+    use float_cmp::assert_approx_eq;
+
+    use super::ArrowScale;
+
+    #[test]
+    fn logarithmic() {
+        let scale = ArrowScale::Logarithmic { base: 2.0 };
+
+        assert_eq!(scale.length(0.0), 0.0);
+        assert_approx_eq!(f32, scale.length(1.0), 2.0 * 2f32.ln());
+
+        // Always growing, but never longer than the linear arrow with the same factor
+        let mut last = 0.0;
+        for speed in [0.01, 0.5, 1.0, 10.0, 100.0, 1e4, 1e8] {
+            let length = scale.length(speed);
+
+            assert!(
+                length > last,
+                "{length} at {speed} isn't longer than {last}"
+            );
+            assert!(length <= ArrowScale::Linear(2.0).length(speed));
+            last = length;
+        }
+
+        // The initial velocity in the demo scene stays a reasonable size
+        assert!(scale.length(Vec3::new(100.0, 100.0, 0.0).length()) < 10.0);
+    }
+
+    #[test]
+    fn keeps_direction() {
+        let scaled = ArrowScale::Logarithmic { base: 1.0 }.apply(Vec3::new(0.0, 3.0, 4.0));
+
+        assert_approx_eq!(&[f32], &scaled.normalize().to_array(), &[0.0, 0.6, 0.8]);
+        assert_eq!(ArrowScale::Linear(3.0).apply(Vec3::X), Vec3::X * 3.0);
+        assert_eq!(
+            ArrowScale::Logarithmic { base: 1.0 }.apply(Vec3::ZERO),
+            Vec3::ZERO
+        );
+    }
+}
+
+#[cfg(test)]
+mod colors {
+    use bevy::color::Color;
+
+    use super::ArrowSettings;
+    use crate::components::BodyColor;
+
+    #[test]
+    fn body_color() {
+        let settings = ArrowSettings::default();
+        let green = BodyColor(Color::srgb(0.0, 1.0, 0.0));
+
+        assert_eq!(settings.velocity_color_for(Some(&green), 1.0), green.0);
+    }
+
+    #[test]
+    fn fallback() {
+        let settings = ArrowSettings {
+            velocity_color: Color::WHITE,
+            ..Default::default()
+        };
+
+        assert_eq!(settings.velocity_color_for(None, 1.0), Color::WHITE);
+        assert_eq!(
+            ArrowSettings::default().velocity_color_for(None, 1.0),
+            Color::srgb(0.65, 0.0, 0.0)
+        );
+    }
+}
+
+#[cfg(test)]
+mod gradient {
+    use bevy::color::{Color, Srgba};
+    use float_cmp::assert_approx_eq;
+
+    use super::{ArrowSettings, SpeedGradient};
+    use crate::components::BodyColor;
+
+    fn rgb(color: Color) -> [f32; 3] {
+        let Srgba {
+            red, green, blue, ..
+        } = color.to_srgba();
+
+        [red, green, blue]
+    }
+
+    #[test]
+    fn blue_to_red() {
+        let gradient = SpeedGradient {
+            max_speed: 20.0,
+            ..Default::default()
+        };
+
+        assert_approx_eq!(&[f32], &rgb(gradient.sample(0.0)), &[0.0, 0.0, 1.0]);
+        assert_approx_eq!(&[f32], &rgb(gradient.sample(10.0)), &[0.5, 0.0, 0.5]);
+        assert_approx_eq!(&[f32], &rgb(gradient.sample(20.0)), &[1.0, 0.0, 0.0]);
+
+        // Past the max speed stays at the fast color
+        assert_approx_eq!(&[f32], &rgb(gradient.sample(500.0)), &[1.0, 0.0, 0.0]);
+    }
+
+    #[test]
+    fn over_body_color() {
+        let settings = ArrowSettings {
+            velocity_gradient: Some(SpeedGradient::default()),
+            ..Default::default()
+        };
+        let green = BodyColor(Color::srgb(0.0, 1.0, 0.0));
+
+        assert_approx_eq!(
+            &[f32],
+            &rgb(settings.velocity_color_for(Some(&green), 0.0)),
+            &[0.0, 0.0, 1.0]
+        );
+    }
+}
+
+#[cfg(test)]
+mod disabled {
+    use bevy::{
+        app::{App, PostUpdate},
+        asset::{AssetApp, AssetPlugin},
+        gizmos::GizmoPlugin,
+        math::Vec3,
+        render::render_resource::Shader,
+        MinimalPlugins,
+    };
+
+    use super::{acceleration, velocity, ArrowSettings, GizmosEnabled};
+    use crate::components::{
+        acceleration::Accelerator,
+        inertia::Inertia,
+        velocity::{AngularVelocity, Velocity},
+        SimulationBundle,
+    };
+
+    /// Runs the arrow systems once for a body whose arrows can't be drawn
+    ///
+    /// Drawing an arrow along a NaN vector fails glam's assertions, so this only returns if the
+    /// systems never got to drawing.
+    fn draw_broken_body(enabled: bool) {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, AssetPlugin::default()));
+        app.init_asset::<Shader>();
+        app.add_plugins(GizmoPlugin);
+        app.init_resource::<ArrowSettings>();
+        app.insert_resource(GizmosEnabled(enabled));
+        app.add_systems(PostUpdate, (velocity, acceleration));
+
+        app.world_mut().spawn(SimulationBundle::new(
+            Velocity(Vec3::NAN),
+            Accelerator(Vec3::NAN),
+            AngularVelocity::ZERO,
+            Inertia::sphere(1.0, 1.0),
+        ));
+
+        app.update();
+    }
+
+    #[test]
+    fn skips_drawing() {
+        draw_broken_body(false);
+    }
+
+    #[test]
+    #[should_panic]
+    fn draws_when_enabled() {
+        draw_broken_body(true);
+    }
+}
+
+#[cfg(test)]
+mod contacts {
+    use std::time::Duration;
+
+    use bevy::{
+        app::App,
+        asset::{AssetApp, AssetPlugin},
+        color::Color,
+        ecs::entity::Entity,
+        gizmos::GizmoPlugin,
+        math::Vec3,
+        render::render_resource::Shader,
+        time::TimeUpdateStrategy,
+        transform::components::Transform,
+        MinimalPlugins,
+    };
+
+    use super::{contact_arrow, ArrowSettings};
+    use crate::{
+        collisions::CollisionEvent,
+        components::{
+            acceleration::Accelerator,
+            inertia::Inertia,
+            shape::Shape,
+            velocity::{AngularVelocity, Velocity},
+            SimulationBundle,
+        },
+        resources::PhysicsSettings,
+        SimulatiorPlugin,
+    };
+
+    fn hit(impulse: f32) -> CollisionEvent {
+        CollisionEvent {
+            a: Entity::PLACEHOLDER,
+            b: Entity::PLACEHOLDER,
+            point: Vec3::new(1.0, 2.0, 3.0),
+            normal: Vec3::Y,
+            impulse,
+        }
+    }
+
+    #[test]
+    fn colored_by_impulse() {
+        let gradient = ArrowSettings::default().contact_gradient;
+
+        let (tip, soft) = contact_arrow(&hit(0.0), &gradient);
+        assert_eq!(tip, Vec3::new(1.0, 3.0, 3.0));
+        assert_eq!(soft, Color::srgb(1.0, 1.0, 0.0));
+
+        let (_, hard) = contact_arrow(&hit(1e3), &gradient);
+        assert_eq!(hard, Color::srgb(1.0, 0.0, 0.0));
+
+        let (_, between) = contact_arrow(&hit(5.0), &gradient);
+        assert_ne!(between, soft);
+        assert_ne!(between, hard);
+    }
+
+    #[test]
+    fn drawn_while_colliding() {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, AssetPlugin::default()));
+        app.init_asset::<Shader>();
+        app.add_plugins((GizmoPlugin, SimulatiorPlugin::default()));
+        app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(
+            20,
+        )));
+        app.insert_resource(PhysicsSettings {
+            gravity: Some(Vec3::ZERO),
+            ..Default::default()
+        });
+        app.insert_resource(ArrowSettings {
+            contacts: true,
+            ..Default::default()
+        });
+
+        let ball = |x: f32, vel: f32| {
+            let mut body = SimulationBundle::new(
+                Velocity(Vec3::X * vel),
+                Accelerator::ZERO,
+                AngularVelocity::ZERO,
+                Inertia::sphere(0.5, 1.0),
+            );
+            body.spatial.transform = Transform::from_xyz(x, 0.0, 0.0);
+
+            (body, Shape::Sphere { radius: 0.5 })
+        };
+        app.world_mut().spawn(ball(-0.6, 1.0));
+        app.world_mut().spawn(ball(0.6, -1.0));
+
+        // Overlapping from the start, so the contacts are drawn on the first frames
+        for _ in 0..10 {
+            app.update();
+        }
+
+        let vel: Vec<_> = app
+            .world_mut()
+            .query::<&Velocity>()
+            .iter(app.world())
+            .map(|vel| vel.0.x)
+            .collect();
+        assert!(vel.iter().any(|x| *x < 0.0) && vel.iter().any(|x| *x > 0.0));
+    }
+}
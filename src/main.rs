@@ -1,15 +1,13 @@
 use bevy::{
     app::{App, Startup},
     asset::AssetServer,
-    color::palettes::css::{BLACK, WHITE},
+    color::Color,
     diagnostic::{FrameTimeDiagnosticsPlugin, LogDiagnosticsPlugin},
     ecs::system::{Commands, Res},
     hierarchy::BuildChildren,
     log::LogPlugin,
     math::Vec3,
-    pbr::AmbientLight,
     prelude::PluginGroup,
-    render::camera::ClearColor,
     scene::SceneBundle,
     transform::components::Transform,
     utils::default,
@@ -19,10 +17,20 @@ use bevy::{
 
 use bevy_infinite_grid::{InfiniteGridBundle, InfiniteGridPlugin, InfiniteGridSettings};
 use physics::components::{
-    acceleration::Accelerator, inertia::Inertia, velocity::AngularVelocity, SimulationBundle,
-    velocity::Velocity,
+    acceleration::Accelerator, inertia::Inertia, velocity::AlignToVelocity,
+    velocity::AngularVelocity, BodyColor, SimulationBundle, velocity::Velocity,
 };
+use physics::prediction::PredictImpact;
+use physics::scene::SceneWatcher;
+use ui::appearance::AppearancePlugin;
 use ui::camera::{CameraPlugin, CameraTarget};
+use ui::grid::GridPlugin;
+use ui::measure::MeasurePlugin;
+use ui::placement::PlacementPlugin;
+use ui::replay::ReplayPlugin;
+use ui::simulation::SimulationPlugin;
+use ui::spin::SpinPlugin;
+use ui::stats::StatsPlugin;
 
 fn main() {
     App::new()
@@ -47,11 +55,26 @@ fn main() {
         .add_plugins(FrameTimeDiagnosticsPlugin)
         .add_plugins(InfiniteGridPlugin)
         .add_plugins(CameraPlugin)
-        .add_plugins(physics::SimulatiorPlugin)
-        .add_systems(Startup, (spawn_tests,))
+        .add_plugins(GridPlugin)
+        .add_plugins(AppearancePlugin)
+        .add_plugins(physics::SimulatiorPlugin::default())
+        .add_plugins(ReplayPlugin)
+        .add_plugins(PlacementPlugin)
+        .add_plugins(SimulationPlugin)
+        .add_plugins(StatsPlugin)
+        .add_plugins(MeasurePlugin)
+        .add_plugins(SpinPlugin)
+        .add_systems(Startup, (spawn_tests, watch_scene_argument))
         .run();
 }
 
+/// Loads the scene file given as the first argument, and loads it again whenever it is saved
+fn watch_scene_argument(mut commands: Commands) {
+    if let Some(path) = std::env::args().nth(1) {
+        commands.insert_resource(SceneWatcher::new(path));
+    }
+}
+
 fn spawn_tests(mut commands: Commands, ass: Res<AssetServer>) {
     let arrow = ass.load("arrow.glb#Scene0");
 
@@ -59,10 +82,13 @@ fn spawn_tests(mut commands: Commands, ass: Res<AssetServer>) {
         .spawn((
             SimulationBundle::new(
                 Velocity(Vec3::new(100.0, 100.0, 0.0)),
-                Accelerator(Vec3::Y * -9.82),
+                Accelerator::ZERO,
                 AngularVelocity(Vec3::ZERO),
                 Inertia::cylinder_x(20.0, 0.5, 50.0),
             ),
+            AlignToVelocity,
+            BodyColor(Color::srgb(0.9, 0.35, 0.1)),
+            PredictImpact,
             CameraTarget,
         ))
         .with_children(|parent| {
@@ -78,13 +104,6 @@ fn spawn_tests(mut commands: Commands, ass: Res<AssetServer>) {
             });
         });
 
-    commands.insert_resource(AmbientLight {
-        color: WHITE.into(),
-        brightness: 100.0,
-    });
-
-    commands.insert_resource(ClearColor(BLACK.into()));
-
     commands.spawn(InfiniteGridBundle {
         settings: InfiniteGridSettings {
             fadeout_distance: 1000.0,
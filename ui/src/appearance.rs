@@ -0,0 +1,203 @@
+use bevy::{
+    app::{App, Plugin, Update},
+    asset::{Assets, Handle},
+    color::{palettes::css::WHITE, Color},
+    ecs::{
+        entity::Entity,
+        query::Added,
+        schedule::{common_conditions::resource_changed, IntoSystemConfigs},
+        system::{Query, Res, ResMut, Resource},
+    },
+    hierarchy::{HierarchyQueryExt, Parent},
+    input::{keyboard::KeyCode, mouse::MouseButton, ButtonInput},
+    pbr::{AmbientLight, StandardMaterial},
+    render::camera::ClearColor,
+};
+use physics::components::BodyColor;
+
+use crate::keybinds::{Keybind, KeybindOptions};
+
+/// Background and ambient light of the scene
+///
+/// Changes are applied to [ClearColor] and [AmbientLight] the same frame.
+#[derive(Resource, Debug, Clone, Copy, PartialEq)]
+pub struct SceneAppearance {
+    pub clear_color: Color,
+    pub ambient_color: Color,
+    pub ambient_brightness: f32,
+}
+
+/// Keybinds used to change the [SceneAppearance]
+#[derive(Resource, Debug)]
+pub struct AppearanceKeybinds {
+    /// Switches between the dark and the light theme
+    pub toggle_theme: Keybind,
+}
+
+pub struct AppearancePlugin;
+
+impl SceneAppearance {
+    /// Black background, the default
+    pub const DARK: Self = Self {
+        clear_color: Color::BLACK,
+        ambient_color: Color::Srgba(WHITE),
+        ambient_brightness: 100.0,
+    };
+
+    /// Light gray background with brighter ambient light
+    pub const LIGHT: Self = Self {
+        clear_color: Color::srgb(0.85, 0.85, 0.85),
+        ambient_color: Color::Srgba(WHITE),
+        ambient_brightness: 400.0,
+    };
+
+    /// Switches to the light theme if currently using the dark one, otherwise to the dark one
+    pub fn toggle(&mut self) {
+        *self = if *self == Self::DARK {
+            Self::LIGHT
+        } else {
+            Self::DARK
+        };
+    }
+}
+
+/// Toggles the theme when the keybind is pressed
+fn toggle_theme(
+    kbd: Res<ButtonInput<KeyCode>>,
+    mos: Res<ButtonInput<MouseButton>>,
+    binds: Res<AppearanceKeybinds>,
+    mut appearance: ResMut<SceneAppearance>,
+) {
+    if binds.toggle_theme.just_pressed(&kbd, &mos) {
+        appearance.toggle();
+    }
+}
+
+/// Copies the [SceneAppearance] into the resources used for rendering
+fn apply(
+    appearance: Res<SceneAppearance>,
+    mut clear: ResMut<ClearColor>,
+    mut ambient: ResMut<AmbientLight>,
+) {
+    clear.0 = appearance.clear_color;
+    ambient.color = appearance.ambient_color;
+    ambient.brightness = appearance.ambient_brightness;
+}
+
+/// Gives every new mesh below an object with a [BodyColor] its own material in that color
+///
+/// Scenes share their materials between every instance, so the material is copied before being
+/// tinted.
+fn tint_bodies(
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    colors: Query<&BodyColor>,
+    parents: Query<&Parent>,
+    mut meshes: Query<(Entity, &mut Handle<StandardMaterial>), Added<Handle<StandardMaterial>>>,
+) {
+    for (entity, mut handle) in meshes.iter_mut() {
+        let Some(color) = parents
+            .iter_ancestors(entity)
+            .find_map(|ancestor| colors.get(ancestor).ok())
+        else {
+            continue;
+        };
+
+        let Some(material) = materials.get(handle.id()) else {
+            continue;
+        };
+
+        let tinted = StandardMaterial {
+            base_color: color.0,
+            ..material.clone()
+        };
+        *handle = materials.add(tinted);
+    }
+}
+
+impl Plugin for AppearancePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SceneAppearance>();
+        app.init_resource::<AppearanceKeybinds>();
+        app.init_resource::<ClearColor>();
+        app.init_resource::<AmbientLight>();
+        app.add_systems(
+            Update,
+            (
+                toggle_theme,
+                apply.run_if(resource_changed::<SceneAppearance>),
+            )
+                .chain(),
+        );
+        app.add_systems(Update, tint_bodies);
+    }
+}
+
+impl Default for SceneAppearance {
+    fn default() -> Self {
+        Self::DARK
+    }
+}
+
+impl Default for AppearanceKeybinds {
+    fn default() -> Self {
+        Self {
+            toggle_theme: Keybind(vec![KeybindOptions::Keyboard(KeyCode::KeyT)]),
+        }
+    }
+}
+
+#[cfg(test)]
+mod theme {
+    use bevy::{
+        app::App,
+        asset::{AssetApp, AssetPlugin},
+        input::{keyboard::KeyCode, mouse::MouseButton, ButtonInput},
+        pbr::{AmbientLight, StandardMaterial},
+        render::camera::ClearColor,
+    };
+
+    use super::{AppearancePlugin, SceneAppearance};
+
+    #[test]
+    fn toggle() {
+        let mut appearance = SceneAppearance::default();
+
+        appearance.toggle();
+        assert_eq!(appearance, SceneAppearance::LIGHT);
+
+        appearance.toggle();
+        assert_eq!(appearance, SceneAppearance::DARK);
+
+        // Anything custom goes back to the default
+        appearance.ambient_brightness = 5.0;
+        appearance.toggle();
+        assert_eq!(appearance, SceneAppearance::DARK);
+    }
+
+    #[test]
+    fn applied_live() {
+        let mut app = App::new();
+        app.add_plugins(AssetPlugin::default());
+        app.init_asset::<StandardMaterial>();
+        app.init_resource::<ButtonInput<KeyCode>>();
+        app.init_resource::<ButtonInput<MouseButton>>();
+        app.add_plugins(AppearancePlugin);
+
+        app.update();
+        assert_eq!(
+            app.world().resource::<ClearColor>().0,
+            SceneAppearance::DARK.clear_color
+        );
+
+        app.world_mut()
+            .resource_mut::<ButtonInput<KeyCode>>()
+            .press(KeyCode::KeyT);
+        app.update();
+
+        let light = SceneAppearance::LIGHT;
+        let ambient = app.world().resource::<AmbientLight>();
+        assert_eq!(app.world().resource::<ClearColor>().0, light.clear_color);
+        assert_eq!(ambient.color, light.ambient_color);
+        assert_eq!(ambient.brightness, light.ambient_brightness);
+    }
+}
@@ -1,14 +1,16 @@
-use std::f32::consts::{PI, TAU};
+use std::f32::consts::{FRAC_PI_2, FRAC_PI_4, PI, TAU};
 
 use bevy::{
-    app::{App, Plugin, PostUpdate, Startup},
+    app::{App, Plugin, Startup, Update},
     core_pipeline::core_3d::Camera3dBundle,
     ecs::{
         bundle::Bundle,
         component::Component,
+        entity::Entity,
         event::EventReader,
-        query::{QuerySingleError, With, Without},
-        system::{Commands, Query, Res},
+        query::{Added, Changed, Has, QuerySingleError, With, Without},
+        schedule::IntoSystemConfigs,
+        system::{Commands, Query, Res, Resource},
     },
     input::{
         gestures::PanGesture,
@@ -16,8 +18,17 @@ use bevy::{
         mouse::{MouseButton, MouseMotion, MouseScrollUnit, MouseWheel},
         ButtonInput,
     },
-    math::{EulerRot, Quat, Vec2, Vec3},
-    transform::components::Transform,
+    math::{primitives::InfinitePlane3d, EulerRot, Quat, Ray3d, Vec2, Vec3},
+    render::camera::{Camera, Exposure, Projection},
+    time::{Fixed, Time},
+    transform::components::{GlobalTransform, Transform},
+    utils::default,
+    window::{PrimaryWindow, Window},
+};
+use physics::{
+    components::Simulated,
+    interpolation::{step_fraction, StepTransforms},
+    PhysicsSet,
 };
 
 use crate::keybinds::{Keybind, KeybindOptions};
@@ -30,13 +41,80 @@ pub struct OrbitCam {
     pub settings: OrbitSettings,
 }
 
+/// Orbit sensitivity used in place of one that isn't positive
+const DEFAULT_ORBIT_SENSITIVITY: f32 = 0.01;
+
+/// Scroll sensitivity per line used in place of one that isn't positive
+const DEFAULT_SCROLL_LINE: f32 = 0.1;
+
+/// Scroll sensitivity per pixel used in place of one that isn't positive
+const DEFAULT_SCROLL_PIXEL: f32 = 0.01;
+
 /// Settings used by Orbit Camera
-#[derive(Component, Debug, Default)]
+///
+/// Sensitivities that are zero, negative or not finite are replaced with defaults when the camera
+/// is spawned, with a warning, as they would freeze or invert the controls.
+#[derive(Component, Debug)]
 pub struct OrbitSettings {
+    /// Radians turned for every pixel dragged
     pub orbit_sensitivity: f32,
+
+    /// Zoom for every line scrolled, the radius is multiplied by `exp(-lines * sensitivity)`
+    ///
+    /// So scrolling up moves closer, and scrolling the same amount back returns to the same
+    /// radius.
     pub scroll_sensitivity_line: f32,
+
+    /// Zoom for every pixel scrolled on a touchpad, like [OrbitSettings::scroll_sensitivity_line]
     pub scroll_sensitivity_pixel: f32,
+
     pub orbit_key: Keybind,
+
+    /// How fast the camera stops coasting after releasing the orbit key, per second
+    ///
+    /// 0 disables coasting, the camera stops as soon as the key is released.
+    pub orbit_damping: f32,
+
+    pub zoom_mode: ZoomMode,
+
+    /// Distance to the near clip plane in meters, raise it for orbital scales and lower it for
+    /// tiny scenes
+    pub near: f32,
+
+    /// Distance to the far clip plane in meters, keep it well past the radius the camera zooms
+    /// out to, or the orbited point disappears
+    pub far: f32,
+
+    /// Exposure value at ISO 100, higher values darken the image
+    pub exposure: f32,
+}
+
+/// What the camera zooms towards when scrolling
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ZoomMode {
+    /// Moves closer to the orbited point, keeping it in the middle of the screen
+    #[default]
+    Target,
+
+    /// Moves closer to the point under the cursor, keeping it under the cursor
+    ///
+    /// Shifts the orbited point along, so this only has a lasting effect without a
+    /// [CameraTarget] to snap back to.
+    Cursor,
+}
+
+/// Steps the primary camera is turned and zoomed by for every press of a nudge keybind in the
+/// [CameraKeybinds], for framing repeatable shots
+#[derive(Resource, Debug)]
+pub struct NudgeSettings {
+    /// Step of the yaw and pitch in radians
+    pub angle: f32,
+
+    /// Step of the orbit radius in meters
+    pub distance: f32,
+
+    /// How many times larger the steps are while the coarse keybind is held
+    pub coarse_factor: f32,
 }
 
 /// Current state of a orbiting camera
@@ -46,6 +124,12 @@ pub struct OrbitState {
     pub radius: f32,
     pub pitch: f32,
     pub yaw: f32,
+
+    /// Rate of the last drag in radians per second, kept for coasting after the drag ends
+    pub velocity: Vec2,
+
+    /// Stops following the [CameraTarget], leaving `target` where the target last was
+    pub frozen: bool,
 }
 
 /// Marks the primary camera
@@ -56,28 +140,245 @@ pub struct PrimaryCameraMarker;
 #[derive(Component)]
 pub struct CameraTarget;
 
+/// Name of a camera viewpoint, like "top" or "side"
+#[derive(Component, Debug)]
+pub struct CameraName(pub String);
+
+/// Keybinds used to switch between and move the cameras
+#[derive(Resource, Debug)]
+pub struct CameraKeybinds {
+    /// Makes the next camera the primary one, in the order they were spawned
+    pub next: Keybind,
+
+    /// Moves the [CameraTarget] to the next simulated object, in the order they were spawned
+    pub next_target: Keybind,
+
+    /// Stops the primary camera from following the [CameraTarget], or starts following again
+    pub freeze_target: Keybind,
+
+    /// Turns the primary camera to look along +Z, keeping its target
+    pub front: Keybind,
+
+    /// Turns the primary camera to look along +X, keeping its target
+    pub side: Keybind,
+
+    /// Turns the primary camera to look straight down, keeping its target
+    pub top: Keybind,
+
+    /// Turns the primary camera left around its target by a [NudgeSettings] step
+    pub yaw_left: Keybind,
+
+    /// Turns the primary camera right around its target by a [NudgeSettings] step
+    pub yaw_right: Keybind,
+
+    /// Tilts the primary camera up over its target by a [NudgeSettings] step
+    pub pitch_up: Keybind,
+
+    /// Tilts the primary camera down under its target by a [NudgeSettings] step
+    pub pitch_down: Keybind,
+
+    /// Moves the primary camera closer to its target by a [NudgeSettings] step
+    pub zoom_in: Keybind,
+
+    /// Moves the primary camera away from its target by a [NudgeSettings] step
+    pub zoom_out: Keybind,
+
+    /// Makes every nudge step `coarse_factor` times larger while held
+    pub coarse: Keybind,
+}
+
 pub struct CameraPlugin;
 
-/// Spawns an isntance of a [OrbitCam] with the [PrimaryCameraMarker]
+/// Spawns an [OrbitCam] for every preset viewpoint, only the first one with the
+/// [PrimaryCameraMarker]
 ///
 /// ONLY RUN THIS FUNCTION ONCE
 /// Multiple primary cameras will cause a panic
 fn spawn(mut cmds: Commands) {
-    cmds.spawn((
-        OrbitCam {
-            settings: OrbitSettings {
-                orbit_sensitivity: 0.01,
-                orbit_key: Keybind(vec![
-                    KeybindOptions::MouseButton(MouseButton::Right),
-                    KeybindOptions::MouseButton(MouseButton::Left),
-                ]),
-                scroll_sensitivity_line: 0.1,
-                scroll_sensitivity_pixel: 0.01,
+    let presets = [
+        ("side", 0.0, 0.0),
+        ("top", -FRAC_PI_2, 0.0),
+        // Pitched down so all three axes look equally long
+        ("iso", -(1.0 / 2f32.sqrt()).atan(), -FRAC_PI_4),
+    ];
+
+    for (i, (name, pitch, yaw)) in presets.into_iter().enumerate() {
+        let mut cam = cmds.spawn((
+            OrbitCam {
+                camera: Camera3dBundle {
+                    camera: Camera {
+                        is_active: i == 0,
+                        ..default()
+                    },
+                    ..default()
+                },
+                state: OrbitState {
+                    pitch,
+                    yaw,
+                    ..default()
+                },
+                settings: OrbitSettings {
+                    orbit_key: Keybind(vec![
+                        KeybindOptions::MouseButton(MouseButton::Right),
+                        KeybindOptions::MouseButton(MouseButton::Left),
+                    ]),
+                    orbit_damping: 4.0,
+                    zoom_mode: ZoomMode::Target,
+                    ..default()
+                },
             },
-            ..Default::default()
+            CameraName(name.to_string()),
+        ));
+
+        if i == 0 {
+            cam.insert(PrimaryCameraMarker);
+        }
+    }
+}
+
+/// Moves the [PrimaryCameraMarker] to the next camera when the keybind is pressed
+///
+/// Only the primary camera is kept active, so there is always exactly one primary camera.
+fn switch_camera(
+    mut cmds: Commands,
+    kbd: Res<ButtonInput<KeyCode>>,
+    mos: Res<ButtonInput<MouseButton>>,
+    binds: Res<CameraKeybinds>,
+    mut cams: Query<(Entity, &mut Camera, Has<PrimaryCameraMarker>), With<OrbitState>>,
+) {
+    if !binds.next.just_pressed(&kbd, &mos) {
+        return;
+    }
+
+    // Entities sort in spawn order, which keeps the cycle stable
+    let mut order: Vec<(Entity, bool)> = cams.iter().map(|(e, _, p)| (e, p)).collect();
+    order.sort();
+
+    let Some(i) = order.iter().position(|(_, primary)| *primary) else {
+        return;
+    };
+    let (current, next) = (order[i].0, order[(i + 1) % order.len()].0);
+
+    if next == current {
+        return;
+    }
+
+    cmds.entity(current).remove::<PrimaryCameraMarker>();
+    cmds.entity(next).insert(PrimaryCameraMarker);
+
+    for (entity, mut camera, _) in cams.iter_mut() {
+        camera.is_active = entity == next;
+    }
+}
+
+/// Moves the [CameraTarget] to the next simulated object when the keybind is pressed
+///
+/// If no simulated object is targeted, the first one becomes the target.
+fn switch_target(
+    mut cmds: Commands,
+    kbd: Res<ButtonInput<KeyCode>>,
+    mos: Res<ButtonInput<MouseButton>>,
+    binds: Res<CameraKeybinds>,
+    targets: Query<Entity, With<CameraTarget>>,
+    bodies: Query<(Entity, Has<CameraTarget>), With<Simulated>>,
+) {
+    if !binds.next_target.just_pressed(&kbd, &mos) {
+        return;
+    }
+
+    // Entities sort in spawn order, which keeps the cycle stable
+    let mut order: Vec<(Entity, bool)> = bodies.iter().collect();
+    order.sort();
+
+    let next = match order.iter().position(|(_, target)| *target) {
+        Some(i) => order[(i + 1) % order.len()].0,
+        None => match order.first() {
+            Some((first, _)) => *first,
+            None => return,
         },
-        PrimaryCameraMarker,
-    ));
+    };
+
+    // There should only be one target, so take it from everything else
+    for entity in targets.iter().filter(|e| *e != next) {
+        cmds.entity(entity).remove::<CameraTarget>();
+    }
+    cmds.entity(next).insert(CameraTarget);
+}
+
+/// Toggles whether the primary camera follows the [CameraTarget] when the keybind is pressed
+fn toggle_follow(
+    kbd: Res<ButtonInput<KeyCode>>,
+    mos: Res<ButtonInput<MouseButton>>,
+    binds: Res<CameraKeybinds>,
+    mut cams: Query<&mut OrbitState, With<PrimaryCameraMarker>>,
+) {
+    if !binds.freeze_target.just_pressed(&kbd, &mos) {
+        return;
+    }
+
+    for mut state in cams.iter_mut() {
+        state.frozen = !state.frozen;
+    }
+}
+
+/// Nudges the primary camera by a step for every nudge keybind pressed this frame
+fn nudge(
+    kbd: Res<ButtonInput<KeyCode>>,
+    mos: Res<ButtonInput<MouseButton>>,
+    binds: Res<CameraKeybinds>,
+    settings: Res<NudgeSettings>,
+    mut cams: Query<&mut OrbitState, With<PrimaryCameraMarker>>,
+) {
+    let pressed = |bind: &Keybind| -> f32 {
+        if bind.just_pressed(&kbd, &mos) {
+            1.0
+        } else {
+            0.0
+        }
+    };
+
+    let scale = if binds.coarse.pressed(&kbd, &mos) {
+        settings.coarse_factor
+    } else {
+        1.0
+    };
+
+    let yaw = (pressed(&binds.yaw_left) - pressed(&binds.yaw_right)) * settings.angle;
+    let pitch = (pressed(&binds.pitch_up) - pressed(&binds.pitch_down)) * settings.angle;
+    let radius = (pressed(&binds.zoom_out) - pressed(&binds.zoom_in)) * settings.distance;
+
+    if (yaw, pitch, radius) == (0.0, 0.0, 0.0) {
+        return;
+    }
+
+    for mut state in cams.iter_mut() {
+        state.nudge(yaw * scale, pitch * scale, radius * scale);
+    }
+}
+
+/// Snaps the primary camera to a standard view when one of the view keybinds is pressed
+fn snap_view(
+    kbd: Res<ButtonInput<KeyCode>>,
+    mos: Res<ButtonInput<MouseButton>>,
+    binds: Res<CameraKeybinds>,
+    mut cams: Query<&mut OrbitState, With<PrimaryCameraMarker>>,
+) {
+    let views = [
+        (&binds.front, Vec3::Z),
+        (&binds.side, Vec3::X),
+        (&binds.top, Vec3::NEG_Y),
+    ];
+
+    let Some((_, dir)) = views
+        .into_iter()
+        .find(|(bind, _)| bind.just_pressed(&kbd, &mos))
+    else {
+        return;
+    };
+
+    for mut state in cams.iter_mut() {
+        state.look_along(dir);
+    }
 }
 
 /// Updates the camera position
@@ -86,17 +387,33 @@ fn spawn(mut cmds: Commands) {
 /// This function will panic if:
 /// - there is more than one camera with [PrimaryCameraMarker]
 /// - there is more than one entity with [CameraTarget]
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
 fn update_camera(
+    time: Res<Time>,
+    fixed: Option<Res<Time<Fixed>>>,
     kbd: Res<ButtonInput<KeyCode>>,
     mos: Res<ButtonInput<MouseButton>>,
     mut mouse_motion_event: EventReader<MouseMotion>,
     mut pan_motion_event: EventReader<PanGesture>,
     mouse_scroll_event: EventReader<MouseWheel>,
-    mut cam: Query<(&mut OrbitState, &mut Transform, &OrbitSettings), With<PrimaryCameraMarker>>,
-    target: Query<&Transform, (With<CameraTarget>, Without<PrimaryCameraMarker>)>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    mut cam: Query<
+        (
+            &mut OrbitState,
+            &mut Transform,
+            &OrbitSettings,
+            &Camera,
+            &GlobalTransform,
+        ),
+        With<PrimaryCameraMarker>,
+    >,
+    target: Query<
+        (&Transform, Option<&StepTransforms>),
+        (With<CameraTarget>, Without<PrimaryCameraMarker>),
+    >,
 ) {
     // Get the state and transform for the camera
-    let (mut state, mut transform, settings) =
+    let (mut state, mut transform, settings, camera, global) =
         cam.get_single_mut().expect("Multiple or no primary camera");
 
     // In case of no entity with target marker, allow user to pan camera
@@ -107,36 +424,93 @@ fn update_camera(
             panic!("There are multiple targets for the primary camera")
         }
         Err(QuerySingleError::NoEntities(_)) => {}
-        Ok(t) => state.target = t.translation,
+        Ok(_) if state.frozen => {}
+        Ok((trans, steps)) => {
+            let fraction = fixed.map_or(1.0, |f| step_fraction(f.overstep(), f.timestep()));
+            state.target = target_position(trans, steps, fraction);
+        }
     };
 
     // Convert mouse movement and scroll events to Vec2s
 
-    // Apply to Pitch/Yaw
-    let mut motion: Vec2 = pan_motion_event.read().map(|ev| ev.0).sum();
+    // Apply to Pitch/Yaw, coasting along after a drag is released
+    let motion: Vec2 = pan_motion_event.read().map(|ev| ev.0).sum();
+    if settings.orbit_key.just_pressed(&kbd, &mos) {
+        state.grab();
+    }
     if settings.orbit_key.pressed(&kbd, &mos) {
-        motion += mouse_motion_event.read().map(|ev| ev.delta).sum::<Vec2>();
+        let drag = mouse_motion_event.read().map(|ev| ev.delta).sum::<Vec2>();
+        state.drag(settings, -drag, time.delta_seconds());
+    } else {
+        state.coast(settings, time.delta_seconds());
     }
     state.orbit(settings, -motion);
 
     // Apply scroll
     let scroll = parse_scroll(mouse_scroll_event, settings);
-    state.zoom(scroll.y);
+    let cursor_ray = windows
+        .get_single()
+        .ok()
+        .and_then(Window::cursor_position)
+        .and_then(|cursor| camera.viewport_to_world(global, cursor));
+
+    match (settings.zoom_mode, cursor_ray) {
+        (ZoomMode::Cursor, Some(ray)) => state.zoom_toward(scroll.y, ray),
+        _ => state.zoom(scroll.y),
+    }
 
     // Apply transformation
     *transform = state.to_transform();
 }
 
+/// Checks the sensitivities of every newly spawned camera, see [OrbitSettings::validate]
+fn validate_settings(mut cams: Query<&mut OrbitSettings, Added<OrbitSettings>>) {
+    for mut settings in cams.iter_mut() {
+        settings.validate();
+    }
+}
+
+/// Applies the clip distances and exposure of the [OrbitSettings] to the camera, when spawned and
+/// whenever the settings change
+fn update_lens(
+    mut cams: Query<(&OrbitSettings, &mut Projection, &mut Exposure), Changed<OrbitSettings>>,
+) {
+    for (settings, mut projection, mut exposure) in cams.iter_mut() {
+        match projection.as_mut() {
+            Projection::Perspective(perspective) => {
+                perspective.near = settings.near;
+                perspective.far = settings.far;
+            }
+            Projection::Orthographic(orthographic) => {
+                orthographic.near = settings.near;
+                orthographic.far = settings.far;
+            }
+        }
+
+        exposure.ev100 = settings.exposure;
+    }
+}
+
+/// Gets the position to orbit around for a target, `fraction` of the way into the next fixed step
+///
+/// Taken from the [StepTransforms] when the target has them, so the camera moves as smoothly as
+/// the rendered objects even if it runs before they are interpolated.
+fn target_position(trans: &Transform, steps: Option<&StepTransforms>, fraction: f32) -> Vec3 {
+    steps.map_or(trans.translation, |steps| {
+        steps.interpolate(fraction).translation
+    })
+}
+
 /// Normalize a euler angle so it loops around
 fn norm_euler(v: f32) -> f32 {
     ((v + PI) % TAU) - PI
 }
 
-/// Parses the [EventReader] for [MouseWheel] and produces a [Vec2] with the total scroll movement
+/// Parses the [EventReader] for [MouseWheel] into a zoom factor along each axis
 ///
-/// If this function unexpectedly returns a 0,0 vector, the most likley reason is that
-/// [OrbitSettings::scroll_sensitivity_line] and/or [OrbitSettings::scroll_sensitivity_pixel] is
-/// set to 0
+/// Each factor is `exp(-scroll * sensitivity)`, so no scrolling gives 1 and leaves the radius as
+/// is. A sensitivity of 0 would do the same for any scrolling, which is why
+/// [OrbitSettings::validate] replaces it.
 fn parse_scroll(mut input: EventReader<MouseWheel>, settings: &OrbitSettings) -> Vec2 {
     let mut result = Vec2::ZERO;
 
@@ -153,6 +527,41 @@ fn parse_scroll(mut input: EventReader<MouseWheel>, settings: &OrbitSettings) ->
     result.exp()
 }
 
+impl OrbitSettings {
+    /// Replaces every sensitivity that is zero, negative or not finite with a default
+    ///
+    /// Zero silently turns the control off and a negative value inverts it, so each replacement
+    /// is logged as a warning.
+    pub fn validate(&mut self) {
+        let sensitivities = [
+            (
+                "orbit_sensitivity",
+                &mut self.orbit_sensitivity,
+                DEFAULT_ORBIT_SENSITIVITY,
+            ),
+            (
+                "scroll_sensitivity_line",
+                &mut self.scroll_sensitivity_line,
+                DEFAULT_SCROLL_LINE,
+            ),
+            (
+                "scroll_sensitivity_pixel",
+                &mut self.scroll_sensitivity_pixel,
+                DEFAULT_SCROLL_PIXEL,
+            ),
+        ];
+
+        for (name, value, default) in sensitivities {
+            if *value > 0.0 && value.is_finite() {
+                continue;
+            }
+
+            log::warn!("Camera {name} of {value} isn't positive, using {default} instead");
+            *value = default;
+        }
+    }
+}
+
 impl OrbitState {
     /// Converts [OrbitState] into a [Transform] the describes the actual camera position
     fn to_transform(&self) -> Transform {
@@ -164,11 +573,73 @@ impl OrbitState {
         Transform::from_rotation(rot).with_translation(self.target + dir * -self.radius)
     }
 
+    /// Turns the camera to look along `dir` at the target, stopping any coasting
+    ///
+    /// The yaw is kept when looking straight up or down, and nothing changes for a zero `dir`.
+    pub fn look_along(&mut self, dir: Vec3) {
+        let Some(dir) = dir.try_normalize() else {
+            return;
+        };
+
+        self.pitch = dir.y.clamp(-1.0, 1.0).asin();
+        if dir.x != 0.0 || dir.z != 0.0 {
+            self.yaw = (-dir.x).atan2(-dir.z);
+        }
+        self.velocity = Vec2::ZERO;
+    }
+
+    /// Adds to the yaw and pitch in radians and the radius in meters
+    ///
+    /// The radius change is skipped if it would put the camera on or past the target.
+    pub fn nudge(&mut self, yaw: f32, pitch: f32, radius: f32) {
+        self.rotate(Vec2::new(yaw, pitch));
+
+        if self.radius + radius > 0.0 {
+            self.radius += radius;
+        }
+    }
+
     fn orbit(&mut self, settings: &OrbitSettings, motion: Vec2) {
-        let motion = motion * settings.orbit_sensitivity;
+        self.rotate(motion * settings.orbit_sensitivity);
+    }
+
+    /// Adds to the yaw and pitch, in radians
+    fn rotate(&mut self, angles: Vec2) {
+        self.yaw = norm_euler(self.yaw + angles.x);
+        self.pitch = norm_euler(self.pitch + angles.y);
+    }
 
-        self.yaw = norm_euler(self.yaw + motion.x);
-        self.pitch = norm_euler(self.pitch + motion.y);
+    /// Orbits with a drag of the mouse, remembering its rate to coast with afterwards
+    fn drag(&mut self, settings: &OrbitSettings, motion: Vec2, delta: f32) {
+        self.velocity = if delta > 0.0 {
+            motion * settings.orbit_sensitivity / delta
+        } else {
+            Vec2::ZERO
+        };
+        self.orbit(settings, motion);
+    }
+
+    /// Keeps orbiting at the rate of the last drag, slowing down until it stops
+    fn coast(&mut self, settings: &OrbitSettings, delta: f32) {
+        if settings.orbit_damping <= 0.0 {
+            self.velocity = Vec2::ZERO;
+        }
+        if self.velocity == Vec2::ZERO {
+            return;
+        }
+
+        self.rotate(self.velocity * delta);
+        self.velocity *= (-settings.orbit_damping * delta).exp();
+
+        // Too slow to notice, stop entirely instead of creeping along forever
+        if self.velocity.length() < 1e-3 {
+            self.velocity = Vec2::ZERO;
+        }
+    }
+
+    /// Stops coasting, like when grabbing the camera again
+    fn grab(&mut self) {
+        self.velocity = Vec2::ZERO;
     }
 
     fn zoom(&mut self, scroll: f32) {
@@ -177,12 +648,103 @@ impl OrbitState {
         }
         self.radius *= scroll;
     }
+
+    /// Zooms towards where `ray` crosses the plane facing the camera through the target
+    ///
+    /// The whole orbit is scaled around that point, so it stays at the same spot on the screen.
+    /// Zooms towards the target instead if the ray points away from the plane.
+    fn zoom_toward(&mut self, scroll: f32, ray: Ray3d) {
+        let forward = self.to_transform().forward();
+        let plane = InfinitePlane3d::new(forward);
+
+        let Some(focus) = ray
+            .intersect_plane(self.target, plane)
+            .map(|distance| ray.get_point(distance))
+        else {
+            self.zoom(scroll);
+            return;
+        };
+
+        if scroll == 0.0 {
+            return;
+        }
+
+        self.target = focus + (self.target - focus) * scroll;
+        self.radius *= scroll;
+    }
 }
 
 impl Plugin for CameraPlugin {
     fn build(&self, app: &mut App) {
+        app.init_resource::<CameraKeybinds>();
+        app.init_resource::<NudgeSettings>();
         app.add_systems(Startup, spawn);
-        app.add_systems(PostUpdate, update_camera);
+        app.add_systems(
+            Update,
+            (
+                validate_settings,
+                switch_camera,
+                switch_target,
+                toggle_follow,
+                nudge,
+                snap_view,
+                update_camera,
+                update_lens,
+            )
+                .chain()
+                .after(PhysicsSet::Interpolate),
+        );
+    }
+}
+
+impl Default for CameraKeybinds {
+    fn default() -> Self {
+        let key = |code| Keybind(vec![KeybindOptions::Keyboard(code)]);
+
+        Self {
+            next: key(KeyCode::KeyC),
+            next_target: key(KeyCode::Tab),
+            freeze_target: key(KeyCode::KeyF),
+            front: key(KeyCode::Numpad1),
+            side: key(KeyCode::Numpad3),
+            top: key(KeyCode::Numpad7),
+            yaw_left: key(KeyCode::Numpad4),
+            yaw_right: key(KeyCode::Numpad6),
+            pitch_up: key(KeyCode::Numpad8),
+            pitch_down: key(KeyCode::Numpad2),
+            zoom_in: key(KeyCode::NumpadAdd),
+            zoom_out: key(KeyCode::NumpadSubtract),
+            coarse: Keybind(vec![
+                KeybindOptions::Keyboard(KeyCode::ShiftLeft),
+                KeybindOptions::Keyboard(KeyCode::ShiftRight),
+            ]),
+        }
+    }
+}
+
+impl Default for NudgeSettings {
+    fn default() -> Self {
+        Self {
+            angle: 1f32.to_radians(),
+            distance: 0.5,
+            coarse_factor: 10.0,
+        }
+    }
+}
+
+impl Default for OrbitSettings {
+    fn default() -> Self {
+        Self {
+            orbit_sensitivity: DEFAULT_ORBIT_SENSITIVITY,
+            scroll_sensitivity_line: DEFAULT_SCROLL_LINE,
+            scroll_sensitivity_pixel: DEFAULT_SCROLL_PIXEL,
+            orbit_key: Keybind::default(),
+            orbit_damping: 0.0,
+            zoom_mode: ZoomMode::default(),
+            near: 0.1,
+            far: 100_000.0,
+            exposure: Exposure::EV100_BLENDER,
+        }
     }
 }
 
@@ -193,6 +755,758 @@ impl Default for OrbitState {
             radius: 10.0,
             pitch: 0.0,
             yaw: 0.0,
+            velocity: Vec2::ZERO,
+            frozen: false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod switching {
+    use bevy::{
+        app::{App, Startup, Update},
+        ecs::{
+            query::{With, Without},
+            schedule::IntoSystemConfigs,
+            world::World,
+        },
+        input::{
+            gestures::PanGesture,
+            keyboard::KeyCode,
+            mouse::{MouseButton, MouseMotion, MouseWheel},
+            ButtonInput,
+        },
+        render::camera::Camera,
+        time::Time,
+    };
+
+    use super::{
+        spawn, switch_camera, update_camera, CameraKeybinds, CameraName, PrimaryCameraMarker,
+    };
+
+    fn app() -> App {
+        let mut app = App::new();
+        app.init_resource::<Time>();
+        app.init_resource::<ButtonInput<KeyCode>>();
+        app.init_resource::<ButtonInput<MouseButton>>();
+        app.init_resource::<CameraKeybinds>();
+        app.add_event::<MouseMotion>();
+        app.add_event::<PanGesture>();
+        app.add_event::<MouseWheel>();
+        app.add_systems(Startup, spawn);
+        app.add_systems(Update, (switch_camera, update_camera).chain());
+
+        app
+    }
+
+    fn press_next(app: &mut App) {
+        let mut kbd = app.world_mut().resource_mut::<ButtonInput<KeyCode>>();
+        kbd.reset_all();
+        kbd.press(KeyCode::KeyC);
+    }
+
+    /// Name of the primary camera, checking that it is the only active one
+    fn primary(world: &mut World) -> String {
+        let name = world
+            .query_filtered::<(&CameraName, &Camera), With<PrimaryCameraMarker>>()
+            .single(world);
+        assert!(name.1.is_active);
+        let name = name.0 .0.clone();
+
+        let mut others = world.query_filtered::<&Camera, Without<PrimaryCameraMarker>>();
+        assert!(others.iter(world).all(|c| !c.is_active));
+
+        name
+    }
+
+    #[test]
+    fn cycles() {
+        let mut app = app();
+        app.update();
+        assert_eq!(primary(app.world_mut()), "side");
+
+        press_next(&mut app);
+        app.update();
+        assert_eq!(primary(app.world_mut()), "top");
+
+        press_next(&mut app);
+        app.update();
+        assert_eq!(primary(app.world_mut()), "iso");
+
+        press_next(&mut app);
+        app.update();
+        assert_eq!(primary(app.world_mut()), "side");
+    }
+}
+
+#[cfg(test)]
+mod targets {
+    use bevy::{
+        app::{App, Update},
+        ecs::{entity::Entity, query::With, world::World},
+        input::{keyboard::KeyCode, mouse::MouseButton, ButtonInput},
+    };
+    use physics::components::Simulated;
+
+    use super::{switch_target, CameraKeybinds, CameraTarget};
+
+    fn app() -> App {
+        let mut app = App::new();
+        app.init_resource::<ButtonInput<KeyCode>>();
+        app.init_resource::<ButtonInput<MouseButton>>();
+        app.init_resource::<CameraKeybinds>();
+        app.add_systems(Update, switch_target);
+
+        app
+    }
+
+    fn press_next(app: &mut App) {
+        let mut kbd = app.world_mut().resource_mut::<ButtonInput<KeyCode>>();
+        kbd.reset_all();
+        kbd.press(KeyCode::Tab);
+        app.update();
+    }
+
+    fn targets(world: &mut World) -> Vec<Entity> {
+        world
+            .query_filtered::<Entity, With<CameraTarget>>()
+            .iter(world)
+            .collect()
+    }
+
+    #[test]
+    fn cycles() {
+        let mut app = app();
+        let world = app.world_mut();
+        let a = world.spawn((Simulated, CameraTarget)).id();
+        let b = world.spawn(Simulated).id();
+        let c = world.spawn(Simulated).id();
+
+        press_next(&mut app);
+        assert_eq!(targets(app.world_mut()), vec![b]);
+
+        press_next(&mut app);
+        assert_eq!(targets(app.world_mut()), vec![c]);
+
+        press_next(&mut app);
+        assert_eq!(targets(app.world_mut()), vec![a]);
+    }
+
+    #[test]
+    fn starts_at_first() {
+        let mut app = app();
+        let first = app.world_mut().spawn(Simulated).id();
+        app.world_mut().spawn(Simulated);
+
+        press_next(&mut app);
+        assert_eq!(targets(app.world_mut()), vec![first]);
+    }
+
+    #[test]
+    fn no_bodies() {
+        let mut app = app();
+
+        press_next(&mut app);
+        assert!(targets(app.world_mut()).is_empty());
+    }
+}
+
+#[cfg(test)]
+mod coasting {
+    use bevy::math::Vec2;
+
+    use super::{OrbitSettings, OrbitState};
+
+    fn assert_near(a: f32, b: f32) {
+        assert!((a - b).abs() < 1e-4, "{a} isn't {b}");
+    }
+
+    fn settings(damping: f32) -> OrbitSettings {
+        OrbitSettings {
+            orbit_sensitivity: 0.01,
+            orbit_damping: damping,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn decays() {
+        let settings = settings(4.0);
+        let mut state = OrbitState::default();
+
+        // 10 pixels in a 100th of a second is 10 radians per second
+        state.drag(&settings, Vec2::new(10.0, 0.0), 0.01);
+        assert_near(state.velocity.x, 10.0);
+
+        for _ in 0..25 {
+            state.coast(&settings, 0.01);
+        }
+
+        // A quarter of a second at a damping of 4 leaves 1/e of the rate, after turning about
+        // 10 * (1 - 1/e) / 4 radians further
+        assert_near(state.velocity.x, 10.0 * (-1.0f32).exp());
+        assert!(state.yaw > 1.5, "only turned to {}", state.yaw);
+
+        for _ in 0..1000 {
+            state.coast(&settings, 0.01);
+        }
+        assert_eq!(state.velocity, Vec2::ZERO);
+    }
+
+    #[test]
+    fn grab_stops() {
+        let settings = settings(2.0);
+        let mut state = OrbitState::default();
+
+        state.drag(&settings, Vec2::new(10.0, 5.0), 0.01);
+        state.coast(&settings, 0.01);
+        state.grab();
+
+        assert_eq!(state.velocity, Vec2::ZERO);
+
+        let yaw = state.yaw;
+        state.coast(&settings, 0.01);
+        assert_eq!(state.yaw, yaw);
+    }
+
+    #[test]
+    fn disabled() {
+        let mut state = OrbitState::default();
+
+        state.drag(&settings(0.0), Vec2::new(10.0, 0.0), 0.01);
+        state.coast(&settings(0.0), 0.01);
+
+        assert_eq!(state.velocity, Vec2::ZERO);
+        assert_near(state.yaw, 0.1);
+    }
+}
+
+#[cfg(test)]
+mod look_along {
+    use std::f32::consts::{FRAC_PI_2, FRAC_PI_4, PI};
+
+    use bevy::math::{Vec2, Vec3};
+
+    use super::OrbitState;
+
+    fn assert_near(a: f32, b: f32) {
+        assert!((a - b).abs() < 1e-5, "{a} isn't {b}");
+    }
+
+    /// Checks that the camera ends up looking along `dir`
+    fn assert_looks(state: &OrbitState, dir: Vec3) {
+        let forward = *state.to_transform().forward();
+        assert!(
+            forward.abs_diff_eq(dir.normalize(), 1e-5),
+            "{forward} isn't {dir}"
+        );
+    }
+
+    #[test]
+    fn axes() {
+        let mut state = OrbitState::default();
+
+        state.look_along(Vec3::Z);
+        assert_near(state.pitch, 0.0);
+        assert_near(state.yaw.abs(), PI);
+        assert_looks(&state, Vec3::Z);
+
+        state.look_along(Vec3::X);
+        assert_near(state.pitch, 0.0);
+        assert_near(state.yaw, -FRAC_PI_2);
+        assert_looks(&state, Vec3::X);
+    }
+
+    #[test]
+    fn diagonal() {
+        let mut state = OrbitState::default();
+        let dir = Vec3::new(-1.0, -2f32.sqrt(), -1.0);
+
+        state.look_along(dir);
+        assert_near(state.pitch, -FRAC_PI_4);
+        assert_near(state.yaw, FRAC_PI_4);
+        assert_looks(&state, dir);
+    }
+
+    #[test]
+    fn straight_down() {
+        let mut state = OrbitState {
+            yaw: 1.0,
+            velocity: Vec2::ONE,
+            ..Default::default()
+        };
+
+        state.look_along(Vec3::NEG_Y * 3.0);
+        assert_near(state.pitch, -FRAC_PI_2);
+        assert_near(state.yaw, 1.0);
+        assert_eq!(state.velocity, Vec2::ZERO);
+
+        // Nothing to look along
+        state.look_along(Vec3::ZERO);
+        assert_near(state.pitch, -FRAC_PI_2);
+    }
+}
+
+#[cfg(test)]
+mod cursor_zoom {
+    use bevy::math::{Dir3, Ray3d, Vec3};
+
+    use super::OrbitState;
+
+    fn assert_near(a: Vec3, b: Vec3) {
+        assert!(a.distance(b) < 1e-4, "{a} isn't {b}");
+    }
+
+    #[test]
+    fn shifts_target() {
+        // Looking down -z at the origin from 10 meters away
+        let mut state = OrbitState::default();
+        let camera = state.to_transform().translation;
+        assert_near(camera, Vec3::Z * 10.0);
+
+        let focus = Vec3::new(2.0, 1.0, 0.0);
+        let ray = Ray3d::new(camera, focus - camera);
+
+        state.zoom_toward(0.5, ray);
+
+        assert_near(state.target, Vec3::new(1.0, 0.5, 0.0));
+        assert_eq!(state.radius, 5.0);
+
+        // The focus is still straight along the same ray
+        let moved = state.to_transform().translation;
+        assert_near((focus - moved).normalize(), *ray.direction);
+    }
+
+    #[test]
+    fn centered_ray() {
+        let mut state = OrbitState::default();
+        let ray = Ray3d::new(Vec3::Z * 10.0, Vec3::NEG_Z);
+
+        state.zoom_toward(2.0, ray);
+
+        assert_near(state.target, Vec3::ZERO);
+        assert_eq!(state.radius, 20.0);
+    }
+
+    #[test]
+    fn facing_away() {
+        let mut state = OrbitState::default();
+        let ray = Ray3d {
+            origin: Vec3::Z * 10.0,
+            direction: Dir3::Z,
+        };
+
+        state.zoom_toward(0.5, ray);
+
+        assert_eq!(state.target, Vec3::ZERO);
+        assert_eq!(state.radius, 5.0);
+    }
+}
+
+#[cfg(test)]
+mod nudging {
+    use bevy::{
+        app::{App, Startup, Update},
+        ecs::{query::With, world::World},
+        input::{keyboard::KeyCode, mouse::MouseButton, ButtonInput},
+    };
+
+    use super::{nudge, spawn, CameraKeybinds, NudgeSettings, OrbitState, PrimaryCameraMarker};
+
+    fn app() -> App {
+        let mut app = App::new();
+        app.init_resource::<ButtonInput<KeyCode>>();
+        app.init_resource::<ButtonInput<MouseButton>>();
+        app.init_resource::<CameraKeybinds>();
+        app.init_resource::<NudgeSettings>();
+        app.add_systems(Startup, spawn);
+        app.add_systems(Update, nudge);
+        app.update();
+
+        app
+    }
+
+    fn state(world: &mut World) -> (f32, f32, f32) {
+        let state = world
+            .query_filtered::<&OrbitState, With<PrimaryCameraMarker>>()
+            .single(world);
+
+        (state.yaw, state.pitch, state.radius)
+    }
+
+    /// Presses only `keys` for a frame and returns how much the yaw, pitch and radius changed
+    fn press(app: &mut App, keys: &[KeyCode]) -> (f32, f32, f32) {
+        let mut kbd = app.world_mut().resource_mut::<ButtonInput<KeyCode>>();
+        kbd.reset_all();
+        for key in keys {
+            kbd.press(*key);
         }
+
+        change(app)
+    }
+
+    /// Keeps the keys held for another frame and returns the change like [press]
+    fn hold(app: &mut App) -> (f32, f32, f32) {
+        app.world_mut()
+            .resource_mut::<ButtonInput<KeyCode>>()
+            .clear();
+
+        change(app)
+    }
+
+    fn change(app: &mut App) -> (f32, f32, f32) {
+        let before = state(app.world_mut());
+        app.update();
+        let after = state(app.world_mut());
+
+        (after.0 - before.0, after.1 - before.1, after.2 - before.2)
+    }
+
+    fn assert_near((a, b, c): (f32, f32, f32), (x, y, z): (f32, f32, f32)) {
+        let close = |a: f32, b: f32| (a - b).abs() < 1e-5;
+        assert!(
+            close(a, x) && close(b, y) && close(c, z),
+            "({a}, {b}, {c}) isn't ({x}, {y}, {z})"
+        );
+    }
+
+    #[test]
+    fn fine() {
+        let mut app = app();
+        let (angle, distance) = {
+            let settings = app.world().resource::<NudgeSettings>();
+            (settings.angle, settings.distance)
+        };
+
+        assert_near(press(&mut app, &[KeyCode::Numpad4]), (angle, 0.0, 0.0));
+        assert_near(press(&mut app, &[KeyCode::Numpad6]), (-angle, 0.0, 0.0));
+        assert_near(press(&mut app, &[KeyCode::Numpad8]), (0.0, angle, 0.0));
+        assert_near(press(&mut app, &[KeyCode::Numpad2]), (0.0, -angle, 0.0));
+        assert_near(
+            press(&mut app, &[KeyCode::NumpadSubtract]),
+            (0.0, 0.0, distance),
+        );
+        assert_near(
+            press(&mut app, &[KeyCode::NumpadAdd]),
+            (0.0, 0.0, -distance),
+        );
+    }
+
+    #[test]
+    fn coarse() {
+        let mut app = app();
+        let angle = app.world().resource::<NudgeSettings>().angle;
+
+        let change = press(&mut app, &[KeyCode::ShiftLeft, KeyCode::Numpad8]);
+        assert_near(change, (0.0, angle * 10.0, 0.0));
+
+        // Holding the key doesn't keep nudging
+        assert_near(hold(&mut app), (0.0, 0.0, 0.0));
+    }
+}
+
+#[cfg(test)]
+mod freezing {
+    use bevy::{
+        app::{App, Startup, Update},
+        ecs::{entity::Entity, query::With, schedule::IntoSystemConfigs, world::World},
+        input::{
+            gestures::PanGesture,
+            keyboard::KeyCode,
+            mouse::{MouseButton, MouseMotion, MouseWheel},
+            ButtonInput,
+        },
+        math::Vec3,
+        time::Time,
+        transform::components::Transform,
+    };
+
+    use super::{
+        spawn, toggle_follow, update_camera, CameraKeybinds, CameraTarget, OrbitState,
+        PrimaryCameraMarker,
+    };
+
+    fn app() -> (App, Entity) {
+        let mut app = App::new();
+        app.init_resource::<Time>();
+        app.init_resource::<ButtonInput<KeyCode>>();
+        app.init_resource::<ButtonInput<MouseButton>>();
+        app.init_resource::<CameraKeybinds>();
+        app.add_event::<MouseMotion>();
+        app.add_event::<PanGesture>();
+        app.add_event::<MouseWheel>();
+        app.add_systems(Startup, spawn);
+        app.add_systems(Update, (toggle_follow, update_camera).chain());
+
+        let target = app
+            .world_mut()
+            .spawn((Transform::from_xyz(1.0, 2.0, 3.0), CameraTarget))
+            .id();
+
+        (app, target)
+    }
+
+    fn press_freeze(app: &mut App) {
+        let mut kbd = app.world_mut().resource_mut::<ButtonInput<KeyCode>>();
+        kbd.reset_all();
+        kbd.press(KeyCode::KeyF);
+    }
+
+    fn release(app: &mut App) {
+        let mut kbd = app.world_mut().resource_mut::<ButtonInput<KeyCode>>();
+        kbd.reset_all();
+    }
+
+    fn move_target(app: &mut App, target: Entity, pos: Vec3) {
+        app.world_mut()
+            .get_mut::<Transform>(target)
+            .unwrap()
+            .translation = pos;
+    }
+
+    fn orbited(world: &mut World) -> Vec3 {
+        world
+            .query_filtered::<&OrbitState, With<PrimaryCameraMarker>>()
+            .single(world)
+            .target
+    }
+
+    #[test]
+    fn stays_put() {
+        let (mut app, target) = app();
+        app.update();
+        assert_eq!(orbited(app.world_mut()), Vec3::new(1.0, 2.0, 3.0));
+
+        press_freeze(&mut app);
+        app.update();
+        release(&mut app);
+
+        // The target flies off, the camera doesn't follow
+        for x in 1..10 {
+            move_target(&mut app, target, Vec3::X * x as f32 * 10.0);
+            app.update();
+            assert_eq!(orbited(app.world_mut()), Vec3::new(1.0, 2.0, 3.0));
+        }
+
+        // Following again catches up right away
+        press_freeze(&mut app);
+        app.update();
+        assert_eq!(orbited(app.world_mut()), Vec3::X * 90.0);
+    }
+}
+
+#[cfg(test)]
+mod interpolated {
+    use bevy::{
+        app::{App, Startup, Update},
+        ecs::query::With,
+        input::{
+            gestures::PanGesture,
+            keyboard::KeyCode,
+            mouse::{MouseButton, MouseMotion, MouseWheel},
+            ButtonInput,
+        },
+        math::Vec3,
+        time::{Fixed, Time},
+        transform::components::Transform,
+    };
+    use physics::interpolation::StepTransforms;
+
+    use super::{
+        spawn, target_position, update_camera, CameraTarget, OrbitState, PrimaryCameraMarker,
+    };
+
+    fn steps() -> StepTransforms {
+        StepTransforms {
+            previous: Transform::from_translation(Vec3::new(1.0, 2.0, 3.0)),
+            current: Transform::from_translation(Vec3::new(3.0, 2.0, 1.0)),
+        }
+    }
+
+    #[test]
+    fn between_steps() {
+        let raw = Transform::from_translation(Vec3::splat(100.0));
+
+        assert_eq!(
+            target_position(&raw, Some(&steps()), 0.5),
+            Vec3::new(2.0, 2.0, 2.0)
+        );
+        assert_eq!(target_position(&raw, None, 0.5), Vec3::splat(100.0));
+    }
+
+    #[test]
+    fn follows_steps() {
+        let mut app = App::new();
+        app.init_resource::<Time>();
+        app.init_resource::<Time<Fixed>>();
+        app.init_resource::<ButtonInput<KeyCode>>();
+        app.init_resource::<ButtonInput<MouseButton>>();
+        app.add_event::<MouseMotion>();
+        app.add_event::<PanGesture>();
+        app.add_event::<MouseWheel>();
+        app.add_systems(Startup, spawn);
+        app.add_systems(Update, update_camera);
+
+        // Still at the raw result of the step, the way it is before interpolating
+        app.world_mut().spawn((
+            Transform::from_translation(Vec3::new(3.0, 2.0, 1.0)),
+            steps(),
+            CameraTarget,
+        ));
+
+        app.update();
+
+        // No time past the last step, so right at the previous one
+        let world = app.world_mut();
+        let state = world
+            .query_filtered::<&OrbitState, With<PrimaryCameraMarker>>()
+            .single(world);
+        assert_eq!(state.target, Vec3::new(1.0, 2.0, 3.0));
+    }
+}
+
+#[cfg(test)]
+mod sensitivity {
+    use bevy::{
+        app::{App, Update},
+        ecs::{entity::Entity, query::With, schedule::IntoSystemConfigs},
+        input::{
+            gestures::PanGesture,
+            keyboard::KeyCode,
+            mouse::{MouseButton, MouseMotion, MouseScrollUnit, MouseWheel},
+            ButtonInput,
+        },
+        time::Time,
+    };
+
+    use super::{
+        update_camera, validate_settings, OrbitCam, OrbitSettings, OrbitState, PrimaryCameraMarker,
+        DEFAULT_ORBIT_SENSITIVITY, DEFAULT_SCROLL_LINE, DEFAULT_SCROLL_PIXEL,
+    };
+
+    fn assert_near(a: f32, b: f32) {
+        assert!((a - b).abs() < 1e-5, "{a} isn't {b}");
+    }
+
+    #[test]
+    fn replaced() {
+        let mut settings = OrbitSettings {
+            orbit_sensitivity: 0.02,
+            scroll_sensitivity_line: 0.0,
+            scroll_sensitivity_pixel: -0.5,
+            ..Default::default()
+        };
+        settings.validate();
+
+        assert_eq!(settings.orbit_sensitivity, 0.02);
+        assert_eq!(settings.scroll_sensitivity_line, DEFAULT_SCROLL_LINE);
+        assert_eq!(settings.scroll_sensitivity_pixel, DEFAULT_SCROLL_PIXEL);
+
+        settings.scroll_sensitivity_line = f32::NAN;
+        settings.validate();
+        assert_eq!(settings.scroll_sensitivity_line, DEFAULT_SCROLL_LINE);
+    }
+
+    #[test]
+    fn default_is_valid() {
+        // Nothing validate would have to replace
+        let settings = OrbitSettings::default();
+
+        assert_eq!(settings.orbit_sensitivity, DEFAULT_ORBIT_SENSITIVITY);
+        assert_eq!(settings.scroll_sensitivity_line, DEFAULT_SCROLL_LINE);
+        assert_eq!(settings.scroll_sensitivity_pixel, DEFAULT_SCROLL_PIXEL);
+    }
+
+    /// Radius of a camera spawned with `line` sensitivity after scrolling up two lines
+    fn zoomed(line: f32) -> f32 {
+        let mut app = App::new();
+        app.init_resource::<Time>();
+        app.init_resource::<ButtonInput<KeyCode>>();
+        app.init_resource::<ButtonInput<MouseButton>>();
+        app.add_event::<MouseMotion>();
+        app.add_event::<PanGesture>();
+        app.add_event::<MouseWheel>();
+        app.add_systems(Update, (validate_settings, update_camera).chain());
+
+        app.world_mut().spawn((
+            OrbitCam {
+                settings: OrbitSettings {
+                    scroll_sensitivity_line: line,
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            PrimaryCameraMarker,
+        ));
+
+        app.world_mut().send_event(MouseWheel {
+            unit: MouseScrollUnit::Line,
+            x: 0.0,
+            y: 2.0,
+            window: Entity::PLACEHOLDER,
+        });
+        app.update();
+
+        app.world_mut()
+            .query_filtered::<&OrbitState, With<PrimaryCameraMarker>>()
+            .single(app.world())
+            .radius
+    }
+
+    #[test]
+    fn still_zooms() {
+        // Starts out 10 away
+        assert_near(zoomed(0.2), 10.0 * (-0.4f32).exp());
+        assert_near(zoomed(0.0), 10.0 * (-2.0 * DEFAULT_SCROLL_LINE).exp());
+    }
+}
+
+#[cfg(test)]
+mod lens {
+    use bevy::{
+        app::{App, Startup, Update},
+        ecs::query::With,
+        render::camera::{Exposure, Projection},
+    };
+
+    use super::{spawn, update_lens, OrbitSettings, PrimaryCameraMarker};
+
+    fn clip(app: &mut App) -> (f32, f32, f32) {
+        let mut cams = app
+            .world_mut()
+            .query_filtered::<(&Projection, &Exposure), With<PrimaryCameraMarker>>();
+        let (projection, exposure) = cams.single(app.world());
+
+        let Projection::Perspective(perspective) = projection else {
+            panic!("Expected a perspective projection");
+        };
+        (perspective.near, perspective.far, exposure.ev100)
+    }
+
+    #[test]
+    fn applied() {
+        let mut app = App::new();
+        app.add_systems(Startup, spawn);
+        app.add_systems(Update, update_lens);
+
+        app.update();
+
+        let defaults = OrbitSettings::default();
+        assert_eq!(
+            clip(&mut app),
+            (defaults.near, defaults.far, defaults.exposure)
+        );
+
+        let mut cams = app
+            .world_mut()
+            .query_filtered::<&mut OrbitSettings, With<PrimaryCameraMarker>>();
+        let mut settings = cams.single_mut(app.world_mut());
+        settings.near = 10.0;
+        settings.far = 1e9;
+        settings.exposure = Exposure::EV100_SUNLIGHT;
+
+        app.update();
+
+        assert_eq!(clip(&mut app), (10.0, 1e9, Exposure::EV100_SUNLIGHT));
     }
 }
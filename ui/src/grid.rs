@@ -0,0 +1,115 @@
+use bevy::{
+    app::{App, Plugin, PostUpdate},
+    ecs::{
+        query::With,
+        schedule::IntoSystemConfigs,
+        system::{Query, Res, Resource},
+    },
+    math::Vec3,
+    transform::{components::Transform, TransformSystem},
+};
+use bevy_infinite_grid::InfiniteGridSettings;
+
+use crate::camera::{OrbitState, PrimaryCameraMarker};
+
+/// Where the infinite grid is placed along the y axis
+#[derive(Resource, Debug, Clone, Copy, PartialEq)]
+pub enum GridHeight {
+    /// Stays at a fixed height
+    Fixed(f32),
+
+    /// Slides along with the point the primary camera orbits, `below` meters under it
+    ///
+    /// Keeps a reference close by when the [CameraTarget](crate::camera::CameraTarget) flies
+    /// far above the ground.
+    FollowTarget { below: f32 },
+}
+
+pub struct GridPlugin;
+
+impl GridHeight {
+    /// Gets the height of the grid when the camera orbits `target`
+    #[must_use]
+    pub fn height(&self, target: Vec3) -> f32 {
+        match *self {
+            GridHeight::Fixed(height) => height,
+            GridHeight::FollowTarget { below } => target.y - below,
+        }
+    }
+}
+
+/// Moves every infinite grid to the [GridHeight]
+fn follow_target(
+    height: Res<GridHeight>,
+    cam: Query<&OrbitState, With<PrimaryCameraMarker>>,
+    mut grids: Query<&mut Transform, With<InfiniteGridSettings>>,
+) {
+    let target = cam.get_single().map_or(Vec3::ZERO, |state| state.target);
+    let y = height.height(target);
+
+    for mut trans in grids.iter_mut() {
+        trans.translation.y = y;
+    }
+}
+
+impl Plugin for GridPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<GridHeight>();
+        app.add_systems(
+            PostUpdate,
+            follow_target.before(TransformSystem::TransformPropagate),
+        );
+    }
+}
+
+impl Default for GridHeight {
+    fn default() -> Self {
+        Self::Fixed(0.0)
+    }
+}
+
+#[cfg(test)]
+mod follow {
+    use bevy::{app::App, math::Vec3, transform::components::Transform, utils::default};
+    use bevy_infinite_grid::InfiniteGridBundle;
+
+    use super::{GridHeight, GridPlugin};
+    use crate::camera::{OrbitState, PrimaryCameraMarker};
+
+    #[test]
+    fn height() {
+        let target = Vec3::new(40.0, 250.0, -3.0);
+
+        assert_eq!(GridHeight::Fixed(2.0).height(target), 2.0);
+        assert_eq!(
+            GridHeight::FollowTarget { below: 0.0 }.height(target),
+            250.0
+        );
+        assert_eq!(
+            GridHeight::FollowTarget { below: 10.0 }.height(target),
+            240.0
+        );
+        assert_eq!(GridHeight::default().height(target), 0.0);
+    }
+
+    #[test]
+    fn moves_grid() {
+        let mut app = App::new();
+        app.add_plugins(GridPlugin);
+        app.insert_resource(GridHeight::FollowTarget { below: 5.0 });
+
+        app.world_mut().spawn((
+            OrbitState {
+                target: Vec3::new(1.0, 100.0, 2.0),
+                ..default()
+            },
+            PrimaryCameraMarker,
+        ));
+        let grid = app.world_mut().spawn(InfiniteGridBundle::default()).id();
+
+        app.update();
+
+        let trans = app.world().get::<Transform>(grid).unwrap();
+        assert_eq!(trans.translation, Vec3::Y * 95.0);
+    }
+}
@@ -1,2 +1,10 @@
+pub mod appearance;
 pub mod camera;
+pub mod grid;
 pub mod keybinds;
+pub mod measure;
+pub mod placement;
+pub mod replay;
+pub mod simulation;
+pub mod spin;
+pub mod stats;
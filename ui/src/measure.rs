@@ -0,0 +1,320 @@
+use bevy::{
+    app::{App, Plugin, Startup, Update},
+    color::Color,
+    ecs::{
+        component::Component,
+        entity::Entity,
+        query::With,
+        schedule::IntoSystemConfigs,
+        system::{Commands, Query, Res, ResMut, Resource},
+    },
+    gizmos::gizmos::Gizmos,
+    input::{keyboard::KeyCode, mouse::MouseButton, ButtonInput},
+    math::{Quat, Ray3d, Vec3},
+    render::{camera::Camera, view::Visibility},
+    text::{Text, TextStyle},
+    transform::components::{GlobalTransform, Transform},
+    ui::{node_bundles::TextBundle, PositionType, Style, Val},
+    utils::default,
+    window::{PrimaryWindow, Window},
+};
+use physics::{
+    components::{velocity::Velocity, Simulated},
+    prediction::{closest_approach, closing_speed, relative_velocity, ClosestApproach},
+};
+
+use crate::{
+    camera::PrimaryCameraMarker,
+    keybinds::{Keybind, KeybindOptions},
+};
+
+/// Settings for measuring between two bodies
+#[derive(Resource, Debug)]
+pub struct MeasureSettings {
+    /// Selects the body under the cursor when pressed
+    pub select_key: Keybind,
+
+    /// How far from the center of a body in meters the cursor can point and still select it
+    pub pick_radius: f32,
+}
+
+/// The bodies being measured between, oldest first
+#[derive(Resource, Debug, Default, Clone, PartialEq, Eq)]
+pub struct MeasuredBodies(pub Vec<Entity>);
+
+/// Separation and relative motion of two bodies
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Measurement {
+    /// Distance between the centers in meters
+    pub distance: f32,
+
+    /// Speed of the second body as seen from the first, in m/s
+    pub relative_speed: f32,
+
+    /// How fast the distance is shrinking in m/s, negative when separating
+    pub closing_speed: f32,
+
+    /// When and where the bodies will be nearest, if neither changes velocity
+    pub closest_approach: ClosestApproach,
+}
+
+/// Marks the text showing the [Measurement]
+#[derive(Component, Debug)]
+pub struct MeasurePanel;
+
+pub struct MeasurePlugin;
+
+impl MeasuredBodies {
+    /// Adds a body, replacing the oldest one if two are already selected
+    ///
+    /// Selecting a body that is already measured does nothing.
+    pub fn select(&mut self, entity: Entity) {
+        if self.0.contains(&entity) {
+            return;
+        }
+
+        self.0.push(entity);
+        if self.0.len() > 2 {
+            self.0.remove(0);
+        }
+    }
+}
+
+impl Measurement {
+    /// Measures between two bodies from their positions and velocities
+    #[must_use]
+    pub fn new(a_pos: Vec3, a_vel: &Velocity, b_pos: Vec3, b_vel: &Velocity) -> Self {
+        Self {
+            distance: a_pos.distance(b_pos),
+            relative_speed: relative_velocity(a_vel, b_vel).length(),
+            closing_speed: closing_speed(a_pos, a_vel.0, b_pos, b_vel.0),
+            closest_approach: closest_approach(a_pos, a_vel.0, b_pos, b_vel.0),
+        }
+    }
+}
+
+/// Finds the body closest along `ray` that the ray passes within `radius` of
+pub(crate) fn pick<'a>(
+    ray: Ray3d,
+    radius: f32,
+    bodies: impl Iterator<Item = (Entity, &'a Transform)>,
+) -> Option<Entity> {
+    bodies
+        .filter_map(|(entity, trans)| {
+            let along = (trans.translation - ray.origin).dot(*ray.direction);
+            let miss = ray.get_point(along).distance(trans.translation);
+
+            (along >= 0.0 && miss <= radius).then_some((entity, along))
+        })
+        .min_by(|a, b| a.1.total_cmp(&b.1))
+        .map(|(entity, _)| entity)
+}
+
+/// Spawns the panel in the bottom left corner, hidden until two bodies are selected
+fn spawn(mut cmds: Commands) {
+    let text = TextBundle::from_section(
+        "",
+        TextStyle {
+            font_size: 18.0,
+            ..default()
+        },
+    )
+    .with_style(Style {
+        position_type: PositionType::Absolute,
+        bottom: Val::Px(10.0),
+        left: Val::Px(10.0),
+        ..default()
+    });
+
+    cmds.spawn((
+        TextBundle {
+            visibility: Visibility::Hidden,
+            ..text
+        },
+        MeasurePanel,
+    ));
+}
+
+/// Selects the body under the cursor when the keybind is pressed
+#[allow(clippy::too_many_arguments)]
+fn select(
+    kbd: Res<ButtonInput<KeyCode>>,
+    mos: Res<ButtonInput<MouseButton>>,
+    settings: Res<MeasureSettings>,
+    mut measured: ResMut<MeasuredBodies>,
+    window: Query<&Window, With<PrimaryWindow>>,
+    cam: Query<(&Camera, &GlobalTransform), With<PrimaryCameraMarker>>,
+    bodies: Query<(Entity, &Transform), With<Simulated>>,
+) {
+    if !settings.select_key.just_pressed(&kbd, &mos) {
+        return;
+    }
+
+    let (Ok(window), Ok((camera, cam_transform))) = (window.get_single(), cam.get_single()) else {
+        return;
+    };
+
+    let ray = window
+        .cursor_position()
+        .and_then(|cursor| camera.viewport_to_world(cam_transform, cursor));
+
+    if let Some(entity) = ray.and_then(|ray| pick(ray, settings.pick_radius, bodies.iter())) {
+        measured.select(entity);
+    }
+}
+
+/// Shows the [Measurement] between the selected bodies and draws a line connecting them
+///
+/// Also marks where both will be at their closest approach, joined by a thinner line. Those
+/// markers sit on the bodies when they are already separating.
+fn show(
+    mut measured: ResMut<MeasuredBodies>,
+    bodies: Query<(&Transform, &Velocity), With<Simulated>>,
+    mut panels: Query<(&mut Text, &mut Visibility), With<MeasurePanel>>,
+    mut gizmos: Gizmos,
+) {
+    // Forget bodies that were despawned or stopped being simulated
+    measured.0.retain(|&entity| bodies.contains(entity));
+
+    let pair = match measured.0[..] {
+        [a, b] => bodies.get(a).ok().zip(bodies.get(b).ok()),
+        _ => None,
+    };
+
+    let Some(((a_trans, a_vel), (b_trans, b_vel))) = pair else {
+        for (_, mut visibility) in panels.iter_mut() {
+            *visibility = Visibility::Hidden;
+        }
+        return;
+    };
+
+    let (a_pos, b_pos) = (a_trans.translation, b_trans.translation);
+    let measurement = Measurement::new(a_pos, a_vel, b_pos, b_vel);
+
+    gizmos.line(a_pos, b_pos, Color::srgb(0.9, 0.9, 0.0));
+
+    let closest = measurement.closest_approach;
+    let marker = Color::srgb(0.9, 0.5, 0.0);
+    gizmos.sphere(closest.a, Quat::IDENTITY, 0.25, marker);
+    gizmos.sphere(closest.b, Quat::IDENTITY, 0.25, marker);
+    gizmos.line(closest.a, closest.b, marker);
+
+    let text = format!(
+        "Distance: {:.2} m\nRelative speed: {:.2} m/s\nClosing speed: {:.2} m/s\n\
+         Closest approach: {:.2} m in {:.2} s",
+        measurement.distance,
+        measurement.relative_speed,
+        measurement.closing_speed,
+        closest.distance(),
+        closest.time
+    );
+
+    for (mut panel, mut visibility) in panels.iter_mut() {
+        panel.sections[0].value.clone_from(&text);
+        *visibility = Visibility::Visible;
+    }
+}
+
+impl Plugin for MeasurePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<MeasureSettings>();
+        app.init_resource::<MeasuredBodies>();
+        app.add_systems(Startup, spawn);
+        app.add_systems(Update, (select, show).chain());
+    }
+}
+
+impl Default for MeasureSettings {
+    fn default() -> Self {
+        Self {
+            select_key: Keybind(vec![KeybindOptions::Keyboard(KeyCode::KeyM)]),
+            pick_radius: 1.0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod measuring {
+    use bevy::{
+        ecs::entity::Entity,
+        math::{Dir3, Ray3d, Vec3},
+        transform::components::Transform,
+    };
+    use physics::components::velocity::Velocity;
+
+    use super::{pick, MeasuredBodies, Measurement};
+
+    fn assert_near(a: f32, b: f32) {
+        assert!((a - b).abs() < 1e-5, "{a} isn't {b}");
+    }
+
+    #[test]
+    fn approaching() {
+        let measurement = Measurement::new(
+            Vec3::ZERO,
+            &Velocity(Vec3::X * 2.0),
+            Vec3::new(3.0, 4.0, 0.0),
+            &Velocity(Vec3::new(-1.0, 0.0, 0.0)),
+        );
+
+        // 3 m/s towards each other along x, of which 3/5 is along the line between them
+        assert_near(measurement.distance, 5.0);
+        assert_near(measurement.relative_speed, 3.0);
+        assert_near(measurement.closing_speed, 1.8);
+
+        // Level along x after a second, still 4 m apart along y
+        assert_near(measurement.closest_approach.time, 1.0);
+        assert_near(measurement.closest_approach.distance(), 4.0);
+    }
+
+    #[test]
+    fn separating() {
+        let measurement = Measurement::new(
+            Vec3::ZERO,
+            &Velocity(Vec3::ZERO),
+            Vec3::Y * 2.0,
+            &Velocity(Vec3::Y),
+        );
+
+        assert_near(measurement.distance, 2.0);
+        assert_near(measurement.closing_speed, -1.0);
+    }
+
+    #[test]
+    fn replaces_oldest() {
+        let [a, b, c] = [0, 1, 2].map(Entity::from_raw);
+        let mut measured = MeasuredBodies::default();
+
+        measured.select(a);
+        measured.select(b);
+        measured.select(b);
+        assert_eq!(measured.0, vec![a, b]);
+
+        measured.select(c);
+        assert_eq!(measured.0, vec![b, c]);
+    }
+
+    #[test]
+    fn picks_closest() {
+        let [near, far, off] = [0, 1, 2].map(Entity::from_raw);
+        let bodies = [
+            (far, Transform::from_xyz(0.0, 0.0, -20.0)),
+            (near, Transform::from_xyz(0.5, 0.0, -10.0)),
+            (off, Transform::from_xyz(5.0, 0.0, -5.0)),
+        ];
+        let ray = Ray3d {
+            origin: Vec3::ZERO,
+            direction: Dir3::NEG_Z,
+        };
+
+        let picked = pick(ray, 1.0, bodies.iter().map(|(e, t)| (*e, t)));
+        assert_eq!(picked, Some(near));
+
+        let behind = pick(
+            ray,
+            1.0,
+            [(off, &Transform::from_xyz(0.0, 0.0, 5.0))].into_iter(),
+        );
+        assert_eq!(behind, None);
+    }
+}
@@ -0,0 +1,346 @@
+use bevy::{
+    app::{App, Plugin, Update},
+    asset::AssetServer,
+    color::Color,
+    ecs::{
+        query::With,
+        system::{Commands, Local, Query, Res, Resource},
+    },
+    gizmos::gizmos::Gizmos,
+    hierarchy::BuildChildren,
+    input::{keyboard::KeyCode, mouse::MouseButton, ButtonInput},
+    math::{primitives::InfinitePlane3d, Vec3},
+    render::camera::Camera,
+    scene::SceneBundle,
+    transform::components::{GlobalTransform, Transform},
+    utils::default,
+    window::{PrimaryWindow, Window},
+};
+use physics::{
+    components::{
+        acceleration::Accelerator, inertia::Inertia, velocity::AngularVelocity, velocity::Velocity,
+        BodyColor, SimulationBundle,
+    },
+    presets::CircularOrbit,
+    resources::CentralBody,
+};
+
+use crate::{
+    camera::PrimaryCameraMarker,
+    keybinds::{Keybind, KeybindOptions},
+};
+
+/// Settings for placing new bodies by clicking on the ground
+#[derive(Resource, Debug)]
+pub struct PlacementSettings {
+    /// Places a body under the cursor when pressed
+    pub place_key: Keybind,
+
+    /// Distance between grid points the placed bodies are snapped to, 0 disables snapping
+    pub spacing: f32,
+
+    /// Launch speed for every meter dragged before releasing the place key
+    pub launch_scale: f32,
+
+    /// Places a body in orbit around the [CentralBody] when pressed
+    pub orbit_key: Keybind,
+
+    /// Orbit the bodies placed with the orbit key are put in
+    pub orbit: CircularOrbit,
+}
+
+/// A body being aimed, spawned when the place key is released
+#[derive(Debug, Clone, Copy)]
+struct PendingPlacement {
+    /// Where on the ground the place key was pressed, the drag is measured from here
+    press: Vec3,
+
+    /// Where the body is spawned, the press snapped to the grid
+    origin: Vec3,
+
+    velocity: Vec3,
+}
+
+impl PendingPlacement {
+    /// Starts aiming a body pressed at `press`, spawned on the closest grid point
+    fn new(press: Vec3, spacing: f32) -> Self {
+        Self {
+            press,
+            origin: snap_to_grid(press, spacing),
+            velocity: Vec3::ZERO,
+        }
+    }
+
+    /// Aims the launch at the cursor, from the point that was pressed rather than the snapped one
+    fn aim(&mut self, cursor: Vec3, scale: f32) {
+        self.velocity = drag_velocity(self.press, cursor, scale);
+    }
+}
+
+pub struct PlacementPlugin;
+
+/// Rounds every component of `pos` to the nearest multiple of `spacing`
+///
+/// A `spacing` of zero or less leaves the position as is.
+///
+/// ```rust
+/// # use ui::placement::snap_to_grid;
+/// # use bevy::math::Vec3;
+/// let snapped = snap_to_grid(Vec3::new(1.4, -2.6, 0.2), 1.0);
+///
+/// assert_eq!(snapped, Vec3::new(1.0, -3.0, 0.0));
+/// ```
+#[must_use]
+pub fn snap_to_grid(pos: Vec3, spacing: f32) -> Vec3 {
+    if spacing <= 0.0 {
+        return pos;
+    }
+
+    (pos / spacing).round() * spacing
+}
+
+/// Color of the `n`th placed body
+///
+/// Steps the hue by the golden angle, so consecutive bodies never get similar colors.
+fn placed_color(n: u32) -> Color {
+    Color::hsl((n as f32 * 137.5) % 360.0, 0.7, 0.5)
+}
+
+/// Inertia of a placed body, a solid 1 kg cube matching the 2 m sides of `cube.glb`
+fn cube_inertia() -> Inertia {
+    Inertia::cuboid(2.0, 2.0, 2.0, 1.0)
+}
+
+/// Gets where the cursor points at the ground, if it does
+fn cursor_on_ground(
+    window: &Window,
+    camera: &Camera,
+    cam_transform: &GlobalTransform,
+) -> Option<Vec3> {
+    let ray = window
+        .cursor_position()
+        .and_then(|cursor| camera.viewport_to_world(cam_transform, cursor))?;
+
+    let distance = ray.intersect_plane(Vec3::ZERO, InfinitePlane3d::new(Vec3::Y))?;
+
+    Some(ray.get_point(distance))
+}
+
+/// Launch velocity for a drag on the ground from `from` to `to`
+///
+/// The body is launched in the direction of the drag, faster the longer the drag is.
+///
+/// ```rust
+/// # use ui::placement::drag_velocity;
+/// # use bevy::math::Vec3;
+/// let vel = drag_velocity(Vec3::new(1.0, 0.0, 1.0), Vec3::new(4.0, 0.0, 5.0), 2.0);
+///
+/// assert_eq!(vel, Vec3::new(6.0, 0.0, 8.0));
+/// ```
+#[must_use]
+pub fn drag_velocity(from: Vec3, to: Vec3, scale: f32) -> Vec3 {
+    (to - from) * scale
+}
+
+/// Places a body on the ground and launches it by dragging
+///
+/// Pressing the place key picks the spawn point under the cursor, dragging while holding it aims
+/// the launch velocity, shown as an arrow, and releasing it spawns the body. A click without
+/// dragging places the body standing still.
+#[allow(clippy::too_many_arguments)]
+fn place_body(
+    mut commands: Commands,
+    mut placed: Local<u32>,
+    mut pending: Local<Option<PendingPlacement>>,
+    ass: Res<AssetServer>,
+    kbd: Res<ButtonInput<KeyCode>>,
+    mos: Res<ButtonInput<MouseButton>>,
+    settings: Res<PlacementSettings>,
+    window: Query<&Window, With<PrimaryWindow>>,
+    cam: Query<(&Camera, &GlobalTransform), With<PrimaryCameraMarker>>,
+    mut gizmos: Gizmos,
+) {
+    let (Ok(window), Ok((camera, cam_transform))) = (window.get_single(), cam.get_single()) else {
+        return;
+    };
+    let cursor = cursor_on_ground(window, camera, cam_transform);
+
+    if settings.place_key.just_pressed(&kbd, &mos) {
+        *pending = cursor.map(|pos| PendingPlacement::new(pos, settings.spacing));
+    }
+
+    let Some(placement) = pending.as_mut() else {
+        return;
+    };
+
+    // Keep the last aim if the cursor leaves the ground
+    if let Some(cursor) = cursor {
+        placement.aim(cursor, settings.launch_scale);
+    }
+
+    if settings.place_key.pressed(&kbd, &mos) {
+        if placement.velocity != Vec3::ZERO {
+            gizmos.arrow(
+                placement.origin,
+                placement.origin + placement.velocity,
+                Color::WHITE,
+            );
+        }
+        return;
+    }
+
+    let PendingPlacement {
+        origin, velocity, ..
+    } = *placement;
+    *pending = None;
+
+    let mut body = SimulationBundle::new(
+        Velocity(velocity),
+        Accelerator::ZERO,
+        AngularVelocity::ZERO,
+        cube_inertia(),
+    );
+    body.spatial.transform = Transform::from_translation(origin);
+
+    let color = BodyColor(placed_color(*placed));
+    *placed += 1;
+
+    commands.spawn((body, color)).with_children(|parent| {
+        parent.spawn(SceneBundle {
+            scene: ass.load("cube.glb#Scene0"),
+            ..default()
+        });
+    });
+}
+
+/// Places a body in a circular orbit around the [CentralBody] when the orbit key is pressed
+///
+/// The radius and speed are measured from the central body itself, so the orbit stays circular
+/// wherever the camera is looking. Nothing is placed without one.
+fn place_satellite(
+    mut commands: Commands,
+    ass: Res<AssetServer>,
+    kbd: Res<ButtonInput<KeyCode>>,
+    mos: Res<ButtonInput<MouseButton>>,
+    settings: Res<PlacementSettings>,
+    central: Option<Res<CentralBody>>,
+) {
+    if !settings.orbit_key.just_pressed(&kbd, &mos) {
+        return;
+    }
+
+    let Some(central) = central else {
+        return;
+    };
+
+    let body = settings
+        .orbit
+        .bundle(central.position, central.mu, cube_inertia());
+
+    commands.spawn(body).with_children(|parent| {
+        parent.spawn(SceneBundle {
+            scene: ass.load("cube.glb#Scene0"),
+            ..default()
+        });
+    });
+}
+
+impl Plugin for PlacementPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<PlacementSettings>();
+        app.add_systems(Update, (place_body, place_satellite));
+    }
+}
+
+impl Default for PlacementSettings {
+    fn default() -> Self {
+        Self {
+            place_key: Keybind(vec![KeybindOptions::MouseButton(MouseButton::Middle)]),
+            spacing: 1.0,
+            launch_scale: 2.0,
+            orbit_key: Keybind(vec![KeybindOptions::Keyboard(KeyCode::KeyO)]),
+            orbit: CircularOrbit {
+                radius: 20.0,
+                inclination: 0.0,
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod snapping {
+    use bevy::math::Vec3;
+
+    use super::snap_to_grid;
+
+    #[test]
+    fn positive() {
+        assert_eq!(
+            snap_to_grid(Vec3::new(0.4, 0.6, 2.49), 1.0),
+            Vec3::new(0.0, 1.0, 2.0)
+        );
+        assert_eq!(
+            snap_to_grid(Vec3::new(3.9, 6.1, 0.0), 2.0),
+            Vec3::new(4.0, 6.0, 0.0)
+        );
+    }
+
+    #[test]
+    fn negative() {
+        assert_eq!(
+            snap_to_grid(Vec3::new(-0.4, -0.6, -2.51), 1.0),
+            Vec3::new(0.0, -1.0, -3.0)
+        );
+        assert_eq!(
+            snap_to_grid(Vec3::new(-3.9, -6.1, -1.1), 2.0),
+            Vec3::new(-4.0, -6.0, -2.0)
+        );
+    }
+
+    #[test]
+    fn disabled() {
+        let pos = Vec3::new(0.3, -1.7, 5.5);
+
+        assert_eq!(snap_to_grid(pos, 0.0), pos);
+        assert_eq!(snap_to_grid(pos, -1.0), pos);
+    }
+}
+
+#[cfg(test)]
+mod launching {
+    use bevy::math::Vec3;
+
+    use super::{drag_velocity, PendingPlacement};
+
+    #[test]
+    fn scaled_by_length() {
+        let from = Vec3::new(2.0, 0.0, -1.0);
+
+        assert_eq!(drag_velocity(from, from + Vec3::X, 3.0), Vec3::X * 3.0);
+        assert_eq!(
+            drag_velocity(from, from + Vec3::new(0.0, 0.0, -4.0), 3.0),
+            Vec3::Z * -12.0
+        );
+    }
+
+    #[test]
+    fn no_drag() {
+        let at = Vec3::new(5.0, 0.0, 5.0);
+
+        assert_eq!(drag_velocity(at, at, 3.0), Vec3::ZERO);
+    }
+    #[test]
+    fn click_off_grid() {
+        // Pressed between grid points and released without moving
+        let press = Vec3::new(0.4, 0.0, -1.3);
+        let mut placement = PendingPlacement::new(press, 1.0);
+        placement.aim(press, 2.0);
+
+        assert_eq!(placement.origin, Vec3::new(0.0, 0.0, -1.0));
+        assert_eq!(placement.velocity, Vec3::ZERO);
+
+        // Still measured from the press once dragged
+        placement.aim(press + Vec3::X, 2.0);
+        assert_eq!(placement.velocity, Vec3::X * 2.0);
+    }
+}
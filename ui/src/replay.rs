@@ -0,0 +1,52 @@
+use bevy::{
+    app::{App, Plugin, Update},
+    ecs::{
+        event::EventWriter,
+        system::{Res, Resource},
+    },
+    input::{keyboard::KeyCode, mouse::MouseButton, ButtonInput},
+};
+use physics::replay::ReplayStep;
+
+use crate::keybinds::{Keybind, KeybindOptions};
+
+/// Keybinds used to scrub through the recorded simulation
+#[derive(Resource, Debug)]
+pub struct ReplayKeybinds {
+    pub back: Keybind,
+    pub forward: Keybind,
+}
+
+pub struct ReplayPlugin;
+
+/// Sends a [ReplayStep] for every scrub keybind pressed this frame
+fn send_steps(
+    kbd: Res<ButtonInput<KeyCode>>,
+    mos: Res<ButtonInput<MouseButton>>,
+    binds: Res<ReplayKeybinds>,
+    mut steps: EventWriter<ReplayStep>,
+) {
+    if binds.back.just_pressed(&kbd, &mos) {
+        steps.send(ReplayStep::Back);
+    }
+
+    if binds.forward.just_pressed(&kbd, &mos) {
+        steps.send(ReplayStep::Forward);
+    }
+}
+
+impl Plugin for ReplayPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ReplayKeybinds>();
+        app.add_systems(Update, send_steps);
+    }
+}
+
+impl Default for ReplayKeybinds {
+    fn default() -> Self {
+        Self {
+            back: Keybind(vec![KeybindOptions::Keyboard(KeyCode::ArrowLeft)]),
+            forward: Keybind(vec![KeybindOptions::Keyboard(KeyCode::ArrowRight)]),
+        }
+    }
+}
@@ -0,0 +1,109 @@
+use bevy::{
+    app::{App, Plugin, Update},
+    ecs::{
+        entity::Entity,
+        event::EventWriter,
+        query::With,
+        system::{Query, Res, ResMut, Resource},
+    },
+    input::{keyboard::KeyCode, mouse::MouseButton, ButtonInput},
+};
+use physics::{
+    pause::{PendingSteps, SimulationPaused},
+    reset::{PerturbBody, ResetSimulation},
+};
+
+use crate::{
+    camera::CameraTarget,
+    keybinds::{Keybind, KeybindOptions},
+};
+
+/// Keybinds used to control the simulation as a whole
+#[derive(Resource, Debug)]
+pub struct SimulationKeybinds {
+    /// Puts every object back where it started
+    pub reset: Keybind,
+
+    /// Resets like [SimulationKeybinds::reset], then slightly changes the velocity of the
+    /// [CameraTarget]
+    pub perturbed_reset: Keybind,
+
+    /// Pauses or resumes the simulation
+    pub pause: Keybind,
+
+    /// Runs a single physics step while paused
+    pub step: Keybind,
+}
+
+pub struct SimulationPlugin;
+
+/// Sends a [ResetSimulation] when the reset keybind is pressed
+fn send_reset(
+    kbd: Res<ButtonInput<KeyCode>>,
+    mos: Res<ButtonInput<MouseButton>>,
+    binds: Res<SimulationKeybinds>,
+    mut resets: EventWriter<ResetSimulation>,
+) {
+    if binds.reset.just_pressed(&kbd, &mos) {
+        resets.send(ResetSimulation);
+    }
+}
+
+/// Sends a [ResetSimulation] and a [PerturbBody] for the [CameraTarget] when the perturbed reset
+/// keybind is pressed
+///
+/// Without a target this is a plain reset.
+fn send_perturbed_reset(
+    kbd: Res<ButtonInput<KeyCode>>,
+    mos: Res<ButtonInput<MouseButton>>,
+    binds: Res<SimulationKeybinds>,
+    target: Query<Entity, With<CameraTarget>>,
+    mut resets: EventWriter<ResetSimulation>,
+    mut perturbations: EventWriter<PerturbBody>,
+) {
+    if !binds.perturbed_reset.just_pressed(&kbd, &mos) {
+        return;
+    }
+
+    resets.send(ResetSimulation);
+
+    if let Ok(entity) = target.get_single() {
+        perturbations.send(PerturbBody(entity));
+    }
+}
+
+/// Pauses, resumes and steps the simulation when the keybinds are pressed
+fn pause(
+    kbd: Res<ButtonInput<KeyCode>>,
+    mos: Res<ButtonInput<MouseButton>>,
+    binds: Res<SimulationKeybinds>,
+    mut paused: ResMut<SimulationPaused>,
+    mut pending: ResMut<PendingSteps>,
+) {
+    if binds.pause.just_pressed(&kbd, &mos) {
+        paused.0 = !paused.0;
+        pending.0 = 0;
+    }
+
+    if paused.0 && binds.step.just_pressed(&kbd, &mos) {
+        pending.0 += 1;
+    }
+}
+
+impl Plugin for SimulationPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SimulationKeybinds>();
+        app.add_systems(Update, (send_reset, send_perturbed_reset, pause));
+    }
+}
+
+impl Default for SimulationKeybinds {
+    fn default() -> Self {
+        Self {
+            reset: Keybind(vec![KeybindOptions::Keyboard(KeyCode::KeyR)]),
+            perturbed_reset: Keybind(vec![KeybindOptions::Keyboard(KeyCode::KeyP)]),
+            pause: Keybind(vec![KeybindOptions::Keyboard(KeyCode::Space)]),
+            step: Keybind(vec![KeybindOptions::Keyboard(KeyCode::Period)]),
+        }
+    }
+}
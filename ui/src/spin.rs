@@ -0,0 +1,234 @@
+use bevy::{
+    app::{App, Plugin, Update},
+    ecs::{
+        entity::Entity,
+        event::EventReader,
+        query::With,
+        system::{Local, Query, Res, Resource},
+    },
+    input::{
+        keyboard::KeyCode,
+        mouse::{MouseButton, MouseMotion},
+        ButtonInput,
+    },
+    math::{Quat, Vec2, Vec3},
+    render::camera::Camera,
+    time::Time,
+    transform::components::{GlobalTransform, Transform},
+    window::{PrimaryWindow, Window},
+};
+use physics::{
+    components::{velocity::AngularVelocity, Simulated},
+    interpolation::StepTransforms,
+};
+
+use crate::{
+    camera::PrimaryCameraMarker,
+    keybinds::{Keybind, KeybindOptions},
+    measure::pick,
+};
+
+/// Settings for spinning bodies by hand
+#[derive(Resource, Debug)]
+pub struct SpinSettings {
+    /// Grabs the body under the cursor while held, moving the mouse turns it
+    pub grab_key: Keybind,
+
+    /// How far from the center of a body in meters the cursor can point and still grab it
+    pub pick_radius: f32,
+
+    /// Radians the body turns for every pixel the mouse moves
+    pub sensitivity: f32,
+}
+
+/// A body being turned with the mouse
+#[derive(Debug, Clone, Copy)]
+struct Grabbed {
+    body: Entity,
+
+    /// Angular velocity of the drag in the last frame, given to the body when released
+    rate: Vec3,
+}
+
+pub struct SpinPlugin;
+
+/// Angular velocity in rad/s of a body dragged `drag` pixels over `delta` seconds
+///
+/// Turns the side facing the camera along with the mouse, so a horizontal drag spins it about the
+/// up axis of the camera and a vertical one about its right axis. `camera` is the rotation of the
+/// camera. No time passing gives no spin.
+///
+/// ```rust
+/// # use ui::spin::drag_angular_velocity;
+/// # use bevy::math::{Quat, Vec2, Vec3};
+/// // 50 pixels to the right in half a second, at 0.01 radians per pixel
+/// let spin = drag_angular_velocity(Vec2::X * 50.0, 0.5, Quat::IDENTITY, 0.01);
+///
+/// assert_eq!(spin, Vec3::Y);
+/// ```
+#[must_use]
+pub fn drag_angular_velocity(drag: Vec2, delta: f32, camera: Quat, sensitivity: f32) -> Vec3 {
+    if delta <= 0.0 {
+        return Vec3::ZERO;
+    }
+
+    // Window coordinates grow downwards, and dragging down tips the front of the body down
+    camera * Vec3::new(drag.y, drag.x, 0.0) * sensitivity / delta
+}
+
+/// Turns the body grabbed with the grab key along with the mouse
+///
+/// The body is grabbed when the key is pressed with the cursor over it, and held still by the
+/// mouse until released. On release it keeps spinning at the rate of the last frame, so letting
+/// go without moving leaves it still. Only the rotation is touched, a moving body keeps moving
+/// along while it is held.
+#[allow(clippy::too_many_arguments)]
+fn spin_body(
+    mut grabbed: Local<Option<Grabbed>>,
+    time: Res<Time>,
+    kbd: Res<ButtonInput<KeyCode>>,
+    mos: Res<ButtonInput<MouseButton>>,
+    settings: Res<SpinSettings>,
+    mut motion: EventReader<MouseMotion>,
+    window: Query<&Window, With<PrimaryWindow>>,
+    cam: Query<(&Camera, &GlobalTransform), With<PrimaryCameraMarker>>,
+    mut bodies: Query<
+        (
+            Entity,
+            &mut Transform,
+            &mut AngularVelocity,
+            Option<&mut StepTransforms>,
+        ),
+        With<Simulated>,
+    >,
+) {
+    let drag = motion.read().map(|ev| ev.delta).sum::<Vec2>();
+
+    let (Ok(window), Ok((camera, cam_transform))) = (window.get_single(), cam.get_single()) else {
+        return;
+    };
+
+    if settings.grab_key.just_pressed(&kbd, &mos) {
+        let ray = window
+            .cursor_position()
+            .and_then(|cursor| camera.viewport_to_world(cam_transform, cursor));
+        let positions = bodies.iter().map(|(entity, trans, ..)| (entity, trans));
+
+        *grabbed = ray
+            .and_then(|ray| pick(ray, settings.pick_radius, positions))
+            .map(|body| Grabbed {
+                body,
+                rate: Vec3::ZERO,
+            });
+    }
+
+    let Some(grab) = grabbed.as_mut() else {
+        return;
+    };
+
+    // Let go of bodies that were despawned while held
+    let Ok((_, mut trans, mut angvel, steps)) = bodies.get_mut(grab.body) else {
+        *grabbed = None;
+        return;
+    };
+
+    if !settings.grab_key.pressed(&kbd, &mos) {
+        angvel.0 = grab.rate;
+        *grabbed = None;
+        return;
+    }
+
+    let delta = time.delta_seconds();
+    grab.rate = drag_angular_velocity(
+        drag,
+        delta,
+        cam_transform.compute_transform().rotation,
+        settings.sensitivity,
+    );
+    angvel.0 = Vec3::ZERO;
+
+    // Both steps get the new rotation so the interpolation doesn't turn it back, while the
+    // translation is left to the integrator
+    let rotation = Quat::from_scaled_axis(grab.rate * delta) * trans.rotation;
+    trans.rotation = rotation;
+
+    if let Some(mut steps) = steps {
+        steps.previous.rotation = rotation;
+        steps.current.rotation = rotation;
+    }
+}
+
+impl Plugin for SpinPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SpinSettings>();
+        app.add_systems(Update, spin_body);
+    }
+}
+
+impl Default for SpinSettings {
+    fn default() -> Self {
+        Self {
+            grab_key: Keybind(vec![KeybindOptions::Keyboard(KeyCode::KeyG)]),
+            pick_radius: 1.0,
+            sensitivity: 0.01,
+        }
+    }
+}
+
+#[cfg(test)]
+mod drag {
+    use std::f32::consts::FRAC_PI_2;
+
+    use bevy::math::{Quat, Vec2, Vec3};
+
+    use super::drag_angular_velocity;
+
+    fn assert_near(a: Vec3, b: Vec3) {
+        assert!(a.abs_diff_eq(b, 1e-5), "{a} isn't {b}");
+    }
+
+    #[test]
+    fn screen_axes() {
+        // Right spins about up, down about right, both faster the shorter the frame
+        assert_near(
+            drag_angular_velocity(Vec2::X * 10.0, 0.1, Quat::IDENTITY, 0.01),
+            Vec3::Y,
+        );
+        assert_near(
+            drag_angular_velocity(Vec2::Y * 10.0, 0.05, Quat::IDENTITY, 0.01),
+            Vec3::X * 2.0,
+        );
+
+        // The side facing the camera moves along with the mouse
+        let spin = drag_angular_velocity(Vec2::new(30.0, 40.0), 1.0, Quat::IDENTITY, 1.0);
+        let front = spin.cross(Vec3::Z);
+        assert!(front.x > 0.0 && front.y < 0.0);
+    }
+
+    #[test]
+    fn turned_camera() {
+        // Looking straight down, so the up axis of the screen is -z
+        let camera = Quat::from_rotation_x(-FRAC_PI_2);
+
+        assert_near(
+            drag_angular_velocity(Vec2::X * 10.0, 0.1, camera, 0.01),
+            Vec3::NEG_Z,
+        );
+        assert_near(
+            drag_angular_velocity(Vec2::Y * 10.0, 0.1, camera, 0.01),
+            Vec3::X,
+        );
+    }
+
+    #[test]
+    fn still() {
+        assert_eq!(
+            drag_angular_velocity(Vec2::ZERO, 0.1, Quat::IDENTITY, 0.01),
+            Vec3::ZERO
+        );
+        assert_eq!(
+            drag_angular_velocity(Vec2::X * 10.0, 0.0, Quat::IDENTITY, 0.01),
+            Vec3::ZERO
+        );
+    }
+}
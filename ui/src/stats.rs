@@ -0,0 +1,201 @@
+use bevy::{
+    app::{App, Plugin, Startup, Update},
+    ecs::{
+        component::Component,
+        query::With,
+        schedule::IntoSystemConfigs,
+        system::{Commands, Query, Res, Resource},
+    },
+    input::{keyboard::KeyCode, mouse::MouseButton, ButtonInput},
+    math::Vec3,
+    render::view::Visibility,
+    text::{Text, TextStyle},
+    ui::{node_bundles::TextBundle, PositionType, Style, Val},
+    utils::default,
+};
+use physics::{
+    analysis::SceneStats,
+    resources::{CentralBody, PhysicsSettings, UpAxis},
+};
+
+use crate::keybinds::{Keybind, KeybindOptions};
+
+/// Keybinds used for the stats panel
+#[derive(Resource, Debug)]
+pub struct StatsKeybinds {
+    /// Shows or hides the panel
+    pub toggle: Keybind,
+}
+
+/// Marks the text showing the [SceneStats]
+#[derive(Component, Debug)]
+pub struct StatsPanel;
+
+pub struct StatsPlugin;
+
+/// Formats the numbers shown in the stats panel, one per line with two decimals
+///
+/// The central body is only listed when there is one.
+#[must_use]
+pub fn format_stats(stats: &SceneStats, gravity: Vec3, central: Option<&CentralBody>) -> String {
+    let mut text = format!(
+        "Bodies: {}\nKinetic energy: {:.2} J\nGravity: {:.2} m/s^2",
+        stats.bodies,
+        stats.kinetic_energy,
+        gravity.length()
+    );
+
+    if let Some(central) = central {
+        text += &format!("\nCentral body: mu = {:.2}", central.mu);
+    }
+
+    text
+}
+
+/// Spawns the panel in the top left corner, hidden until toggled
+fn spawn(mut cmds: Commands) {
+    let text = TextBundle::from_section(
+        "",
+        TextStyle {
+            font_size: 18.0,
+            ..default()
+        },
+    )
+    .with_style(Style {
+        position_type: PositionType::Absolute,
+        top: Val::Px(10.0),
+        left: Val::Px(10.0),
+        ..default()
+    });
+
+    cmds.spawn((
+        TextBundle {
+            visibility: Visibility::Hidden,
+            ..text
+        },
+        StatsPanel,
+    ));
+}
+
+/// Shows or hides the panel when the keybind is pressed
+fn toggle(
+    kbd: Res<ButtonInput<KeyCode>>,
+    mos: Res<ButtonInput<MouseButton>>,
+    binds: Res<StatsKeybinds>,
+    mut panels: Query<&mut Visibility, With<StatsPanel>>,
+) {
+    if !binds.toggle.just_pressed(&kbd, &mos) {
+        return;
+    }
+
+    for mut visibility in panels.iter_mut() {
+        *visibility = match *visibility {
+            Visibility::Hidden => Visibility::Visible,
+            _ => Visibility::Hidden,
+        };
+    }
+}
+
+/// Writes the current [SceneStats] into the panel
+fn update_panel(
+    stats: Res<SceneStats>,
+    settings: Res<PhysicsSettings>,
+    up: Res<UpAxis>,
+    central: Option<Res<CentralBody>>,
+    mut panels: Query<&mut Text, With<StatsPanel>>,
+) {
+    let gravity = settings.gravity_for(*up).0;
+    let text = format_stats(&stats, gravity, central.as_deref());
+
+    for mut panel in panels.iter_mut() {
+        panel.sections[0].value.clone_from(&text);
+    }
+}
+
+impl Plugin for StatsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<StatsKeybinds>();
+        app.add_systems(Startup, spawn);
+        app.add_systems(Update, (toggle, update_panel).chain());
+    }
+}
+
+impl Default for StatsKeybinds {
+    fn default() -> Self {
+        Self {
+            toggle: Keybind(vec![KeybindOptions::Keyboard(KeyCode::F3)]),
+        }
+    }
+}
+
+#[cfg(test)]
+mod panel {
+    use bevy::{
+        app::App,
+        ecs::query::With,
+        input::{keyboard::KeyCode, mouse::MouseButton, ButtonInput},
+        math::Vec3,
+        render::view::Visibility,
+        text::Text,
+    };
+    use physics::{
+        analysis::SceneStats,
+        resources::{CentralBody, PhysicsSettings, UpAxis},
+    };
+
+    use super::{format_stats, StatsPanel, StatsPlugin};
+
+    #[test]
+    fn formatting() {
+        let stats = SceneStats {
+            bodies: 3,
+            kinetic_energy: 1234.5678,
+            ..Default::default()
+        };
+
+        assert_eq!(
+            format_stats(&stats, Vec3::Y * -9.82, None),
+            "Bodies: 3\nKinetic energy: 1234.57 J\nGravity: 9.82 m/s^2"
+        );
+
+        let central = CentralBody {
+            mu: 398.6,
+            position: Vec3::ZERO,
+        };
+        assert_eq!(
+            format_stats(&SceneStats::default(), Vec3::ZERO, Some(&central)),
+            "Bodies: 0\nKinetic energy: 0.00 J\nGravity: 0.00 m/s^2\nCentral body: mu = 398.60"
+        );
+    }
+
+    #[test]
+    fn toggled_live() {
+        let mut app = App::new();
+        app.init_resource::<ButtonInput<KeyCode>>();
+        app.init_resource::<ButtonInput<MouseButton>>();
+        app.init_resource::<PhysicsSettings>();
+        app.init_resource::<UpAxis>();
+        app.insert_resource(SceneStats {
+            bodies: 2,
+            ..Default::default()
+        });
+        app.add_plugins(StatsPlugin);
+
+        app.update();
+
+        let mut panels = app
+            .world_mut()
+            .query_filtered::<(&Text, &Visibility), With<StatsPanel>>();
+        let (text, visibility) = panels.single(app.world());
+        assert!(text.sections[0].value.starts_with("Bodies: 2\n"));
+        assert_eq!(visibility, Visibility::Hidden);
+
+        app.world_mut()
+            .resource_mut::<ButtonInput<KeyCode>>()
+            .press(KeyCode::F3);
+        app.update();
+
+        let (_, visibility) = panels.single(app.world());
+        assert_eq!(visibility, Visibility::Visible);
+    }
+}